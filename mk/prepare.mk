@@ -72,6 +72,13 @@ endef
 
 PREPARE_TOOLS = $(filter-out compiletest, $(TOOLS))
 
+# The debugger pretty printers go to lib/rustlib/etc, where the rust-gdb and
+# rust-lldb wrappers look for them
+DEBUGGER_SCRIPTS = gdb_load_rust_pretty_printers.py \
+                   gdb_rust_pretty_printing.py \
+                   lldb_rust_formatters.py
+DEBUGGER_BIN_SCRIPTS = rust-gdb rust-lldb
+
 
 # $(1) is tool
 # $(2) is stage
@@ -141,7 +148,10 @@ prepare-target-$(2)-host-$(3)-$(1)-$(4): prepare-maybe-clean-$(4) \
             $$(foreach crate,$$(HOST_CRATES), \
               $$(call PREPARE_LIB,$$(call CFG_LIB_GLOB_$(2),$$(crate)))),) \
           $$(call PREPARE_LIB,libmorestack.a) \
-          $$(call PREPARE_LIB,libcompiler-rt.a),),),)
+          $$(call PREPARE_LIB,libcompiler-rt.a) \
+          $$(call PREPARE_LIB,libprofiler-rt.a) \
+          $$(foreach san,$$(SANITIZERS_$(2)), \
+            $$(call PREPARE_LIB,lib$$(san)-rt.a)),),),)
 endef
 
 define DEF_PREPARE
@@ -157,13 +167,22 @@ prepare-base-$(1): prepare-everything-$(1)
 
 prepare-everything-$(1): prepare-host-$(1) prepare-targets-$(1)
 
-prepare-host-$(1): prepare-host-tools-$(1)
+prepare-host-$(1): prepare-host-tools-$(1) prepare-debugger-scripts-$(1)
 
 prepare-host-tools-$(1): \
         $$(foreach tool, $$(PREPARE_TOOLS), \
           $$(foreach host,$$(CFG_HOST), \
             prepare-host-tool-$$(tool)-$$(PREPARE_STAGE)-$$(host)-$(1)))
 
+prepare-debugger-scripts-$(1): prepare-host-dirs-$(1)
+	$$(call PREPARE_DIR,$$(PREPARE_DEST_LIB_DIR)/rustlib/etc)
+	$$(Q)for script in $$(DEBUGGER_SCRIPTS); do \
+	  $$(PREPARE_LIB_CMD) $$(S)src/etc/$$$$script $$(PREPARE_DEST_LIB_DIR)/rustlib/etc/; \
+	done
+	$$(Q)for script in $$(DEBUGGER_BIN_SCRIPTS); do \
+	  $$(PREPARE_BIN_CMD) $$(S)src/etc/$$$$script $$(PREPARE_DEST_BIN_DIR)/; \
+	done
+
 prepare-host-dirs-$(1): prepare-maybe-clean-$(1)
 	$$(call PREPARE_DIR,$$(PREPARE_DEST_BIN_DIR))
 	$$(call PREPARE_DIR,$$(PREPARE_DEST_LIB_DIR))
@@ -235,6 +235,77 @@ $$(COMPRT_LIB_$(1)): $$(COMPRT_DEPS) $$(MKFILE_DEPS)
 		triple-builtins
 	$$(Q)cp $$(COMPRT_BUILD_DIR_$(1))/triple/builtins/libcompiler_rt.a $$(COMPRT_LIB_$(1))
 
+################################################################################
+# compiler-rt's profiling runtime
+#
+# Programs built with `-C profile-generate` or `-Z coverage` link against
+# this to write out their profile or gcov data. compiler-rt's makefiles
+# don't build it for the `triple` platform, so we build it ourselves.
+################################################################################
+
+PROFRT_NAME_$(1) := $$(call CFG_STATIC_LIB_NAME_$(1),profiler-rt)
+PROFRT_LIB_$(1) := $$(RT_OUTPUT_DIR_$(1))/$$(PROFRT_NAME_$(1))
+PROFRT_BUILD_DIR_$(1) := $$(RT_OUTPUT_DIR_$(1))/profiler-rt
+PROFRT_OBJS_$(1) := $$(foreach f,GCDAProfiling \
+				  InstrProfiling \
+				  InstrProfilingBuffer \
+				  InstrProfilingFile \
+				  InstrProfilingPlatformDarwin \
+				  InstrProfilingPlatformLinux \
+				  InstrProfilingPlatformOther \
+				  InstrProfilingRuntime, \
+			$$(PROFRT_BUILD_DIR_$(1))/$$(f).o)
+
+$$(PROFRT_BUILD_DIR_$(1))/%.o: $(S)src/compiler-rt/lib/profile/%.c $$(MKFILE_DEPS)
+	@mkdir -p $$(@D)
+	@$$(call E, compile: $$@)
+	$$(Q)$$(call CFG_COMPILE_C_$(1), $$@,) $$<
+
+$$(PROFRT_BUILD_DIR_$(1))/%.o: $(S)src/compiler-rt/lib/profile/%.cc $$(MKFILE_DEPS)
+	@mkdir -p $$(@D)
+	@$$(call E, compile: $$@)
+	$$(Q)$$(call CFG_COMPILE_CXX_$(1), $$@,) $$<
+
+$$(PROFRT_LIB_$(1)): $$(PROFRT_OBJS_$(1))
+	@$$(call E, link: $$@)
+	$$(Q)$$(AR_$(1)) rcs $$@ $$^
+
+################################################################################
+# compiler-rt's sanitizer runtimes
+#
+# Programs built with `-Z sanitizer` link against the runtime of their
+# sanitizer, named after compiler-rt's abbreviation for it (e.g. asan-rt).
+# compiler-rt only has these for 64-bit Linux, where its `clang_linux`
+# platform builds all of them, one configuration each. Building them takes a
+# while, so they are only built when configured with --enable-sanitizers.
+################################################################################
+
+SANITIZERS_$(1) :=
+ifdef CFG_ENABLE_SANITIZERS
+ifeq ($(1),x86_64-unknown-linux-gnu)
+SANITIZERS_$(1) := asan tsan msan
+endif
+endif
+
+SANRT_BUILD_DIR_$(1) := $$(RT_OUTPUT_DIR_$(1))/sanitizer-rt
+SANRT_LIBS_$(1) := $$(foreach san,$$(SANITIZERS_$(1)), \
+			$$(RT_OUTPUT_DIR_$(1))/$$(call CFG_STATIC_LIB_NAME_$(1),$$(san)-rt))
+
+$$(SANRT_BUILD_DIR_$(1))/stamp: $$(COMPRT_DEPS) $$(MKFILE_DEPS)
+	@$$(call E, make: compiler-rt sanitizers)
+	$$(Q)$$(MAKE) -C "$(S)src/compiler-rt" \
+		ProjSrcRoot="$(S)src/compiler-rt" \
+		ProjObjRoot="$$(abspath $$(SANRT_BUILD_DIR_$(1)))" \
+		CC="$$(CC_$(1))" \
+		AR="$$(AR_$(1))" \
+		RANLIB="$$(AR_$(1)) s" \
+		clang_linux
+	$$(Q)touch $$@
+
+$$(SANRT_LIBS_$(1)): $$(RT_OUTPUT_DIR_$(1))/$$(call CFG_STATIC_LIB_NAME_$(1),%-rt): \
+		$$(SANRT_BUILD_DIR_$(1))/stamp
+	$$(Q)cp $$(SANRT_BUILD_DIR_$(1))/clang_linux/$$*-x86_64/libcompiler_rt.a $$@
+
 ################################################################################
 # libbacktrace
 #
@@ -0,0 +1,119 @@
+# Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+# file at the top-level directory of this distribution and at
+# http://rust-lang.org/COPYRIGHT.
+#
+# Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+# http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+# <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+# option. This file may not be copied, modified, or distributed
+# except according to those terms.
+
+######################################################################
+# Standard libraries instrumented with the sanitizers
+#
+# `-Z sanitizer` only checks the code it compiles, and the memory
+# sanitizer reports every read of memory that uninstrumented code
+# initialized. `make sanitizers` therefore builds all of the target
+# crates once per sanitizer with the stage2 compiler, each set into a
+# sysroot of its own:
+#
+#   $(CFG_BUILD)/sanitizers/<sanitizer>/lib/rustlib/<target>/lib
+#
+# Programs pick one of them with `--sysroot`, e.g.
+#
+#   rustc -Z sanitizer=thread \
+#       --sysroot x86_64-unknown-linux-gnu/sanitizers/thread foo.rs
+#
+# Only targets with the sanitizer runtimes get these, and rt.mk only
+# builds those when configured with --enable-sanitizers. The
+# run-make tests of the sanitizers use the sysroots of $(CFG_BUILD)
+# when they have been built.
+######################################################################
+
+ifndef CFG_ENABLE_SANITIZERS
+$(error the sanitizer runtimes are not built; configure with --enable-sanitizers)
+endif
+
+SANITIZER_address := asan
+SANITIZER_thread := tsan
+SANITIZER_memory := msan
+
+# The sanitizers find out about heap memory by intercepting malloc and
+# friends, which jemalloc doesn't go through, so liballoc uses the
+# system allocator in these sysroots.
+SANITIZED_RUSTFLAGS_alloc :=
+
+SANITIZED_TARGETS := $(foreach target,$(CFG_TARGET), \
+                       $(if $(SANITIZERS_$(target)),$(target),))
+
+# $(1) is the sanitizer
+# $(2) is the target triple
+define SANITIZED_SYSROOT
+
+SANLIB_$(1)_T_$(2) := $(CFG_BUILD)/sanitizers/$(1)/$$(CFG_LIBDIR_RELATIVE)/rustlib/$(2)/lib
+
+$$(SANLIB_$(1)_T_$(2))/:
+	mkdir -p $$@
+
+# The linker looks for the native runtimes in the sysroot too
+$$(foreach lib,morestack compiler-rt $$(SANITIZER_$(1))-rt, \
+    $$(SANLIB_$(1)_T_$(2))/lib$$(lib).a): \
+	    $$(SANLIB_$(1)_T_$(2))/%: \
+	    $$(TLIB2_T_$(2)_H_$(CFG_BUILD))/% \
+	    | $$(SANLIB_$(1)_T_$(2))/
+	@$$(call E, cp: $$@)
+	$$(Q)cp $$< $$@
+
+sanitizers-$(1)-T-$(2): \
+	$$(foreach crate,$$(TARGET_CRATES),$$(SANLIB_$(1)_T_$(2))/stamp.$$(crate))
+
+endef
+
+# $(1) is the sanitizer
+# $(2) is the target triple
+# $(3) is the crate
+define SANITIZED_CRATE
+
+SANITIZED_RUSTFLAGS_$(3) ?= $$(RUSTFLAGS_$(3))
+
+$$(SANLIB_$(1)_T_$(2))/stamp.$(3): \
+		$$(CRATEFILE_$(3)) \
+		$$(RSINPUTS_$(3)) \
+		$$(foreach dep,$$(RUST_DEPS_$(3)), \
+		  $$(SANLIB_$(1)_T_$(2))/stamp.$$(dep)) \
+		$$(foreach dep,$$(NATIVE_DEPS_$(3)), \
+		  $$(RT_OUTPUT_DIR_$(2))/$$(call CFG_STATIC_LIB_NAME_$(2),$$(dep))) \
+		$$(foreach lib,morestack compiler-rt $$(SANITIZER_$(1))-rt, \
+		  $$(SANLIB_$(1)_T_$(2))/lib$$(lib).a) \
+		$$(TSREQ2_T_$(2)_H_$(CFG_BUILD)) \
+		| $$(SANLIB_$(1)_T_$(2))/
+	@$$(call E, rustc: $$(@D)/lib$(3))
+	$$(call REMOVE_ALL_OLD_GLOB_MATCHES, \
+	    $$(dir $$@)$$(call CFG_LIB_GLOB_$(2),$(3)))
+	$$(call REMOVE_ALL_OLD_GLOB_MATCHES, \
+	    $$(dir $$@)$$(call CFG_RLIB_GLOB,$(3)))
+	$$(STAGE2_T_$(2)_H_$(CFG_BUILD)) \
+		--sysroot $(CFG_BUILD)/sanitizers/$(1) \
+		-Z sanitizer=$(1) \
+		-L "$$(RT_OUTPUT_DIR_$(2))" \
+		$$(SANITIZED_RUSTFLAGS_$(3)) \
+		--out-dir $$(@D) \
+		-C extra-filename=-$$(CFG_FILENAME_EXTRA) \
+		$$<
+	@touch $$@
+
+endef
+
+$(foreach san,address thread memory, \
+ $(foreach target,$(SANITIZED_TARGETS), \
+  $(eval $(call SANITIZED_SYSROOT,$(san),$(target))) \
+  $(foreach crate,$(TARGET_CRATES), \
+   $(eval $(call SANITIZED_CRATE,$(san),$(target),$(crate))))))
+
+.PHONY: sanitizers sanitizers-address sanitizers-thread sanitizers-memory
+
+sanitizers: sanitizers-address sanitizers-thread sanitizers-memory
+
+$(foreach san,address thread memory, \
+ $(eval sanitizers-$(san): \
+   $(foreach target,$(SANITIZED_TARGETS),sanitizers-$(san)-T-$(target))))
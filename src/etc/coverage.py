@@ -0,0 +1,217 @@
+#!/usr/bin/env python
+#
+# Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+# file at the top-level directory of this distribution and at
+# http://rust-lang.org/COPYRIGHT.
+#
+# Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+# http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+# <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+# option. This file may not be copied, modified, or distributed
+# except according to those terms.
+
+"""
+Turns the counts written by a program compiled with `-Z coverage` into a
+line coverage report.
+
+Running an instrumented program leaves a `.gcda` file next to each of the
+`.gcno` files rustc wrote beside the crate's object file. This script runs
+`gcov` over them, maps the counts back to the Rust sources they came from,
+and writes an LCOV tracefile (for genhtml, IDEs and CI services) and/or a
+plain HTML report:
+
+    $ rustc --test -Z coverage foo.rs
+    $ ./foo
+    $ python coverage.py --lcov foo.info --html foo-coverage .
+
+Counts from all the given directories are summed, so running several test
+binaries before generating the report gives their combined coverage.
+"""
+
+import argparse
+import cgi
+import os
+import shutil
+import subprocess
+import sys
+import tempfile
+
+
+def find_gcda(paths):
+    for path in paths:
+        if os.path.isfile(path):
+            yield os.path.abspath(path)
+            continue
+        for dirpath, _, filenames in os.walk(path):
+            for f in filenames:
+                if f.endswith('.gcda'):
+                    yield os.path.abspath(os.path.join(dirpath, f))
+
+
+def parse_gcov(path, root, counts):
+    """Adds the counts in the gcov output file `path` to `counts`, a map
+    from source file to a map from line number to execution count."""
+    source = None
+    with open(path) as f:
+        for line in f:
+            parts = line.split(':', 2)
+            if len(parts) != 3:
+                continue
+            count, lineno, text = [p.strip() for p in parts]
+            lineno = int(lineno)
+            if lineno == 0:
+                if count == '-' and text.startswith('Source:'):
+                    source = os.path.normpath(os.path.join(root, text[len('Source:'):]))
+                continue
+            # Code from other crates, e.g. inlined generics from the standard
+            # library, refers to sources which aren't around.
+            if source is None or not source.endswith('.rs') or not os.path.isfile(source):
+                return
+            if count == '-':
+                continue
+            if count in ('#####', '====='):
+                n = 0
+            else:
+                n = int(count.rstrip('*'))
+            lines = counts.setdefault(source, {})
+            lines[lineno] = lines.get(lineno, 0) + n
+
+
+def collect(gcov, root, paths):
+    counts = {}
+    tmp = tempfile.mkdtemp()
+    try:
+        for gcda in find_gcda(paths):
+            # gcov writes its output to the current directory, one file per
+            # source; `-p` keeps sources with the same name apart.
+            objdir = os.path.dirname(gcda)
+            ret = subprocess.call([gcov, '-p', '-o', objdir, gcda], cwd=tmp,
+                                  stdout=open(os.devnull, 'w'))
+            if ret != 0:
+                sys.stderr.write('warning: `%s` failed on %s\n' % (gcov, gcda))
+            for f in os.listdir(tmp):
+                if f.endswith('.gcov'):
+                    parse_gcov(os.path.join(tmp, f), root, counts)
+                os.remove(os.path.join(tmp, f))
+    finally:
+        shutil.rmtree(tmp)
+    return counts
+
+
+def summary(lines):
+    return len(lines), len([n for n in lines.values() if n > 0])
+
+
+def percent(found, hit):
+    if found == 0:
+        return 100.0
+    return 100.0 * hit / found
+
+
+def write_lcov(counts, out):
+    with open(out, 'w') as f:
+        f.write('TN:\n')
+        for source in sorted(counts):
+            lines = counts[source]
+            f.write('SF:%s\n' % source)
+            for lineno in sorted(lines):
+                f.write('DA:%d,%d\n' % (lineno, lines[lineno]))
+            found, hit = summary(lines)
+            f.write('LF:%d\nLH:%d\nend_of_record\n' % (found, hit))
+
+
+STYLE = """
+body { font-family: sans-serif; }
+table { border-collapse: collapse; }
+td, th { padding: 0 0.5em; text-align: left; }
+pre { margin: 0; }
+.hit { background-color: #cfc; }
+.miss { background-color: #fcc; }
+.count { text-align: right; color: #666; }
+"""
+
+
+def page(title, body):
+    return ('<!DOCTYPE html>\n<html><head><meta charset="utf-8">'
+            '<title>%s</title><style>%s</style></head>\n<body>\n<h1>%s</h1>\n'
+            '%s</body></html>\n') % (cgi.escape(title), STYLE, cgi.escape(title), body)
+
+
+def write_html(counts, root, out):
+    if not os.path.isdir(out):
+        os.makedirs(out)
+    rows = []
+    total_found, total_hit = 0, 0
+    for i, source in enumerate(sorted(counts)):
+        lines = counts[source]
+        found, hit = summary(lines)
+        total_found += found
+        total_hit += hit
+        name = os.path.relpath(source, root)
+        html = '%d.html' % i
+        rows.append('<tr><td><a href="%s">%s</a></td><td>%d / %d</td><td>%.1f%%</td></tr>'
+                    % (html, cgi.escape(name), hit, found, percent(found, hit)))
+
+        body = ['<p>%d of %d lines run (%.1f%%)</p>' % (hit, found, percent(found, hit)),
+                '<table>']
+        with open(source) as f:
+            for lineno, text in enumerate(f, 1):
+                n = lines.get(lineno)
+                if n is None:
+                    cls, count = '', ''
+                else:
+                    cls, count = 'hit' if n > 0 else 'miss', str(n)
+                body.append('<tr class="%s"><td class="count">%d</td>'
+                            '<td class="count">%s</td><td><pre>%s</pre></td></tr>'
+                            % (cls, lineno, count, cgi.escape(text.rstrip('\n'))))
+        body.append('</table>')
+        with open(os.path.join(out, html), 'w') as f:
+            f.write(page(name, '\n'.join(body)))
+
+    body = ['<p>%d of %d lines run (%.1f%%)</p>'
+            % (total_hit, total_found, percent(total_found, total_hit)),
+            '<table>',
+            '<tr><th>File</th><th>Lines</th><th>Coverage</th></tr>']
+    body.extend(rows)
+    body.append('</table>')
+    with open(os.path.join(out, 'index.html'), 'w') as f:
+        f.write(page('Coverage report', '\n'.join(body)))
+
+
+def main():
+    parser = argparse.ArgumentParser(
+        description='Generate a line coverage report for a program compiled '
+                    'with `rustc -Z coverage`.')
+    parser.add_argument('paths', nargs='+', metavar='PATH',
+                        help='.gcda files, or directories to search for them')
+    parser.add_argument('--gcov', default='gcov',
+                        help='the gcov to run (default: gcov)')
+    parser.add_argument('--root', default='.',
+                        help='the directory rustc was run from, which relative '
+                             'source paths are resolved against (default: .)')
+    parser.add_argument('--lcov', metavar='FILE', help='write an LCOV tracefile')
+    parser.add_argument('--html', metavar='DIR', help='write an HTML report')
+    args = parser.parse_args()
+
+    root = os.path.abspath(args.root)
+    counts = collect(args.gcov, root, args.paths)
+    if not counts:
+        sys.stderr.write('error: no coverage data found\n')
+        return 1
+
+    if args.lcov:
+        write_lcov(counts, args.lcov)
+    if args.html:
+        write_html(counts, root, args.html)
+
+    found, hit = 0, 0
+    for lines in counts.values():
+        f, h = summary(lines)
+        found += f
+        hit += h
+    print('%d of %d lines run (%.1f%%)' % (hit, found, percent(found, hit)))
+    return 0
+
+
+if __name__ == '__main__':
+    sys.exit(main())
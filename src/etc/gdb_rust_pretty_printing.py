@@ -28,6 +28,30 @@ def rust_pretty_printer_lookup_function(val):
     if struct_kind == STRUCT_KIND_STR_SLICE:
       return RustStringSlicePrinter(val)
 
+    if struct_kind == STRUCT_KIND_SLICE:
+      return RustSlicePrinter(val)
+
+    if struct_kind == STRUCT_KIND_STD_VEC:
+      return RustStdVecPrinter(val)
+
+    if struct_kind == STRUCT_KIND_STD_STRING:
+      return RustStdStringPrinter(val)
+
+    if struct_kind == STRUCT_KIND_STD_HASHMAP:
+      return RustStdHashMapPrinter(val)
+
+    if struct_kind == STRUCT_KIND_STD_RC:
+      return RustStdRcPrinter(val, "value")
+
+    if struct_kind == STRUCT_KIND_STD_ARC:
+      return RustStdRcPrinter(val, "data")
+
+    if struct_kind == STRUCT_KIND_STD_TREEMAP:
+      return RustStdTreeMapPrinter(val)
+
+    if struct_kind == STRUCT_KIND_STD_RINGBUF:
+      return RustStdRingBufPrinter(val)
+
     if struct_kind == STRUCT_KIND_TUPLE:
       return RustTuplePrinter(val)
 
@@ -51,31 +75,29 @@ def rust_pretty_printer_lookup_function(val):
     enum_member_count = len(enum_members)
 
     if enum_member_count == 0:
-      return RustStructPrinter(val, false)
+      return RustStructPrinter(val, False)
 
     if enum_member_count == 1:
-      if enum_members[0].name == None:
+      first_variant_name = enum_members[0].name
+      if first_variant_name == None:
         # This is a singleton enum
         return rust_pretty_printer_lookup_function(val[enum_members[0]])
       else:
-        assert enum_members[0].name.startswith("RUST$ENCODED$ENUM$")
+        assert first_variant_name.startswith(ENCODED_ENUM_PREFIX)
         # This is a space-optimized enum
-        last_separator_index = enum_members[0].name.rfind("$")
-        second_last_separator_index = first_variant_name.rfind("$", 0, last_separator_index)
-        disr_field_index = first_variant_name[second_last_separator_index + 1 :
-                                              last_separator_index]
-        disr_field_index = int(disr_field_index)
-
         sole_variant_val = val[enum_members[0]]
-        disr_field = get_field_at_index(sole_variant_val, disr_field_index)
-        discriminant = int(sole_variant_val[disr_field])
 
-        if discriminant == 0:
+        if encoded_enum_has_null_variant_value(sole_variant_val, first_variant_name):
+          last_separator_index = first_variant_name.rfind("$")
           null_variant_name = first_variant_name[last_separator_index + 1:]
           return IdentityPrinter(null_variant_name)
 
         return rust_pretty_printer_lookup_function(sole_variant_val)
 
+    niche_variant_index = niche_filled_enum_variant_index(val)
+    if niche_variant_index != None:
+      return rust_pretty_printer_lookup_function(val[enum_members[niche_variant_index]])
+
     # This is a regular enum, extract the discriminant
     discriminant_name, discriminant_val = extract_discriminant_value(val)
     return rust_pretty_printer_lookup_function(val[enum_members[discriminant_val]])
@@ -163,6 +185,158 @@ class RustStringSlicePrinter:
     return '"%s"' % self.val["data_ptr"].string(encoding = "utf-8",
                                                 length = slice_byte_len)
 
+class RustSlicePrinter:
+  def __init__(self, val):
+    self.val = val
+
+  def to_string(self):
+    return "%s(len: %d)" % (self.val.type.tag, int(self.val["length"]))
+
+  def children(self):
+    return pointer_range_children(self.val["data_ptr"], int(self.val["length"]))
+
+  def display_hint(self):
+    return "array"
+
+class RustStdVecPrinter:
+  def __init__(self, val):
+    self.val = val
+
+  def to_string(self):
+    return "%s(len: %d, cap: %d)" % (self.val.type.tag,
+                                     int(self.val["len"]),
+                                     int(self.val["cap"]))
+
+  def children(self):
+    return pointer_range_children(self.val["ptr"], int(self.val["len"]))
+
+  def display_hint(self):
+    return "array"
+
+class RustStdStringPrinter:
+  def __init__(self, val):
+    self.val = val
+
+  def to_string(self):
+    vec = self.val["vec"]
+    return vec["ptr"].string(encoding = "utf-8", length = int(vec["len"]))
+
+  def display_hint(self):
+    return "string"
+
+class RustStdHashMapPrinter:
+  def __init__(self, val):
+    self.val = val
+
+  def to_string(self):
+    return "%s(len: %d)" % (self.val.type.tag, int(self.val["table"]["size"]))
+
+  def children(self):
+    table = self.val["table"]
+    table_type = table.type.strip_typedefs()
+    capacity = int(table["capacity"])
+    hashes = table["hashes"]
+
+    # The keys and values aren't mentioned in the field types of the table,
+    # so their types have to come from its template parameters
+    key_type = table_type.template_argument(0)
+    value_type = table_type.template_argument(1)
+
+    # This mirrors calculate_offsets() in libstd/collections/hashmap/table.rs:
+    # the hashes, keys and values are stored one after the other in a single
+    # allocation
+    keys_offset = round_up(capacity * hashes.type.target().sizeof,
+                           type_alignment(key_type))
+    values_offset = round_up(keys_offset + capacity * key_type.sizeof,
+                             type_alignment(value_type))
+
+    hashes_address = int(hashes)
+    keys = gdb.Value(hashes_address + keys_offset).cast(key_type.pointer())
+    values = gdb.Value(hashes_address + values_offset).cast(value_type.pointer())
+
+    cs = []
+    for index in range(capacity):
+      # A hash of zero marks an empty bucket
+      if int((hashes + index).dereference()) == 0:
+        continue
+      cs.append( ("[%d]" % len(cs), (keys + index).dereference()) )
+      cs.append( ("[%d]" % len(cs), (values + index).dereference()) )
+
+    return cs
+
+  def display_hint(self):
+    return "map"
+
+class RustStdRcPrinter:
+  def __init__(self, val, value_field_name):
+    self.val = val
+    self.value_field_name = value_field_name
+
+  def to_string(self):
+    box = self.val["_ptr"].dereference()
+    return "%s(strong: %d, weak: %d)" % (self.val.type.tag,
+                                         counter_value(box["strong"]),
+                                         counter_value(box["weak"]))
+
+  def children(self):
+    box = self.val["_ptr"].dereference()
+    return [ ("", box[self.value_field_name]) ]
+
+  def display_hint(self):
+    return "array"
+
+class RustStdTreeMapPrinter:
+  def __init__(self, val):
+    self.val = val
+
+  def to_string(self):
+    return "%s(len: %d)" % (self.val.type.tag, int(self.val["length"]))
+
+  def children(self):
+    cs = []
+    # Walk the tree in order, i.e. sorted by key
+    stack = []
+    node = get_option_payload(self.val["root"])
+    while node is not None or len(stack) > 0:
+      if node is not None:
+        node = node.dereference()
+        stack.append(node)
+        node = get_option_payload(node["left"])
+      else:
+        node = stack.pop()
+        cs.append( ("[%d]" % len(cs), node["key"]) )
+        cs.append( ("[%d]" % len(cs), node["value"]) )
+        node = get_option_payload(node["right"])
+
+    return cs
+
+  def display_hint(self):
+    return "map"
+
+class RustStdRingBufPrinter:
+  def __init__(self, val):
+    self.val = val
+
+  def to_string(self):
+    return "%s(len: %d)" % (self.val.type.tag, int(self.val["nelts"]))
+
+  def children(self):
+    elts = self.val["elts"]
+    elts_len = int(elts["len"])
+    lo = int(self.val["lo"])
+
+    cs = []
+    for index in range(int(self.val["nelts"])):
+      # The elements wrap around the end of the underlying vector
+      raw_index = (lo + index) % elts_len
+      element = get_option_payload((elts["ptr"] + raw_index).dereference())
+      cs.append( ("", element) )
+
+    return cs
+
+  def display_hint(self):
+    return "array"
+
 class RustCStyleEnumPrinter:
   def __init__(self, val):
     assert val.type.code == gdb.TYPE_CODE_ENUM
@@ -173,7 +347,7 @@ class RustCStyleEnumPrinter:
 
 class IdentityPrinter:
   def __init__(self, string):
-    self.string
+    self.string = string
 
   def to_string(self):
     return self.string
@@ -185,6 +359,33 @@ STRUCT_KIND_TUPLE_VARIANT   = 3
 STRUCT_KIND_STRUCT_VARIANT  = 4
 STRUCT_KIND_CSTYLE_VARIANT  = 5
 STRUCT_KIND_STR_SLICE       = 6
+STRUCT_KIND_SLICE           = 7
+STRUCT_KIND_STD_VEC         = 8
+STRUCT_KIND_STD_STRING      = 9
+STRUCT_KIND_STD_HASHMAP     = 10
+STRUCT_KIND_STD_RC          = 11
+STRUCT_KIND_STD_ARC         = 12
+STRUCT_KIND_STD_TREEMAP     = 13
+STRUCT_KIND_STD_RINGBUF     = 14
+
+# The prefix of the name rustc gives the sole member of space-optimized enums
+ENCODED_ENUM_PREFIX = "RUST$ENCODED$ENUM$"
+
+# The prefix of the name rustc gives the member for the dataful variant of
+# enums keeping their discriminant in a niche of that variant
+ENCODED_NICHE_PREFIX = "RUST$ENCODED$NICHE$"
+
+# Standard library types are recognized by the start of their (unqualified)
+# type name and their field names
+STD_TYPES = [
+  ("Vec<",      ["len", "cap", "ptr"],                STRUCT_KIND_STD_VEC),
+  ("String",    ["vec"],                              STRUCT_KIND_STD_STRING),
+  ("HashMap<",  ["hasher", "table", "resize_policy"], STRUCT_KIND_STD_HASHMAP),
+  ("Rc<",       ["_ptr", "_nosend", "_noshare"],      STRUCT_KIND_STD_RC),
+  ("Arc<",      ["_ptr"],                             STRUCT_KIND_STD_ARC),
+  ("TreeMap<",  ["root", "length"],                   STRUCT_KIND_STD_TREEMAP),
+  ("RingBuf<",  ["nelts", "lo", "elts"],              STRUCT_KIND_STD_RINGBUF),
+]
 
 def classify_struct(type):
   if type.tag == "&str":
@@ -196,6 +397,17 @@ def classify_struct(type):
   if field_count == 0:
     return STRUCT_KIND_REGULAR_STRUCT
 
+  field_names = [field.name for field in fields]
+
+  if type.tag != None:
+    if ((type.tag.startswith("&[") or type.tag.startswith("&mut [")) and
+        field_names == ["data_ptr", "length"]):
+      return STRUCT_KIND_SLICE
+
+    for (name_prefix, std_field_names, kind) in STD_TYPES:
+      if type.tag.startswith(name_prefix) and field_names == std_field_names:
+        return kind
+
   if fields[0].artificial:
     if field_count == 1:
       return STRUCT_KIND_CSTYLE_VARIANT
@@ -219,6 +431,90 @@ def extract_discriminant_value(enum_val):
     for field in variant_val.type.fields():
       return (field.name, int(variant_val[field]))
 
+def encoded_enum_has_null_variant_value(variant_val, encoded_name):
+  """Checks whether the sole variant of a space-optimized enum actually holds
+  the enum's other, dataless variant. The encoded name has the form
+  RUST$ENCODED$ENUM$<field index>$...$<field index>$<dataless variant name>,
+  where the indices lead to the field that is zero for the dataless variant."""
+  field_indices = encoded_name[len(ENCODED_ENUM_PREFIX):].split("$")[:-1]
+  discriminant = variant_val
+  for field_index in field_indices:
+    discriminant = discriminant[get_field_at_index(discriminant, int(field_index))]
+  return int(discriminant) == 0
+
+def niche_filled_enum_variant_index(enum_val):
+  """Returns the index of the variant an enum value holds if the enum keeps
+  its discriminant in a niche, or None otherwise. The member for the dataful
+  variant is named RUST$ENCODED$NICHE$<start>$<field index>$...$<field index>,
+  where the indices lead to a scalar holding <start> + i for the i-th variant
+  (wrapping around), and any other value for the dataful variant."""
+  enum_members = list(enum_val.type.fields())
+  for (dataful_index, member) in enumerate(enum_members):
+    if member.name != None and member.name.startswith(ENCODED_NICHE_PREFIX):
+      encoded = member.name[len(ENCODED_NICHE_PREFIX):].split("$")
+      niche = enum_val[member]
+      for field_index in encoded[1:]:
+        niche = niche[get_field_at_index(niche, int(field_index))]
+      bits = niche.type.sizeof * 8
+      variant_index = (int(niche) - int(encoded[0])) % (1 << bits)
+      if variant_index < len(enum_members):
+        return variant_index
+      return dataful_index
+  return None
+
+def get_option_payload(option_val):
+  "Returns the value inside of a `Some`, or None if the Option is `None`"
+  variants = list(option_val.type.fields())
+
+  if len(variants) == 1:
+    some_val = option_val[variants[0]]
+    if encoded_enum_has_null_variant_value(some_val, variants[0].name):
+      return None
+    return some_val[get_field_at_index(some_val, 0)]
+
+  niche_variant_index = niche_filled_enum_variant_index(option_val)
+  if niche_variant_index != None:
+    if niche_variant_index == 0:
+      return None
+    some_val = option_val[variants[1]]
+    return some_val[get_field_at_index(some_val, 0)]
+
+  discriminant_name, discriminant_val = extract_discriminant_value(option_val)
+  variant_val = option_val[variants[discriminant_val]]
+  if discriminant_val == 0:
+    # `None` is the first variant of Option
+    return None
+  return variant_val[get_field_at_index(variant_val, 1)]
+
+def pointer_range_children(data_ptr, length):
+  cs = []
+  for index in range(length):
+    cs.append( ("", (data_ptr + index).dereference()) )
+  return cs
+
+def counter_value(counter_val):
+  "Reads the current count out of a Cell<uint> or an AtomicUint"
+  for field in counter_val.type.fields():
+    # Both wrap their value in an UnsafeCell, which is their first field
+    return int(counter_val[field]["value"])
+
+def round_up(unrounded, alignment):
+  return (unrounded + alignment - 1) & ~(alignment - 1)
+
+def type_alignment(type):
+  """Computes the alignment of the given type. GDB doesn't know about type
+  alignments before version 8, so this assumes that scalars are aligned to
+  their size (FIXME: which isn't true for 64 bit values on 32 bit x86)"""
+  type = type.strip_typedefs()
+  if type.code == gdb.TYPE_CODE_STRUCT or type.code == gdb.TYPE_CODE_UNION:
+    alignment = 1
+    for field in type.fields():
+      alignment = max(alignment, type_alignment(field.type))
+    return alignment
+  if type.code == gdb.TYPE_CODE_ARRAY:
+    return type_alignment(type.target())
+  return max(type.sizeof, 1)
+
 def first_field(val):
   for field in val.type.fields():
     return field
@@ -228,4 +524,5 @@ def get_field_at_index(val, index):
   for field in val.type.fields():
     if i == index:
       return field
+    i += 1
   return None
@@ -39,34 +39,123 @@ def print_struct_val(val, internal_dict):
 
   if is_vec_slice(val):
     return print_vec_slice_val(val, internal_dict)
-  else:
-    return print_struct_val_starting_from(0, val, internal_dict)
 
-def print_vec_slice_val(val, internal_dict):
-  output = "&["
+  std_type_printer = STD_TYPE_PRINTERS.get(classify_std_type(val.GetType()))
+  if std_type_printer:
+    return std_type_printer(val, internal_dict)
 
-  length = val.GetChildAtIndex(1).GetValueAsUnsigned()
+  return print_struct_val_starting_from(0, val, internal_dict)
 
+def print_vec_slice_val(val, internal_dict):
+  length = val.GetChildAtIndex(1).GetValueAsUnsigned()
   data_ptr_val = val.GetChildAtIndex(0)
-  data_ptr_type = data_ptr_val.GetType()
-  assert data_ptr_type.IsPointerType()
-
-  element_type = data_ptr_type.GetPointeeType()
-  element_type_size = element_type.GetByteSize()
-
-  start_address = data_ptr_val.GetValueAsUnsigned()
-
-  for i in range(length):
-    address = start_address + i * element_type_size
-    element_val = val.CreateValueFromAddress( val.GetName() + ("[%s]" % i), address, element_type )
-    output += print_val(element_val, internal_dict)
+  return "&" + print_array_of_values(val.GetName(), data_ptr_val, length, internal_dict)
+
+def print_std_vec_val(val, internal_dict):
+  length = val.GetChildMemberWithName("len").GetValueAsUnsigned()
+  data_ptr_val = val.GetChildMemberWithName("ptr")
+  return "vec!" + print_array_of_values(val.GetName(), data_ptr_val, length, internal_dict)
+
+def print_std_string_val(val, internal_dict):
+  vec_val = val.GetChildMemberWithName("vec")
+  length = vec_val.GetChildMemberWithName("len").GetValueAsUnsigned()
+  data_ptr_val = vec_val.GetChildMemberWithName("ptr")
+  return '"%s"' % read_utf8_string(data_ptr_val, length)
+
+def print_std_hashmap_val(val, internal_dict):
+  table_val = val.GetChildMemberWithName("table")
+  table_type = table_val.GetType()
+  capacity = table_val.GetChildMemberWithName("capacity").GetValueAsUnsigned()
+  hashes_val = table_val.GetChildMemberWithName("hashes")
+
+  # The keys and values aren't mentioned in the field types of the table, so
+  # their types have to come from its template parameters
+  if table_type.GetNumberOfTemplateArguments() != 2:
+    length = table_val.GetChildMemberWithName("size").GetValueAsUnsigned()
+    return "HashMap(len: %d)" % length
+
+  key_type = table_type.GetTemplateArgumentType(0)
+  value_type = table_type.GetTemplateArgumentType(1)
+  hash_type = hashes_val.GetType().GetPointeeType()
+
+  # This mirrors calculate_offsets() in libstd/collections/hashmap/table.rs:
+  # the hashes, keys and values are stored one after the other in a single
+  # allocation
+  keys_offset = round_up(capacity * hash_type.GetByteSize(), type_alignment(key_type))
+  values_offset = round_up(keys_offset + capacity * key_type.GetByteSize(),
+                           type_alignment(value_type))
+
+  hashes_address = hashes_val.GetValueAsUnsigned()
+  entries = []
+  for i in range(capacity):
+    hash_address = hashes_address + i * hash_type.GetByteSize()
+    hash_val = val.CreateValueFromAddress("hash", hash_address, hash_type)
+    # A hash of zero marks an empty bucket
+    if hash_val.GetValueAsUnsigned() == 0:
+      continue
+
+    key_address = hashes_address + keys_offset + i * key_type.GetByteSize()
+    value_address = hashes_address + values_offset + i * value_type.GetByteSize()
+    entries.append((val.CreateValueFromAddress("key", key_address, key_type),
+                    val.CreateValueFromAddress("value", value_address, value_type)))
+
+  return print_map_entries(entries, internal_dict)
+
+def print_std_treemap_val(val, internal_dict):
+  # Walk the tree in order, i.e. sorted by key
+  entries = []
+  stack = []
+  node_val = get_option_payload(val.GetChildMemberWithName("root"))
+  while node_val is not None or len(stack) > 0:
+    if node_val is not None:
+      node_val = node_val.Dereference()
+      stack.append(node_val)
+      node_val = get_option_payload(node_val.GetChildMemberWithName("left"))
+    else:
+      node_val = stack.pop()
+      entries.append((node_val.GetChildMemberWithName("key"),
+                      node_val.GetChildMemberWithName("value")))
+      node_val = get_option_payload(node_val.GetChildMemberWithName("right"))
+
+  return print_map_entries(entries, internal_dict)
+
+def print_std_ringbuf_val(val, internal_dict):
+  nelts = val.GetChildMemberWithName("nelts").GetValueAsUnsigned()
+  lo = val.GetChildMemberWithName("lo").GetValueAsUnsigned()
+  elts_val = val.GetChildMemberWithName("elts")
+  elts_len = elts_val.GetChildMemberWithName("len").GetValueAsUnsigned()
+  elts_ptr_val = elts_val.GetChildMemberWithName("ptr")
+  element_type = elts_ptr_val.GetType().GetPointeeType()
+  start_address = elts_ptr_val.GetValueAsUnsigned()
 
-    if i != length - 1:
+  output = "["
+  for i in range(nelts):
+    # The elements wrap around the end of the underlying vector
+    raw_index = (lo + i) % elts_len
+    address = start_address + raw_index * element_type.GetByteSize()
+    element_val = val.CreateValueFromAddress("[%s]" % i, address, element_type)
+    output += print_val(get_option_payload(element_val), internal_dict)
+
+    if i != nelts - 1:
       output += ", "
 
   output += "]"
   return output
 
+def print_std_rc_val(val, internal_dict):
+  return print_std_rc_box_val("Rc", "value", val, internal_dict)
+
+def print_std_arc_val(val, internal_dict):
+  return print_std_rc_box_val("Arc", "data", val, internal_dict)
+
+def print_std_rc_box_val(name, value_field_name, val, internal_dict):
+  box_val = val.GetChildMemberWithName("_ptr").Dereference()
+  strong = read_counter_value(box_val.GetChildMemberWithName("strong"))
+  weak = read_counter_value(box_val.GetChildMemberWithName("weak"))
+  value_val = box_val.GetChildMemberWithName(value_field_name)
+  return "%s(strong: %d, weak: %d, %s)" % (name, strong, weak,
+                                          print_val(value_val, internal_dict))
+
 def print_struct_val_starting_from(field_start_index, val, internal_dict):
   '''
   Prints a struct, tuple, or tuple struct value with Rust syntax.
@@ -119,7 +208,7 @@ def print_enum_val(val, internal_dict):
 
   if val.num_children == 1:
     first_variant_name = val.GetChildAtIndex(0).GetName()
-    if first_variant_name and first_variant_name.startswith("RUST$ENCODED$ENUM$"):
+    if first_variant_name and first_variant_name.startswith(ENCODED_ENUM_PREFIX):
       # Try to extract the
 
       last_separator_index = first_variant_name.rfind("$")
@@ -147,6 +236,11 @@ def print_enum_val(val, internal_dict):
     else:
       return print_struct_val_starting_from(0, val.GetChildAtIndex(0), internal_dict)
 
+  niche_variant_index = niche_filled_enum_variant_index(val)
+  if niche_variant_index != None:
+    return print_struct_val_starting_from(0, val.GetChildAtIndex(niche_variant_index),
+                                          internal_dict)
+
   # extract the discriminator value by
   disr_val = val.GetChildAtIndex(0).GetChildAtIndex(0)
   disr_type = disr_val.GetType()
@@ -189,6 +283,39 @@ def print_fixed_size_vec_val(val, internal_dict):
 
 unqualified_type_markers = frozenset(["(", "[", "&", "*"])
 
+# The name rustc gives the discriminant field of enum variants
+ENUM_DISCRIMINANT_FIELD_NAME = "RUST$ENUM$DISR"
+
+# The prefix of the name rustc gives the sole member of space-optimized enums
+ENCODED_ENUM_PREFIX = "RUST$ENCODED$ENUM$"
+
+# The prefix of the name rustc gives the member for the dataful variant of
+# enums keeping their discriminant in a niche of that variant
+ENCODED_NICHE_PREFIX = "RUST$ENCODED$NICHE$"
+
+# Standard library types are recognized by the start of their (unqualified)
+# type name and their field names
+STD_TYPES = [
+  ("Vec<",      ["len", "cap", "ptr"]),
+  ("String",    ["vec"]),
+  ("HashMap<",  ["hasher", "table", "resize_policy"]),
+  ("Rc<",       ["_ptr", "_nosend", "_noshare"]),
+  ("Arc<",      ["_ptr"]),
+  ("TreeMap<",  ["root", "length"]),
+  ("RingBuf<",  ["nelts", "lo", "elts"]),
+]
+
+STD_TYPE_PRINTERS = {
+  "Vec<":     print_std_vec_val,
+  "String":   print_std_string_val,
+  "HashMap<": print_std_hashmap_val,
+  "Rc<":      print_std_rc_val,
+  "Arc<":     print_std_arc_val,
+  "TreeMap<": print_std_treemap_val,
+  "RingBuf<": print_std_ringbuf_val,
+}
+
+
 def extract_type_name(qualified_type_name):
   '''Extracts the type name from a fully qualified path'''
   if qualified_type_name[0] in unqualified_type_markers:
@@ -211,7 +338,140 @@ def type_has_field_names(ty):
   if ty.GetNumberOfFields() > 1:
     return ty.GetFieldAtIndex(1).GetName() != None
   else:
-    return ty.GetFieldAtIndex(0).GetName() != None
+    field_name = ty.GetFieldAtIndex(0).GetName()
+    return field_name != None and field_name != ENUM_DISCRIMINANT_FIELD_NAME
+
+
+def classify_std_type(ty):
+  '''Returns the STD_TYPE_PRINTERS key of the given type, if it is a standard library type'''
+  type_name = extract_type_name(ty.GetName())
+  field_names = [ty.GetFieldAtIndex(i).GetName() for i in range(ty.GetNumberOfFields())]
+  for (name_prefix, std_field_names) in STD_TYPES:
+    if type_name.startswith(name_prefix) and field_names == std_field_names:
+      return name_prefix
+  return None
+
+
+def print_array_of_values(array_name, data_ptr_val, length, internal_dict):
+  '''Prints a contiguous memory range, interpreting it as values of the
+     pointee-type of data_ptr_val'''
+  data_ptr_type = data_ptr_val.GetType()
+  assert data_ptr_type.IsPointerType()
+
+  element_type = data_ptr_type.GetPointeeType()
+  element_type_size = element_type.GetByteSize()
+
+  start_address = data_ptr_val.GetValueAsUnsigned()
+
+  output = "["
+  for i in range(length):
+    address = start_address + i * element_type_size
+    element_val = data_ptr_val.CreateValueFromAddress(array_name + ("[%s]" % i),
+                                                      address,
+                                                      element_type)
+    output += print_val(element_val, internal_dict)
+
+    if i != length - 1:
+      output += ", "
+
+  output += "]"
+  return output
+
+
+def print_map_entries(entries, internal_dict):
+  output = "{"
+  for (i, (key_val, value_val)) in enumerate(entries):
+    output += print_val(key_val, internal_dict) + ": " + print_val(value_val, internal_dict)
+
+    if i != len(entries) - 1:
+      output += ", "
+
+  output += "}"
+  return output
+
+
+def read_utf8_string(data_ptr_val, length):
+  if length == 0:
+    return ""
+  error = lldb.SBError()
+  data = data_ptr_val.GetProcess().ReadMemory(data_ptr_val.GetValueAsUnsigned(), length, error)
+  if error.Fail():
+    return "<error reading string: %s>" % error.GetCString()
+  return data.decode("utf-8", "replace")
+
+
+def read_counter_value(counter_val):
+  '''Reads the count out of a Cell<uint> or an AtomicUint. Both wrap their value
+     in an UnsafeCell, which is their first field.'''
+  return counter_val.GetChildAtIndex(0).GetChildMemberWithName("value").GetValueAsUnsigned()
+
+
+def niche_filled_enum_variant_index(enum_val):
+  '''Returns the index of the variant an enum value holds if the enum keeps its
+     discriminant in a niche, or None otherwise. The member for the dataful
+     variant is named RUST$ENCODED$NICHE$<start>$<field index>$...$<field index>,
+     where the indices lead to a scalar holding <start> + i for the i-th
+     variant (wrapping around), and any other value for the dataful variant.'''
+  for dataful_index in range(enum_val.num_children):
+    member_name = enum_val.GetChildAtIndex(dataful_index).GetName()
+    if member_name and member_name.startswith(ENCODED_NICHE_PREFIX):
+      encoded = member_name[len(ENCODED_NICHE_PREFIX):].split("$")
+      niche_val = enum_val.GetChildAtIndex(dataful_index)
+      for field_index in encoded[1:]:
+        niche_val = niche_val.GetChildAtIndex(int(field_index))
+      bits = niche_val.GetType().GetByteSize() * 8
+      variant_index = (niche_val.GetValueAsUnsigned() - int(encoded[0])) % (1 << bits)
+      if variant_index < enum_val.num_children:
+        return variant_index
+      return dataful_index
+  return None
+
+
+def get_option_payload(option_val):
+  '''Returns the value inside of a `Some`, or None if the Option is `None`'''
+  assert option_val.GetType().GetTypeClass() == lldb.eTypeClassUnion
+
+  if option_val.num_children == 1:
+    some_val = option_val.GetChildAtIndex(0)
+    # The indices of the fields leading to the discriminant are encoded in the
+    # name, as in RUST$ENCODED$ENUM$<field index>$...$<field index>$None
+    field_indices = some_val.GetName()[len(ENCODED_ENUM_PREFIX):].split("$")[:-1]
+    disr_val = some_val
+    for field_index in field_indices:
+      disr_val = disr_val.GetChildAtIndex(int(field_index))
+    if disr_val.GetValueAsUnsigned() == 0:
+      return None
+    return some_val.GetChildAtIndex(0)
+
+  niche_variant_index = niche_filled_enum_variant_index(option_val)
+  if niche_variant_index != None:
+    if niche_variant_index == 0:
+      return None
+    return option_val.GetChildAtIndex(1).GetChildAtIndex(0)
+
+  variant_index = option_val.GetChildAtIndex(0).GetChildAtIndex(0).GetValueAsUnsigned()
+  if variant_index == 0:
+    # `None` is the first variant of Option
+    return None
+  return option_val.GetChildAtIndex(variant_index).GetChildAtIndex(1)
+
+
+def round_up(unrounded, alignment):
+  return (unrounded + alignment - 1) & ~(alignment - 1)
+
+
+def type_alignment(ty):
+  '''Computes the alignment of the given type, assuming that scalars are aligned
+     to their size (FIXME: which isn't true for 64 bit values on 32 bit x86)'''
+  type_class = ty.GetTypeClass()
+  if type_class == lldb.eTypeClassStruct or type_class == lldb.eTypeClassUnion:
+    alignment = 1
+    for i in range(ty.GetNumberOfFields()):
+      alignment = max(alignment, type_alignment(ty.GetFieldAtIndex(i).GetType()))
+    return alignment
+  if type_class == lldb.eTypeClassArray:
+    return type_alignment(ty.GetArrayElementType())
+  return max(ty.GetByteSize(), 1)
 
 
 def is_vec_slice(val):
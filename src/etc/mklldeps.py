@@ -76,7 +76,11 @@ for llconfig in sys.argv[4:]:
       args = [llconfig, '--libs']
     else:
       args = [llconfig, '--libs', '--system-libs']
-    args.extend(components)
+    # the profile reader used by `-C profile-use` is new in 3.5
+    if version < '3.5':
+      args.extend([c for c in components if c != 'profiledata'])
+    else:
+      args.extend(components)
     out = run(args)
     for lib in out.strip().replace("\n", ' ').split(' '):
         lib = lib.strip()[2:] # chop of the leading '-l'
@@ -630,14 +630,7 @@ impl<K: Ord, V: Ord> Ord for BTreeMap<K, V> {
 
 impl<K: Show, V: Show> Show for BTreeMap<K, V> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        try!(write!(f, "{{"));
-
-        for (i, (k, v)) in self.iter().enumerate() {
-            if i != 0 { try!(write!(f, ", ")); }
-            try!(write!(f, "{}: {}", *k, *v));
-        }
-
-        write!(f, "}}")
+        self.iter().fold(f.show_map(), |map, (k, v)| map.key_value(k, v)).finish()
     }
 }
 
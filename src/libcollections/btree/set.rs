@@ -188,14 +188,7 @@ impl<T: Ord> Default for BTreeSet<T> {
 
 impl<T: Show> Show for BTreeSet<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        try!(write!(f, "{{"));
-
-        for (i, x) in self.iter().enumerate() {
-            if i != 0 { try!(write!(f, ", ")); }
-            try!(write!(f, "{}", *x));
-        }
-
-        write!(f, "}}")
+        self.iter().fold(f.show_set(), |set, x| set.entry(x)).finish()
     }
 }
 
@@ -729,14 +729,7 @@ impl<A: Clone> Clone for DList<A> {
 
 impl<A: fmt::Show> fmt::Show for DList<A> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        try!(write!(f, "["));
-
-        for (i, e) in self.iter().enumerate() {
-            if i != 0 { try!(write!(f, ", ")); }
-            try!(write!(f, "{}", *e));
-        }
-
-        write!(f, "]")
+        self.iter().fold(f.show_list(), |list, e| list.entry(e)).finish()
     }
 }
 
@@ -517,14 +517,7 @@ impl<A> Extendable<A> for RingBuf<A> {
 
 impl<T: fmt::Show> fmt::Show for RingBuf<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        try!(write!(f, "["));
-
-        for (i, e) in self.iter().enumerate() {
-            if i != 0 { try!(write!(f, ", ")); }
-            try!(write!(f, "{}", *e));
-        }
-
-        write!(f, "]")
+        self.iter().fold(f.show_list(), |list, e| list.entry(e)).finish()
     }
 }
 
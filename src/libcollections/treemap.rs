@@ -195,14 +195,7 @@ impl<K: Ord, V: Ord> Ord for TreeMap<K, V> {
 
 impl<K: Ord + Show, V: Show> Show for TreeMap<K, V> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        try!(write!(f, "{{"));
-
-        for (i, (k, v)) in self.iter().enumerate() {
-            if i != 0 { try!(write!(f, ", ")); }
-            try!(write!(f, "{}: {}", *k, *v));
-        }
-
-        write!(f, "}}")
+        self.iter().fold(f.show_map(), |map, (k, v)| map.key_value(k, v)).finish()
     }
 }
 
@@ -1087,14 +1080,7 @@ impl<T: Ord> Ord for TreeSet<T> {
 
 impl<T: Ord + Show> Show for TreeSet<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        try!(write!(f, "{{"));
-
-        for (i, x) in self.iter().enumerate() {
-            if i != 0 { try!(write!(f, ", ")); }
-            try!(write!(f, "{}", *x));
-        }
-
-        write!(f, "}}")
+        self.iter().fold(f.show_set(), |set, x| set.entry(x)).finish()
     }
 }
 
@@ -0,0 +1,225 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Helpers for writing `Show` implementations of structures and
+//! collections.
+//!
+//! The helpers write everything on one line by default. When the alternate
+//! flag is given, as in `{:#}`, they write each field or element on a line
+//! of its own, indented by four spaces, and format nested values with the
+//! alternate flag as well.
+
+use collections::Collection;
+use iter::Iterator;
+use option::{Option, Some, None};
+use result::Ok;
+use slice::ImmutableSlice;
+use str::StrSlice;
+
+use super::{Formatter, FormatWriter, Result, Show};
+
+/// Indents everything written through it by one level.
+struct PadAdapter<'a, 'b: 'a> {
+    fmt: &'a mut Formatter<'b>,
+    on_newline: bool,
+}
+
+impl<'a, 'b> PadAdapter<'a, 'b> {
+    fn new(fmt: &'a mut Formatter<'b>) -> PadAdapter<'a, 'b> {
+        PadAdapter { fmt: fmt, on_newline: false }
+    }
+}
+
+impl<'a, 'b> FormatWriter for PadAdapter<'a, 'b> {
+    fn write(&mut self, mut bytes: &[u8]) -> Result {
+        while !bytes.is_empty() {
+            if self.on_newline {
+                try!(self.fmt.write(b"    "));
+            }
+
+            let split = match bytes.iter().position(|&b| b == b'\n') {
+                Some(pos) => {
+                    self.on_newline = true;
+                    pos + 1
+                }
+                None => {
+                    self.on_newline = false;
+                    bytes.len()
+                }
+            };
+            try!(self.fmt.write(bytes[..split]));
+            bytes = bytes[split..];
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes the next entry of a structure or collection: on the same line
+/// after `separator`, or on a new indented line in alternate mode.
+fn write_entry(fmt: &mut Formatter, separator: &str, name: Option<&str>,
+               value: &Show) -> Result {
+    if fmt.alternate() {
+        let mut writer = PadAdapter::new(fmt);
+        try!(writer.write(separator.trim_right().as_bytes()));
+        match name {
+            Some(name) => write!(writer, "\n{}: {:#}", name, value),
+            None => write!(writer, "\n{:#}", value),
+        }
+    } else {
+        try!(fmt.write(separator.as_bytes()));
+        match name {
+            Some(name) => write!(fmt, "{}: {}", name, value),
+            None => write!(fmt, "{}", value),
+        }
+    }
+}
+
+/// Closes a structure or collection which had at least one entry.
+fn write_end(fmt: &mut Formatter, end: &str) -> Result {
+    if fmt.alternate() {
+        try!(fmt.write(b"\n"));
+        fmt.write(end.trim_left().as_bytes())
+    } else {
+        fmt.write(end.as_bytes())
+    }
+}
+
+/// A helper for `Show` implementations of structs with named fields,
+/// created by `Formatter::show_struct`.
+///
+/// Writes `Name { a: 1, b: 2 }`, or one field per line in alternate mode.
+pub struct ShowStruct<'a, 'b: 'a> {
+    fmt: &'a mut Formatter<'b>,
+    result: Result,
+    has_fields: bool,
+}
+
+pub fn show_struct_new<'a, 'b>(fmt: &'a mut Formatter<'b>, name: &str) -> ShowStruct<'a, 'b> {
+    let result = fmt.write(name.as_bytes());
+    ShowStruct { fmt: fmt, result: result, has_fields: false }
+}
+
+impl<'a, 'b> ShowStruct<'a, 'b> {
+    /// Adds a field to the output.
+    pub fn field(mut self, name: &str, value: &Show) -> ShowStruct<'a, 'b> {
+        if self.result.is_ok() {
+            let separator = if self.has_fields { ", " } else { " { " };
+            self.result = write_entry(self.fmt, separator, Some(name), value);
+        }
+        self.has_fields = true;
+        self
+    }
+
+    /// Finishes the output and returns the result of all the writes.
+    pub fn finish(mut self) -> Result {
+        try!(self.result);
+        if self.has_fields {
+            write_end(self.fmt, " }")
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A helper for `Show` implementations of tuple structs and enum variants,
+/// created by `Formatter::show_tuple`.
+///
+/// Writes `Name(1, 2)`, or one field per line in alternate mode.
+pub struct ShowTuple<'a, 'b: 'a> {
+    fmt: &'a mut Formatter<'b>,
+    result: Result,
+    has_fields: bool,
+}
+
+pub fn show_tuple_new<'a, 'b>(fmt: &'a mut Formatter<'b>, name: &str) -> ShowTuple<'a, 'b> {
+    let result = fmt.write(name.as_bytes());
+    ShowTuple { fmt: fmt, result: result, has_fields: false }
+}
+
+impl<'a, 'b> ShowTuple<'a, 'b> {
+    /// Adds a field to the output.
+    pub fn field(mut self, value: &Show) -> ShowTuple<'a, 'b> {
+        if self.result.is_ok() {
+            let separator = if self.has_fields { ", " } else { "(" };
+            self.result = write_entry(self.fmt, separator, None, value);
+        }
+        self.has_fields = true;
+        self
+    }
+
+    /// Finishes the output and returns the result of all the writes.
+    pub fn finish(mut self) -> Result {
+        try!(self.result);
+        if self.has_fields {
+            write_end(self.fmt, ")")
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A helper for `Show` implementations of sequences, sets and maps,
+/// created by `Formatter::show_list`, `Formatter::show_set` and
+/// `Formatter::show_map`.
+///
+/// Writes `[1, 2]`, `{1, 2}` or `{a: 1, b: 2}`, or one entry per line in
+/// alternate mode.
+pub struct ShowCollection<'a, 'b: 'a> {
+    fmt: &'a mut Formatter<'b>,
+    result: Result,
+    has_entries: bool,
+    close: &'static str,
+}
+
+pub fn show_collection_new<'a, 'b>(fmt: &'a mut Formatter<'b>, open: &'static str,
+                                   close: &'static str) -> ShowCollection<'a, 'b> {
+    let result = fmt.write(open.as_bytes());
+    ShowCollection { fmt: fmt, result: result, has_entries: false, close: close }
+}
+
+impl<'a, 'b> ShowCollection<'a, 'b> {
+    /// Adds an element to a list or set.
+    pub fn entry(mut self, value: &Show) -> ShowCollection<'a, 'b> {
+        if self.result.is_ok() {
+            let separator = if self.has_entries { ", " } else { "" };
+            self.result = write_entry(self.fmt, separator, None, value);
+        }
+        self.has_entries = true;
+        self
+    }
+
+    /// Adds a key and its value to a map.
+    pub fn key_value(mut self, key: &Show, value: &Show) -> ShowCollection<'a, 'b> {
+        if self.result.is_ok() {
+            let separator = if self.has_entries { ", " } else { "" };
+            self.result = write_entry(self.fmt, separator, None, key);
+        }
+        if self.result.is_ok() {
+            self.result = if self.fmt.alternate() {
+                write!(PadAdapter::new(self.fmt), ": {:#}", value)
+            } else {
+                write!(self.fmt, ": {}", value)
+            };
+        }
+        self.has_entries = true;
+        self
+    }
+
+    /// Finishes the output and returns the result of all the writes.
+    pub fn finish(mut self) -> Result {
+        try!(self.result);
+        if self.has_entries {
+            write_end(self.fmt, self.close)
+        } else {
+            self.fmt.write(self.close.as_bytes())
+        }
+    }
+}
@@ -30,9 +30,11 @@ use str;
 pub use self::num::radix;
 pub use self::num::Radix;
 pub use self::num::RadixFmt;
+pub use self::builders::{ShowStruct, ShowTuple, ShowCollection};
 
 mod num;
 mod float;
+mod builders;
 pub mod rt;
 
 pub type Result = result::Result<(), FormatError>;
@@ -552,6 +554,41 @@ impl<'a> Formatter<'a> {
     pub fn write_fmt(&mut self, fmt: &Arguments) -> Result {
         write(self.buf, fmt)
     }
+
+    /// Whether the alternate flag, `#`, was specified, as in `{:#}`.
+    pub fn alternate(&self) -> bool {
+        self.flags & (1 << (rt::FlagAlternate as uint)) != 0
+    }
+
+    /// Creates a `ShowStruct` builder for writing a struct with named
+    /// fields, one field per line if the alternate flag is set.
+    pub fn show_struct<'b>(&'b mut self, name: &str) -> ShowStruct<'b, 'a> {
+        builders::show_struct_new(self, name)
+    }
+
+    /// Creates a `ShowTuple` builder for writing a tuple struct or an enum
+    /// variant, one field per line if the alternate flag is set.
+    pub fn show_tuple<'b>(&'b mut self, name: &str) -> ShowTuple<'b, 'a> {
+        builders::show_tuple_new(self, name)
+    }
+
+    /// Creates a `ShowCollection` builder for writing a sequence as
+    /// `[a, b]`, one element per line if the alternate flag is set.
+    pub fn show_list<'b>(&'b mut self) -> ShowCollection<'b, 'a> {
+        builders::show_collection_new(self, "[", "]")
+    }
+
+    /// Creates a `ShowCollection` builder for writing a set as `{a, b}`,
+    /// one element per line if the alternate flag is set.
+    pub fn show_set<'b>(&'b mut self) -> ShowCollection<'b, 'a> {
+        builders::show_collection_new(self, "{", "}")
+    }
+
+    /// Creates a `ShowCollection` builder for writing a map as
+    /// `{k: v, k2: v2}`, one entry per line if the alternate flag is set.
+    pub fn show_map<'b>(&'b mut self) -> ShowCollection<'b, 'a> {
+        builders::show_collection_new(self, "{", "}")
+    }
 }
 
 /// This is a function which calls are emitted to by the compiler itself to
@@ -763,22 +800,7 @@ impl<'a> Show for &'a any::Any+'a {
 
 impl<'a, T: Show> Show for &'a [T] {
     fn fmt(&self, f: &mut Formatter) -> Result {
-        if f.flags & (1 << (rt::FlagAlternate as uint)) == 0 {
-            try!(write!(f, "["));
-        }
-        let mut is_first = true;
-        for x in self.iter() {
-            if is_first {
-                is_first = false;
-            } else {
-                try!(write!(f, ", "));
-            }
-            try!(write!(f, "{}", *x))
-        }
-        if f.flags & (1 << (rt::FlagAlternate as uint)) == 0 {
-            try!(write!(f, "]"));
-        }
-        Ok(())
+        self.iter().fold(f.show_list(), |list, x| list.entry(x)).finish()
     }
 }
 
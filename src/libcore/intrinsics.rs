@@ -616,6 +616,73 @@ extern "rust-intrinsic" {
     pub fn u32_mul_with_overflow(x: u32, y: u32) -> (u32, bool);
     /// Performs checked `u64` multiplication.
     pub fn u64_mul_with_overflow(x: u64, y: u64) -> (u64, bool);
+
+    // The SIMD intrinsics below operate on `#[simd]` structs, lane by lane.
+    // Comparisons return a vector of integers as wide as the lanes compared,
+    // with all bits of a lane set if the comparison holds for it.
+
+    /// Compares the lanes of two SIMD vectors for equality.
+    #[cfg(not(stage0))]
+    pub fn simd_eq<T, U>(x: T, y: T) -> U;
+    /// Compares the lanes of two SIMD vectors for inequality.
+    #[cfg(not(stage0))]
+    pub fn simd_ne<T, U>(x: T, y: T) -> U;
+    /// Checks whether the lanes of `x` are less than those of `y`.
+    #[cfg(not(stage0))]
+    pub fn simd_lt<T, U>(x: T, y: T) -> U;
+    /// Checks whether the lanes of `x` are less than or equal to those of `y`.
+    #[cfg(not(stage0))]
+    pub fn simd_le<T, U>(x: T, y: T) -> U;
+    /// Checks whether the lanes of `x` are greater than those of `y`.
+    #[cfg(not(stage0))]
+    pub fn simd_gt<T, U>(x: T, y: T) -> U;
+    /// Checks whether the lanes of `x` are greater than or equal to those of `y`.
+    #[cfg(not(stage0))]
+    pub fn simd_ge<T, U>(x: T, y: T) -> U;
+
+    /// Builds a SIMD vector of 2 lanes out of those of `x` and `y`. The lanes
+    /// of `y` are numbered after those of `x`, and `idx` must be an array
+    /// literal of constants.
+    #[cfg(not(stage0))]
+    pub fn simd_shuffle2<T, U>(x: T, y: T, idx: [u32, ..2]) -> U;
+    /// Builds a SIMD vector of 4 lanes out of those of `x` and `y`.
+    #[cfg(not(stage0))]
+    pub fn simd_shuffle4<T, U>(x: T, y: T, idx: [u32, ..4]) -> U;
+    /// Builds a SIMD vector of 8 lanes out of those of `x` and `y`.
+    #[cfg(not(stage0))]
+    pub fn simd_shuffle8<T, U>(x: T, y: T, idx: [u32, ..8]) -> U;
+    /// Builds a SIMD vector of 16 lanes out of those of `x` and `y`.
+    #[cfg(not(stage0))]
+    pub fn simd_shuffle16<T, U>(x: T, y: T, idx: [u32, ..16]) -> U;
+
+    /// Replaces lane `idx` of a SIMD vector. The result is undefined if
+    /// `idx` is out of bounds.
+    #[cfg(not(stage0))]
+    pub fn simd_insert<T, E>(x: T, idx: u32, val: E) -> T;
+    /// Reads lane `idx` of a SIMD vector. The result is undefined if `idx`
+    /// is out of bounds.
+    #[cfg(not(stage0))]
+    pub fn simd_extract<T, E>(x: T, idx: u32) -> E;
+
+    /// Converts the lanes of a SIMD vector as `as` would.
+    #[cfg(not(stage0))]
+    pub fn simd_cast<T, U>(x: T) -> U;
+    /// Picks the lanes of `x` where `mask` is nonzero and those of `y`
+    /// elsewhere.
+    #[cfg(not(stage0))]
+    pub fn simd_select<M, T>(mask: M, x: T, y: T) -> T;
+
+    /// Takes the square root of the lanes of a SIMD vector of floats.
+    #[cfg(not(stage0))]
+    pub fn simd_sqrt<T>(x: T) -> T;
+    /// Takes the smaller of each pair of lanes of two SIMD vectors of floats,
+    /// or the lane of `y` if either is NaN.
+    #[cfg(not(stage0))]
+    pub fn simd_fmin<T>(x: T, y: T) -> T;
+    /// Takes the larger of each pair of lanes of two SIMD vectors of floats,
+    /// or the lane of `y` if either is NaN.
+    #[cfg(not(stage0))]
+    pub fn simd_fmax<T>(x: T, y: T) -> T;
 }
 
 
@@ -12,11 +12,23 @@
 //!
 //! These types can be used for accessing basic SIMD operations. Each of them
 //! implements the standard arithmetic operator traits (Add, Sub, Mul, Div,
-//! Rem, Shl, Shr) through compiler magic, rather than explicitly. Currently
-//! comparison operators are not implemented. To use SSE3+, you must enable
-//! the features, like `-C target-feature=sse3,sse4.1,sse4.2`, or a more
-//! specific `target-cpu`. No other SIMD intrinsics or high-level wrappers are
-//! provided beyond this module.
+//! Rem, Shl, Shr) through compiler magic, rather than explicitly.
+//!
+//! Everything else is provided by methods: moving vectors in and out of
+//! slices (`load`, `store`), access to single lanes (`extract`, `replace`),
+//! comparisons, which return a mask of integers as wide as the lanes
+//! (`eq`, `lt`, ...), blending two vectors by a mask (`select`), and
+//! horizontal operations across the lanes of one vector (`sum`, `max_lane`,
+//! ...). Arbitrary shuffles are available through the `simd_shuffle*`
+//! intrinsics.
+//!
+//! All of these are translated to portable LLVM IR, which LLVM lowers to the
+//! instructions of the target, or to scalar code where it has none. A few
+//! operations (such as `min` and `max` of floats) use target specific
+//! instructions directly when the features of the crate being compiled allow
+//! it. To use SSE3+, you must enable the features, like
+//! `-C target-feature=+sse3,+sse4.1,+sse4.2`, or a more specific
+//! `target-cpu`.
 //!
 //! ```rust
 //! #[allow(experimental)];
@@ -26,6 +38,10 @@
 //!     let a = f32x4(40.0, 41.0, 42.0, 43.0);
 //!     let b = f32x4(1.0, 1.1, 3.4, 9.8);
 //!     println!("{}", a + b);
+//!
+//!     let pixels = [0.5f32, 1.5, 2.5, 3.5, 4.5];
+//!     let v = f32x4::load(&pixels, 1);
+//!     assert_eq!(v.max(f32x4::splat(2.0)).sum(), 2.0 + 2.5 + 3.5 + 4.5);
 //! }
 //! ```
 //!
@@ -37,6 +53,13 @@
 #![allow(non_camel_case_types)]
 #![allow(missing_doc)]
 
+#[cfg(not(stage0))] use intrinsics;
+#[cfg(not(stage0))] use iter::Iterator;
+#[cfg(not(stage0))] use mem;
+#[cfg(not(stage0))] use ptr;
+#[cfg(not(stage0))] use ptr::RawPtr;
+#[cfg(not(stage0))] use slice::{ImmutableSlice, MutableSlice};
+
 #[experimental]
 #[simd]
 #[deriving(Show)]
@@ -104,3 +127,231 @@ pub struct f32x4(pub f32, pub f32, pub f32, pub f32);
 #[deriving(Show)]
 #[repr(C)]
 pub struct f64x2(pub f64, pub f64);
+
+// Methods shared by all the vector types. `$mask` is the type of the result
+// of comparisons: integers as wide as the lanes of `$name`.
+macro_rules! simd_methods(
+    ($name:ident, $elem:ty, $mask:ident, $lanes:expr) => (
+        #[cfg(not(stage0))]
+        #[experimental]
+        impl $name {
+            /// Returns a vector with every lane set to `x`.
+            #[inline]
+            pub fn splat(x: $elem) -> $name {
+                $name::from_array([x, ..$lanes])
+            }
+
+            /// Converts an array into a vector, lane by lane.
+            #[inline]
+            pub fn from_array(lanes: [$elem, ..$lanes]) -> $name {
+                unsafe { mem::transmute(lanes) }
+            }
+
+            /// Converts the vector into an array, lane by lane.
+            #[inline]
+            pub fn to_array(self) -> [$elem, ..$lanes] {
+                unsafe { mem::transmute(self) }
+            }
+
+            /// Reads a vector from the elements of `slice` starting at
+            /// `offset`, which need not be aligned for the vector.
+            ///
+            /// Fails if there are fewer than the number of lanes elements
+            /// after `offset`.
+            #[inline]
+            pub fn load(slice: &[$elem], offset: uint) -> $name {
+                assert!(offset <= slice.len() && slice.len() - offset >= $lanes);
+                let mut lanes: [$elem, ..$lanes] = unsafe { mem::uninitialized() };
+                unsafe {
+                    ptr::copy_nonoverlapping_memory(lanes.as_mut_ptr(),
+                                                    slice.as_ptr().offset(offset as int),
+                                                    $lanes);
+                }
+                $name::from_array(lanes)
+            }
+
+            /// Writes the vector to the elements of `slice` starting at
+            /// `offset`, which need not be aligned for the vector.
+            ///
+            /// Fails if there are fewer than the number of lanes elements
+            /// after `offset`.
+            #[inline]
+            pub fn store(self, slice: &mut [$elem], offset: uint) {
+                assert!(offset <= slice.len() && slice.len() - offset >= $lanes);
+                let lanes = self.to_array();
+                unsafe {
+                    ptr::copy_nonoverlapping_memory(slice.as_mut_ptr().offset(offset as int),
+                                                    lanes.as_ptr(),
+                                                    $lanes);
+                }
+            }
+
+            /// Returns lane `i`. Fails if `i` is out of bounds.
+            #[inline]
+            pub fn extract(self, i: uint) -> $elem {
+                assert!(i < $lanes);
+                unsafe { intrinsics::simd_extract(self, i as u32) }
+            }
+
+            /// Returns the vector with lane `i` set to `x`. Fails if `i` is
+            /// out of bounds.
+            #[inline]
+            pub fn replace(self, i: uint, x: $elem) -> $name {
+                assert!(i < $lanes);
+                unsafe { intrinsics::simd_insert(self, i as u32, x) }
+            }
+
+            /// Compares the lanes of two vectors for equality.
+            #[inline]
+            pub fn eq(self, other: $name) -> $mask {
+                unsafe { intrinsics::simd_eq(self, other) }
+            }
+
+            /// Compares the lanes of two vectors for inequality.
+            #[inline]
+            pub fn ne(self, other: $name) -> $mask {
+                unsafe { intrinsics::simd_ne(self, other) }
+            }
+
+            /// Checks whether the lanes of `self` are less than those of
+            /// `other`.
+            #[inline]
+            pub fn lt(self, other: $name) -> $mask {
+                unsafe { intrinsics::simd_lt(self, other) }
+            }
+
+            /// Checks whether the lanes of `self` are less than or equal to
+            /// those of `other`.
+            #[inline]
+            pub fn le(self, other: $name) -> $mask {
+                unsafe { intrinsics::simd_le(self, other) }
+            }
+
+            /// Checks whether the lanes of `self` are greater than those of
+            /// `other`.
+            #[inline]
+            pub fn gt(self, other: $name) -> $mask {
+                unsafe { intrinsics::simd_gt(self, other) }
+            }
+
+            /// Checks whether the lanes of `self` are greater than or equal
+            /// to those of `other`.
+            #[inline]
+            pub fn ge(self, other: $name) -> $mask {
+                unsafe { intrinsics::simd_ge(self, other) }
+            }
+
+            /// Picks the lanes of `if_true` where `mask` is nonzero and
+            /// those of `if_false` elsewhere.
+            #[inline]
+            pub fn select(mask: $mask, if_true: $name, if_false: $name) -> $name {
+                unsafe { intrinsics::simd_select(mask, if_true, if_false) }
+            }
+
+            /// Adds up the lanes of the vector.
+            #[inline]
+            pub fn sum(self) -> $elem {
+                let lanes = self.to_array();
+                lanes.slice_from(1).iter().fold(lanes[0], |acc, &x| acc + x)
+            }
+
+            /// Multiplies the lanes of the vector together.
+            #[inline]
+            pub fn product(self) -> $elem {
+                let lanes = self.to_array();
+                lanes.slice_from(1).iter().fold(lanes[0], |acc, &x| acc * x)
+            }
+
+            /// Returns the smallest lane of the vector.
+            #[inline]
+            pub fn min_lane(self) -> $elem {
+                let lanes = self.to_array();
+                lanes.slice_from(1).iter().fold(lanes[0], |acc, &x| if x < acc { x } else { acc })
+            }
+
+            /// Returns the largest lane of the vector.
+            #[inline]
+            pub fn max_lane(self) -> $elem {
+                let lanes = self.to_array();
+                lanes.slice_from(1).iter().fold(lanes[0], |acc, &x| if x > acc { x } else { acc })
+            }
+        }
+    )
+)
+
+// The vector types of integers, which are also the masks.
+macro_rules! simd_int_methods(
+    ($name:ident, $elem:ty, $mask:ident, $lanes:expr) => (
+        simd_methods!($name, $elem, $mask, $lanes)
+
+        #[cfg(not(stage0))]
+        #[experimental]
+        impl $name {
+            /// Takes the smaller of each pair of lanes of two vectors.
+            #[inline]
+            pub fn min(self, other: $name) -> $name {
+                $name::select(self.lt(other), self, other)
+            }
+
+            /// Takes the larger of each pair of lanes of two vectors.
+            #[inline]
+            pub fn max(self, other: $name) -> $name {
+                $name::select(self.gt(other), self, other)
+            }
+
+            /// Checks whether every lane of a mask is set.
+            #[inline]
+            pub fn all(self) -> bool {
+                self.to_array().iter().all(|&x| x != 0)
+            }
+
+            /// Checks whether any lane of a mask is set.
+            #[inline]
+            pub fn any(self) -> bool {
+                self.to_array().iter().any(|&x| x != 0)
+            }
+        }
+    )
+)
+
+// The vector types of floats.
+macro_rules! simd_float_methods(
+    ($name:ident, $elem:ty, $mask:ident, $lanes:expr) => (
+        simd_methods!($name, $elem, $mask, $lanes)
+
+        #[cfg(not(stage0))]
+        #[experimental]
+        impl $name {
+            /// Takes the smaller of each pair of lanes of two vectors, or
+            /// the lane of `other` if either is NaN.
+            #[inline]
+            pub fn min(self, other: $name) -> $name {
+                unsafe { intrinsics::simd_fmin(self, other) }
+            }
+
+            /// Takes the larger of each pair of lanes of two vectors, or the
+            /// lane of `other` if either is NaN.
+            #[inline]
+            pub fn max(self, other: $name) -> $name {
+                unsafe { intrinsics::simd_fmax(self, other) }
+            }
+
+            /// Takes the square root of each lane.
+            #[inline]
+            pub fn sqrt(self) -> $name {
+                unsafe { intrinsics::simd_sqrt(self) }
+            }
+        }
+    )
+)
+
+simd_int_methods!(i8x16, i8, i8x16, 16)
+simd_int_methods!(i16x8, i16, i16x8, 8)
+simd_int_methods!(i32x4, i32, i32x4, 4)
+simd_int_methods!(i64x2, i64, i64x2, 2)
+simd_int_methods!(u8x16, u8, i8x16, 16)
+simd_int_methods!(u16x8, u16, i16x8, 8)
+simd_int_methods!(u32x4, u32, i32x4, 4)
+simd_int_methods!(u64x2, u64, i64x2, 2)
+simd_float_methods!(f32x4, f32, i32x4, 4)
+simd_float_methods!(f64x2, f64, i64x2, 2)
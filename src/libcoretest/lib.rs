@@ -7,7 +7,7 @@
 // <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
-#![feature(globs, unsafe_destructor, macro_rules, slicing_syntax)]
+#![feature(globs, unsafe_destructor, macro_rules, slicing_syntax, simd)]
 
 extern crate core;
 extern crate test;
@@ -28,6 +28,7 @@ mod option;
 mod ptr;
 mod raw;
 mod result;
+mod simd;
 mod slice;
 mod str;
 mod tuple;
@@ -0,0 +1,108 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::f32;
+use core::intrinsics;
+use core::simd::*;
+
+#[test]
+fn test_load_store() {
+    let src = [1i32, 2, 3, 4, 5, 6];
+    let v = i32x4::load(&src, 1);
+    assert_eq!(v.to_array().as_slice(), [2, 3, 4, 5].as_slice());
+
+    let mut dst = [0i32, ..6];
+    v.store(&mut dst, 2);
+    assert_eq!(dst.as_slice(), [0, 0, 2, 3, 4, 5].as_slice());
+}
+
+#[test]
+#[should_fail]
+fn test_load_out_of_bounds() {
+    let src = [1u8, ..15];
+    u8x16::load(&src, 0);
+}
+
+#[test]
+fn test_lanes() {
+    let v = f64x2::splat(1.5).replace(1, -2.0);
+    assert_eq!(v.extract(0), 1.5);
+    assert_eq!(v.extract(1), -2.0);
+    assert_eq!(u16x8::from_array([7, ..8]).to_array().as_slice(),
+               u16x8::splat(7).to_array().as_slice());
+}
+
+#[test]
+fn test_comparisons() {
+    let a = f32x4(1.0, 2.0, 3.0, f32::NAN);
+    let b = f32x4(3.0, 2.0, 1.0, f32::NAN);
+    assert_eq!(a.lt(b).to_array().as_slice(), [-1, 0, 0, 0].as_slice());
+    assert_eq!(a.le(b).to_array().as_slice(), [-1, -1, 0, 0].as_slice());
+    assert_eq!(a.eq(b).to_array().as_slice(), [0, -1, 0, 0].as_slice());
+    assert_eq!(a.ne(b).to_array().as_slice(), [-1, 0, -1, -1].as_slice());
+
+    // Unsigned lanes compare as unsigned
+    let x = u8x16::splat(200);
+    let y = u8x16::splat(100);
+    assert!(x.gt(y).all());
+    assert!(!x.le(y).any());
+}
+
+#[test]
+fn test_select_min_max() {
+    let a = i32x4(1, -2, 3, -4);
+    let b = i32x4(-1, 2, -3, 4);
+    assert_eq!(a.min(b).to_array().as_slice(), [-1, -2, -3, -4].as_slice());
+    assert_eq!(a.max(b).to_array().as_slice(), [1, 2, 3, 4].as_slice());
+    let mask = i32x4(0, -1, 0, -1);
+    assert_eq!(i32x4::select(mask, a, b).to_array().as_slice(), [-1, -2, -3, -4].as_slice());
+
+    let c = f32x4(1.0, 5.0, f32::NAN, 0.0);
+    let d = f32x4(2.0, 4.0, 1.0, f32::NAN);
+    assert_eq!(c.min(d).extract(0), 1.0);
+    assert_eq!(c.min(d).extract(1), 4.0);
+    assert_eq!(c.min(d).extract(2), 1.0);
+    assert!(c.min(d).extract(3).is_nan());
+    assert_eq!(c.max(d).extract(1), 5.0);
+}
+
+#[test]
+fn test_horizontal() {
+    let v = i16x8(1, 2, 3, 4, 5, 6, 7, -8);
+    assert_eq!(v.sum(), 20);
+    assert_eq!(i64x2(-3, 7).product(), -21);
+    assert_eq!(v.min_lane(), -8);
+    assert_eq!(v.max_lane(), 7);
+    assert_eq!(f32x4(4.0, 9.0, 16.0, 25.0).sqrt().sum(), 14.0);
+}
+
+#[test]
+fn test_intrinsics() {
+    unsafe {
+        let a = u32x4(0, 1, 2, 3);
+        let b = u32x4(4, 5, 6, 7);
+        let lo: u32x4 = intrinsics::simd_shuffle4(a, b, [0, 4, 1, 5]);
+        assert_eq!(lo.to_array().as_slice(), [0, 4, 1, 5].as_slice());
+        let pair: u32x2 = intrinsics::simd_shuffle2(a, b, [7, 0]);
+        assert_eq!(pair, u32x2(7, 0));
+
+        let f: f32x4 = intrinsics::simd_cast(i32x4(-1, 0, 1, 2));
+        assert_eq!(f.to_array().as_slice(), [-1.0, 0.0, 1.0, 2.0].as_slice());
+        let w: i64x2 = intrinsics::simd_cast(i32x2(-1, 1));
+        assert_eq!(w.to_array().as_slice(), [-1, 1].as_slice());
+    }
+}
+
+#[simd]
+#[deriving(PartialEq, Show)]
+struct u32x2(u32, u32);
+
+#[simd]
+struct i32x2(i32, i32);
@@ -12,6 +12,7 @@ use stack::Stack;
 use std::uint;
 use std::mem::transmute;
 use std::rt::stack;
+use std::rt::stack_overflow;
 use std::raw;
 #[cfg(target_arch = "x86_64")]
 use std::simd;
@@ -99,6 +100,11 @@ impl Context {
         rtdebug!("noting the stack limit and doing raw swap");
 
         unsafe {
+            // The lowest page of each task's stack is its guard page (see
+            // `Stack::new`), which the scheduler's stack overflow handler
+            // needs to know about to recognize faults in it.
+            stack_overflow::set_guard_page(in_context.stack_bounds.map(|(lo, _)| lo));
+
             // Right before we switch to the new context, set the new context's
             // stack limit in the OS-specified TLS slot. This also  means that
             // we cannot call any more rust functions after record_stack_bounds
@@ -12,6 +12,7 @@ use std::mem;
 use std::rt::local::Local;
 use std::rt::mutex::NativeMutex;
 use std::rt::rtio::{RemoteCallback, PausableIdleCallback, Callback, EventLoop};
+use std::rt::stack_overflow;
 use std::rt::task::BlockedTask;
 use std::rt::task::Task;
 use std::sync::deque;
@@ -181,6 +182,9 @@ impl Scheduler {
     // Take a main task to run, and a scheduler to run it in. Create a
     // scheduler task and bootstrap into it.
     pub fn bootstrap(mut self: Box<Scheduler>) {
+        // Report overflows of the scheduler's tasks, as libnative does for its
+        // own.
+        let _handler = unsafe { stack_overflow::Handler::new() };
 
         // Build an Idle callback.
         let cb = box SchedRunner as Box<Callback + Send>;
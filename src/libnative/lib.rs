@@ -131,6 +131,7 @@ pub fn start(argc: int, argv: *const *const u8, main: proc()) -> int {
     ignore_sigpipe();
 
     rt::init(argc, argv);
+    let _handler = unsafe { rt::stack_overflow::Handler::new() };
     let mut exit_code = None;
     let mut main = Some(main);
     let mut task = task::new((my_stack_bottom, my_stack_top));
@@ -21,6 +21,7 @@ use std::rt::local::Local;
 use std::rt::mutex::NativeMutex;
 use std::rt::rtio;
 use std::rt::stack;
+use std::rt::stack_overflow;
 use std::rt::task::{Task, BlockedTask, TaskOpts};
 use std::rt::thread::Thread;
 use std::rt;
@@ -86,6 +87,7 @@ pub fn spawn_opts(opts: TaskOpts, f: proc():Send) {
         unsafe {
             stack::record_os_managed_stack_bounds(my_stack - stack + 1024, my_stack);
         }
+        let _handler = unsafe { stack_overflow::Handler::new() };
         let mut ops = ops;
         ops.stack_bounds = (my_stack - stack + 1024, my_stack);
 
@@ -338,6 +338,51 @@ pub fn mangle<PI: Iterator<PathElem>>(mut path: PI,
     n
 }
 
+/// Turns a symbol made by `mangle` back into a path, without the hash. Other
+/// symbols are returned unchanged.
+pub fn demangle(symbol: &str) -> String {
+    if !symbol.starts_with("_ZN") || !symbol.ends_with("E") {
+        return symbol.to_string();
+    }
+
+    let mut rest = symbol.slice(3, symbol.len() - 1);
+    let mut elems = vec!();
+    while !rest.is_empty() {
+        let digits = rest.chars().take_while(|c| c.is_digit()).count();
+        let len = match from_str::<uint>(rest.slice_to(digits)) {
+            Some(len) if digits + len <= rest.len() => len,
+            _ => return symbol.to_string(),
+        };
+        elems.push(rest.slice(digits, digits + len));
+        rest = rest.slice_from(digits + len);
+    }
+
+    // The hash is an `h` and 16 hex digits, possibly followed by the
+    // characters `mangle_exported_name` adds to tell items apart.
+    let is_hash = match elems.last() {
+        Some(elem) => elem.len() >= 17 && elem.starts_with("h") &&
+                      elem.slice(1, 17).chars().all(|c| c.is_digit_radix(16)),
+        None => false,
+    };
+    if is_hash {
+        elems.pop();
+    }
+
+    // The reverse of `sanitize`, except for the unicode escapes.
+    static ESCAPES: &'static [(&'static str, &'static str)] = &[
+        ("$SP$", "@"), ("$UP$", "~"), ("$RP$", "*"), ("$BP$", "&"), ("$LT$", "<"),
+        ("$GT$", ">"), ("$LP$", "("), ("$C$", ","), (".", ":"),
+    ];
+    let elems: Vec<String> = elems.iter().map(|elem| {
+        let mut elem = elem.to_string();
+        for &(escape, c) in ESCAPES.iter() {
+            elem = elem.as_slice().replace(escape, c);
+        }
+        elem
+    }).collect();
+    elems.connect("::")
+}
+
 pub fn exported_name(path: PathElems, hash: &str) -> String {
     mangle(path, Some(hash))
 }
@@ -761,6 +806,9 @@ fn link_staticlib(sess: &Session, obj_filename: &Path, out_filename: &Path) {
     };
     ab.add_native_library("morestack").unwrap();
     ab.add_native_library("compiler-rt").unwrap();
+    if sess.opts.cg.profile_generate || sess.coverage() {
+        ab.add_native_library("profiler-rt").unwrap();
+    }
 
     let crates = sess.cstore.get_used_crates(cstore::RequireStatic);
     let mut all_native_libs = vec![];
@@ -862,6 +910,40 @@ fn link_natively(sess: &Session, trans: &CrateTranslation, dylib: bool,
                 }
             }
         }
+
+    // The linker reads the compressed debug sections of the objects, but
+    // writes its own output uncompressed.
+    if sess.compress_debuginfo() && is_elf(sess) {
+        let mut cmd = Command::new("objcopy");
+        cmd.arg("--compress-debug-sections").arg(out_filename);
+        debug!("{}", &cmd);
+        match cmd.output() {
+            Ok(prog) => {
+                if !prog.status.success() {
+                    sess.err(format!("compressing debuginfo with `objcopy` failed: {}",
+                                     prog.status).as_slice());
+                    sess.note(format!("{}", &cmd).as_slice());
+                    let mut output = prog.error.clone();
+                    output.push_all(prog.output.as_slice());
+                    sess.note(str::from_utf8(output.as_slice()).unwrap());
+                    sess.abort_if_errors();
+                }
+            }
+            Err(e) => {
+                sess.err(format!("could not exec `objcopy`: {}", e).as_slice());
+                sess.abort_if_errors();
+            }
+        }
+    }
+}
+
+/// Whether the target's object files are ELF, the only format that the
+/// `-C debuginfo` options apply to.
+pub fn is_elf(sess: &Session) -> bool {
+    match sess.targ_cfg.os {
+        abi::OsMacos | abi::OsiOS | abi::OsWindows => false,
+        _ => true,
+    }
 }
 
 fn link_args(cmd: &mut Command,
@@ -909,6 +991,19 @@ fn link_args(cmd: &mut Command,
         }
     }
 
+    // The sanitizer runtimes replace libc functions like `malloc` and
+    // `pthread_create`, so they go into executables whole, with their symbols
+    // exported for shared libraries to bind to. Dynamic libraries use the
+    // runtime of the executable that loads them.
+    match sess.opts.sanitizer {
+        Some(sanitizer) if !dylib => {
+            let rt = format!("-l{}-rt", sanitizer.short_name());
+            cmd.args(["-Wl,--whole-archive", rt.as_slice(), "-Wl,--no-whole-archive",
+                      "-rdynamic", "-lpthread", "-lrt", "-ldl", "-lm"]);
+        }
+        _ => {}
+    }
+
     // When linking a dynamic library, we put the metadata into a section of the
     // executable. This metadata is in a separate object file from the main
     // object file, so we link that in here.
@@ -1133,6 +1228,12 @@ fn link_args(cmd: &mut Command,
         cmd.args(rpath::get_rpath_flags(rpath_config).as_slice());
     }
 
+    // Code instrumented by `-C profile-generate` or `-Z coverage` needs the
+    // runtime which writes out its profile or counts when the program exits.
+    if sess.opts.cg.profile_generate || sess.coverage() {
+        cmd.arg("-lprofiler-rt");
+    }
+
     // compiler-rt contains implementations of low-level LLVM helpers. This is
     // used to resolve symbols from the object file we just created, as well as
     // any system static libraries that may be expecting gcc instead. Most
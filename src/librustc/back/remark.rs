@@ -0,0 +1,217 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Optimization remarks from LLVM, for `-C remark` and `-C remark-report`.
+//!
+//! The remarks of the inliner and the loop vectorizer are classified by what
+//! happened to the call or loop, and why if LLVM says; the remarks of other
+//! passes are kept as they are. With `-C remark-report` they are written as
+//! JSON to `<crate>.remarks.json` next to the other outputs, grouped by the
+//! function the remark was made in.
+//!
+//! LLVM finds the source of a remark through the line tables, which is why
+//! asking for remarks turns those on.
+
+use back::link;
+use driver::session::Session;
+use llvm::diagnostic::{OptimizationDiagnosticKind, OptimizationRemark, OptimizationMissed};
+use llvm::diagnostic::{OptimizationAnalysis, OptimizationFailure};
+
+use std::collections::TreeMap;
+use std::io::File;
+use std::mem;
+use std::sync::{Arc, Mutex};
+use serialize::{json, Encodable};
+use syntax::codemap::{BytePos, Pos, Span, NO_EXPANSION};
+
+/// What a remark says happened to the call or loop it is about.
+#[deriving(Clone, PartialEq, Show)]
+pub enum RemarkKind {
+    Inlined,
+    NotInlined,
+    Vectorized,
+    NotVectorized,
+    OtherRemark,
+}
+
+impl RemarkKind {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Inlined => "inlined",
+            NotInlined => "not_inlined",
+            Vectorized => "vectorized",
+            NotVectorized => "not_vectorized",
+            OtherRemark => "other",
+        }
+    }
+}
+
+#[deriving(Clone, Encodable)]
+pub struct Location {
+    pub file: String,
+    pub line: uint,
+    /// Counted in characters from one, like in the compiler's messages.
+    pub col: uint,
+}
+
+#[deriving(Clone, Encodable)]
+pub struct Remark {
+    /// One of the names of `RemarkKind`.
+    pub kind: String,
+    /// How LLVM classified the remark: `remark`, `missed`, `analysis` or
+    /// `failure`.
+    pub level: String,
+    pub pass: String,
+    /// The function that was or wasn't inlined.
+    pub callee: Option<String>,
+    /// Why the call wasn't inlined or the loop wasn't vectorized.
+    pub reason: Option<String>,
+    /// LLVM's message, with the symbols in it demangled.
+    pub message: String,
+    pub location: Option<Location>,
+}
+
+impl Remark {
+    pub fn new(level: OptimizationDiagnosticKind,
+               pass: &str,
+               message: &str,
+               location: Option<Location>) -> Remark {
+        let message: Vec<String> = message.split(' ').map(|word| link::demangle(word)).collect();
+        let message = message.connect(" ");
+        let (kind, callee, reason) = classify(level, pass, message.as_slice());
+        Remark {
+            kind: kind.name().to_string(),
+            level: level.describe().to_string(),
+            pass: pass.to_string(),
+            callee: callee,
+            reason: reason,
+            message: message,
+            location: location,
+        }
+    }
+}
+
+/// Works out what happened from the messages of the inliner, which start
+/// with the callee (`f inlined into g`, `f too costly to inline (cost=...)`),
+/// and of the loop vectorizer (`loop not vectorized: <reason>`).
+fn classify(level: OptimizationDiagnosticKind, pass: &str, message: &str)
+            -> (RemarkKind, Option<String>, Option<String>) {
+    match pass {
+        "inline" => {
+            let (callee, rest) = match message.find(' ') {
+                Some(i) => (Some(message.slice_to(i).to_string()), message.slice_from(i + 1)),
+                None => (None, message),
+            };
+            match level {
+                OptimizationRemark => (Inlined, callee, None),
+                OptimizationMissed | OptimizationFailure => (NotInlined, callee, None),
+                OptimizationAnalysis if rest.starts_with("too costly") ||
+                                        rest.starts_with("should never") ||
+                                        rest.contains("not be inlined") => {
+                    (NotInlined, callee, Some(rest.to_string()))
+                }
+                OptimizationAnalysis => (OtherRemark, callee, None),
+            }
+        }
+        "loop-vectorize" => {
+            match level {
+                OptimizationRemark => (Vectorized, None, None),
+                _ => {
+                    let prefix = "loop not vectorized: ";
+                    let reason = if message.starts_with(prefix) {
+                        Some(message.slice_from(prefix.len()).to_string())
+                    } else {
+                        match level {
+                            OptimizationAnalysis => Some(message.to_string()),
+                            _ => None,
+                        }
+                    };
+                    (NotVectorized, None, reason)
+                }
+            }
+        }
+        _ => (OtherRemark, None, None),
+    }
+}
+
+/// The remarks of all codegen units, each with the symbol of the function it
+/// was made in, collected for the report.
+pub type RemarkSink = Arc<Mutex<Vec<(String, Remark)>>>;
+
+/// Finds the span of a location in one of the files of this crate. Code
+/// inlined from other crates has no span.
+pub fn span_for_location(sess: &Session, loc: &Location) -> Option<Span> {
+    let path = sess.working_dir.join(loc.file.as_slice());
+    let files = sess.codemap().files.borrow();
+    let fm = match files.iter().find(|fm| sess.working_dir.join(fm.name.as_slice()) == path) {
+        Some(fm) => fm,
+        None => return None,
+    };
+
+    let lines = fm.lines.borrow();
+    if loc.line == 0 || loc.line > lines.len() {
+        return None;
+    }
+    let line_start = *lines.get(loc.line - 1);
+    let line = fm.src.as_slice().slice_from((line_start - fm.start_pos).to_uint());
+    let line = line.slice_to(line.find('\n').unwrap_or(line.len()));
+    let offset = match line.char_indices().nth(loc.col - 1) {
+        Some((offset, _)) => offset,
+        None => line.len(),
+    };
+    let pos = line_start + BytePos(offset as u32);
+    Some(Span { lo: pos, hi: pos, expn_id: NO_EXPANSION })
+}
+
+#[deriving(Encodable)]
+struct FunctionRemarks {
+    function: String,
+    symbol: String,
+    remarks: Vec<Remark>,
+}
+
+#[deriving(Encodable)]
+struct RemarkReport {
+    crate_name: String,
+    functions: Vec<FunctionRemarks>,
+}
+
+pub fn write_report(sess: &Session, crate_name: &str, path: &Path, sink: RemarkSink) {
+    let remarks = mem::replace(&mut *sink.lock(), vec!());
+    let mut functions: TreeMap<(String, String), Vec<Remark>> = TreeMap::new();
+    for (symbol, remark) in remarks.into_iter() {
+        let key = (link::demangle(symbol.as_slice()), symbol);
+        if !functions.contains_key(&key) {
+            functions.insert(key.clone(), vec!());
+        }
+        functions.find_mut(&key).unwrap().push(remark);
+    }
+
+    let report = RemarkReport {
+        crate_name: crate_name.to_string(),
+        functions: functions.into_iter().map(|((function, symbol), remarks)| {
+            FunctionRemarks {
+                function: function,
+                symbol: symbol,
+                remarks: remarks,
+            }
+        }).collect(),
+    };
+
+    let result = File::create(path).and_then(|mut file| {
+        let mut encoder = json::PrettyEncoder::new(&mut file);
+        report.encode(&mut encoder)
+    });
+    match result {
+        Err(e) => sess.fatal(format!("could not write {}: {}",
+                                     path.display(), e).as_slice()),
+        Ok(()) => {}
+    }
+}
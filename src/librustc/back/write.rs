@@ -9,7 +9,9 @@
 // except according to those terms.
 
 use back::lto;
-use back::link::{get_cc_prog, remove};
+use back::link::{get_cc_prog, is_elf, remove};
+use back::remark;
+use back::remark::{Remark, RemarkSink};
 use driver::driver::{CrateTranslation, ModuleTranslation, OutputFilenames};
 use driver::config::{NoDebugInfo, Passes, SomePasses, AllPasses};
 use driver::session::Session;
@@ -30,6 +32,7 @@ use std::iter::Unfold;
 use std::ptr;
 use std::str;
 use std::mem;
+use std::os;
 use std::sync::{Arc, Mutex};
 use std::task::TaskBuilder;
 use libc::{c_uint, c_int, c_void};
@@ -207,6 +210,7 @@ fn create_target_machine(sess: &Session) -> TargetMachineRef {
     let ffunction_sections = sess.targ_cfg.os != abi::OsMacos &&
                              sess.targ_cfg.os != abi::OsWindows;
     let fdata_sections = ffunction_sections;
+    let compress_debug_sections = sess.compress_debuginfo();
 
     let code_model = match sess.opts.cg.code_model.as_slice() {
         "default" => llvm::CodeModelDefault,
@@ -237,11 +241,12 @@ fn create_target_machine(sess: &Session) -> TargetMachineRef {
                         code_model,
                         reloc_model,
                         opt_level,
-                        true /* EnableSegstk */,
+                        sess.stack_check() == config::SplitStack,
                         use_softfp,
                         no_fp_elim,
                         ffunction_sections,
                         fdata_sections,
+                        compress_debug_sections,
                     )
                 })
             })
@@ -249,6 +254,18 @@ fn create_target_machine(sess: &Session) -> TargetMachineRef {
     }
 }
 
+/// Whether code for the target may use the LLVM subtarget feature `feature`
+/// (e.g. `sse2` or `avx`), which it can if the CPU chosen by `-C target-cpu`
+/// has it or `-C target-feature` turns it on.
+pub fn target_has_feature(sess: &Session, feature: &str) -> bool {
+    unsafe {
+        configure_llvm(sess);
+        let tm = create_target_machine(sess);
+        let has = feature.with_c_str(|f| llvm::LLVMRustHasFeature(tm, f));
+        llvm::LLVMRustDisposeTargetMachine(tm);
+        has
+    }
+}
 
 /// Module-specific configuration for `optimize_and_codegen`.
 #[deriving(Clone)]
@@ -275,6 +292,17 @@ struct ModuleConfig {
     no_prepopulate_passes: bool,
     no_builtins: bool,
     time_passes: bool,
+
+    // Profile-guided optimization: whether to instrument the module, and the
+    // merged profile to optimize it with.
+    profile_generate: bool,
+    profile_use: Option<String>,
+    // Whether to add gcov line coverage counters (`-Z coverage`).
+    coverage: bool,
+    // Whether to move the debuginfo into .dwo files (`-C debuginfo=split`).
+    split_debuginfo: bool,
+    // Which runtime checks to instrument the module with (`-Z sanitizer`).
+    sanitizer: Option<config::Sanitizer>,
 }
 
 impl ModuleConfig {
@@ -295,6 +323,12 @@ impl ModuleConfig {
             no_prepopulate_passes: false,
             no_builtins: false,
             time_passes: false,
+
+            profile_generate: false,
+            profile_use: None,
+            coverage: false,
+            split_debuginfo: false,
+            sanitizer: None,
         }
     }
 
@@ -315,14 +349,20 @@ struct CodegenContext<'a> {
     handler: &'a Handler,
     // LLVM optimizations for which we want to print remarks.
     remark: Passes,
+    // Where to collect the remarks instead of printing them, for
+    // `-C remark-report`.
+    remark_sink: Option<RemarkSink>,
 }
 
 impl<'a> CodegenContext<'a> {
-    fn new_with_session(sess: &'a Session, reachable: &'a [String]) -> CodegenContext<'a> {
+    fn new_with_session(sess: &'a Session,
+                        reachable: &'a [String],
+                        remark_sink: Option<RemarkSink>) -> CodegenContext<'a> {
         CodegenContext {
             lto_ctxt: Some((sess, reachable)),
             handler: sess.diagnostic().handler(),
             remark: sess.opts.cg.remark.clone(),
+            remark_sink: remark_sink,
         }
     }
 }
@@ -372,12 +412,21 @@ unsafe extern "C" fn diagnostic_handler(info: DiagnosticInfoRef, user: *mut c_vo
             };
 
             if enabled {
-                let loc = llvm::debug_loc_to_string(llcx, opt.debug_loc);
-                cgcx.handler.note(format!("optimization {:s} for {:s} at {:s}: {:s}",
-                                          opt.kind.describe(),
-                                          pass_name,
-                                          if loc.is_empty() { "[unknown]" } else { loc.as_slice() },
-                                          llvm::twine_to_string(opt.message)).as_slice());
+                let location = llvm::unpack_debug_loc(llcx, opt.debug_loc).map(|(file, line, col)| {
+                    remark::Location { file: file, line: line, col: col + 1 }
+                });
+                let remark = Remark::new(opt.kind, pass_name,
+                                         llvm::twine_to_string(opt.message).as_slice(),
+                                         location);
+
+                match cgcx.remark_sink {
+                    Some(ref sink) => {
+                        let symbol = CString::new(llvm::LLVMGetValueName(opt.function), false);
+                        let symbol = symbol.as_str().expect("got a non-UTF8 symbol from LLVM");
+                        sink.lock().push((symbol.to_string(), remark));
+                    }
+                    None => report_remark(cgcx, &remark),
+                }
             }
         }
 
@@ -385,6 +434,27 @@ unsafe extern "C" fn diagnostic_handler(info: DiagnosticInfoRef, user: *mut c_vo
     }
 }
 
+fn report_remark(cgcx: &CodegenContext, remark: &Remark) {
+    let msg = format!("optimization {:s} for {:s}: {:s}",
+                      remark.level, remark.pass, remark.message);
+
+    // Only the main thread can map the location to a span in the crate.
+    let span = match (cgcx.lto_ctxt, &remark.location) {
+        (Some((sess, _)), &Some(ref loc)) => {
+            remark::span_for_location(sess, loc).map(|span| (sess, span))
+        }
+        _ => None,
+    };
+    match (span, &remark.location) {
+        (Some((sess, span)), _) => sess.span_note(span, msg.as_slice()),
+        (None, &Some(ref loc)) => {
+            cgcx.handler.note(format!("{}:{}:{}: {}", loc.file, loc.line, loc.col,
+                                      msg).as_slice())
+        }
+        (None, &None) => cgcx.handler.note(format!("[unknown]: {}", msg).as_slice()),
+    }
+}
+
 // Unsafe due to LLVM calls.
 unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
                                mtrans: ModuleTranslation,
@@ -414,6 +484,34 @@ unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
         })
     }
 
+    // The profiling passes must see the module exactly as it was translated,
+    // because `-C profile-use` finds the counts of each block by position, and
+    // coverage counts are only meaningful per source line before inlining.
+    if config.profile_generate || config.profile_use.is_some() || config.coverage {
+        let pm = llvm::LLVMCreatePassManager();
+        if config.profile_generate {
+            llvm::LLVMRustAddProfileInstrumentationPass(pm);
+        }
+        if config.coverage {
+            // The runtime writes the .gcda file next to the .gcno one, so
+            // the path has to survive the program running elsewhere.
+            let ext = format!("{}.gcno", name_extra);
+            let notes = os::make_absolute(&output_names.with_extension(ext.as_slice()));
+            notes.with_c_str(|s| llvm::LLVMRustAddGcovPass(pm, llmod, s));
+        }
+        match config.profile_use {
+            Some(ref path) => {
+                if !path.as_slice().with_c_str(|s| llvm::LLVMRustAddProfileUsePass(pm, s)) {
+                    llvm_err(cgcx.handler, format!("failed to read profile `{}`", path));
+                }
+            }
+            None => {}
+        }
+        time(config.time_passes, "llvm profile passes", (), |()|
+             llvm::LLVMRunPassManager(pm, llmod));
+        llvm::LLVMDisposePassManager(pm);
+    }
+
     match config.opt_level {
         Some(opt_level) => {
             // Create the two optimizing pass managers. These mirror what clang
@@ -446,6 +544,16 @@ unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
                 })
             }
 
+            // Like clang, instrument the optimized code, so that the checks
+            // aren't in the way of the optimizations and the accesses they
+            // remove don't get checked.
+            match config.sanitizer {
+                Some(config::AddressSanitizer) => llvm::LLVMRustAddAddressSanitizerPasses(mpm),
+                Some(config::ThreadSanitizer) => llvm::LLVMRustAddThreadSanitizerPass(mpm),
+                Some(config::MemorySanitizer) => llvm::LLVMRustAddMemorySanitizerPass(mpm),
+                None => {}
+            }
+
             // Finally, run the actual optimization passes
             time(config.time_passes, "llvm function passes", (), |()|
                  llvm::LLVMRustRunFunctionPassManager(fpm, llmod));
@@ -498,6 +606,8 @@ unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
         })
     }
 
+    let dwo_path = dwo_path(&output_names, name_extra.as_slice());
+
     time(config.time_passes, "codegen passes", (), |()| {
         if config.emit_ir {
             let ext = format!("{}.ll", name_extra);
@@ -520,6 +630,10 @@ unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
             with_codegen(tm, llmod, config.no_builtins, |cpm| {
                 write_output_file(cgcx.handler, tm, cpm, llmod, &path, llvm::ObjectFileType);
             });
+
+            if config.split_debuginfo {
+                extract_dwo(cgcx.handler, &path, &dwo_path);
+            }
         }
     });
 
@@ -528,6 +642,41 @@ unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
     llvm::LLVMRustDisposeTargetMachine(tm);
 }
 
+/// The .dwo file for the codegen unit `name_extra` with `-C debuginfo=split`.
+/// The skeleton compile units left in the object refer to it by this name, so
+/// it is absolute for debuggers to find it wherever the program is run from.
+pub fn dwo_path(output_names: &OutputFilenames, name_extra: &str) -> Path {
+    os::make_absolute(&output_names.with_extension(format!("{}.dwo", name_extra).as_slice()))
+}
+
+/// Moves the sections LLVM emits for split DWARF out of the object file and
+/// into a .dwo file of their own, which the linker never has to look at.
+fn extract_dwo(handler: &Handler, obj_path: &Path, dwo_path: &Path) {
+    let mut extract = Command::new("objcopy");
+    extract.arg("--extract-dwo").arg(obj_path).arg(dwo_path);
+    let mut strip = Command::new("objcopy");
+    strip.arg("--strip-dwo").arg(obj_path);
+
+    for cmd in [extract, strip].iter() {
+        match cmd.output() {
+            Ok(prog) => {
+                if !prog.status.success() {
+                    handler.err(format!("splitting debuginfo with `objcopy` failed: {}",
+                                        prog.status).as_slice());
+                    handler.note(format!("{}", cmd).as_slice());
+                    let mut note = prog.error.clone();
+                    note.push_all(prog.output.as_slice());
+                    handler.note(str::from_utf8(note.as_slice()).unwrap());
+                    handler.abort_if_errors();
+                }
+            }
+            Err(e) => {
+                handler.fatal(format!("could not exec `objcopy`: {}", e).as_slice());
+            }
+        }
+    }
+}
+
 pub fn run_passes(sess: &Session,
                   trans: &CrateTranslation,
                   output_types: &[OutputType],
@@ -545,6 +694,34 @@ pub fn run_passes(sess: &Session,
         sess.fatal("can't perform LTO when using multiple codegen units");
     }
 
+    // The profiling runtime finds the counters through the sections they are
+    // placed in, which only works with ELF and Mach-O.
+    if sess.opts.cg.profile_generate {
+        match sess.targ_cfg.os {
+            abi::OsLinux | abi::OsAndroid | abi::OsMacos | abi::OsiOS => {}
+            _ => sess.fatal("-C profile-generate is not supported on this target"),
+        }
+    }
+    if sess.coverage() && sess.targ_cfg.os == abi::OsWindows {
+        sess.fatal("-Z coverage is not supported on this target");
+    }
+    // Split DWARF and compressed debug sections are extensions of ELF.
+    if sess.split_debuginfo() && !is_elf(sess) {
+        sess.fatal("-C debuginfo=split is not supported on this target");
+    }
+    if sess.compress_debuginfo() && !is_elf(sess) {
+        sess.warn("-C debuginfo=compress has no effect on this target");
+    }
+    // compiler-rt only has the sanitizer runtimes for 64-bit Linux.
+    match sess.opts.sanitizer {
+        Some(sanitizer) if sess.targ_cfg.os != abi::OsLinux ||
+                           sess.targ_cfg.arch != abi::X86_64 => {
+            sess.fatal(format!("-Z sanitizer={} is not supported on this target",
+                               sanitizer.name()).as_slice());
+        }
+        _ => {}
+    }
+
     // Sanity check
     assert!(trans.modules.len() == sess.opts.cg.codegen_units);
 
@@ -603,6 +780,13 @@ pub fn run_passes(sess: &Session,
     modules_config.set_flags(sess, trans);
     metadata_config.set_flags(sess, trans);
 
+    // Only the modules with code are instrumented.
+    modules_config.profile_generate = sess.opts.cg.profile_generate;
+    modules_config.profile_use = sess.opts.cg.profile_use.clone();
+    modules_config.coverage = sess.coverage();
+    modules_config.split_debuginfo = sess.split_debuginfo();
+    modules_config.sanitizer = sess.opts.sanitizer;
+
 
     // Populate a buffer with a list of codegen tasks.  Items are processed in
     // LIFO order, just because it's a tiny bit simpler that way.  (The order
@@ -627,11 +811,27 @@ pub fn run_passes(sess: &Session,
         work_items.push(work);
     }
 
+    let remark_sink = if sess.opts.cg.remark_report {
+        Some(Arc::new(Mutex::new(vec!())))
+    } else {
+        None
+    };
+
     // Process the work items, optionally using worker threads.
     if sess.opts.cg.codegen_units == 1 {
-        run_work_singlethreaded(sess, trans.reachable.as_slice(), work_items);
+        run_work_singlethreaded(sess, trans.reachable.as_slice(), work_items,
+                                remark_sink.clone());
     } else {
-        run_work_multithreaded(sess, work_items, sess.opts.cg.codegen_units);
+        run_work_multithreaded(sess, work_items, sess.opts.cg.codegen_units,
+                               remark_sink.clone());
+    }
+
+    match remark_sink {
+        Some(sink) => {
+            remark::write_report(sess, trans.link.crate_name.as_slice(),
+                                 &crate_output.with_extension("remarks.json"), sink);
+        }
+        None => {}
     }
 
     // All codegen is finished.
@@ -835,8 +1035,9 @@ fn build_work_item(sess: &Session,
 
 fn run_work_singlethreaded(sess: &Session,
                            reachable: &[String],
-                           work_items: Vec<WorkItem>) {
-    let cgcx = CodegenContext::new_with_session(sess, reachable);
+                           work_items: Vec<WorkItem>,
+                           remark_sink: Option<RemarkSink>) {
+    let cgcx = CodegenContext::new_with_session(sess, reachable, remark_sink);
     let mut work_items = work_items;
 
     // Since we're running single-threaded, we can pass the session to
@@ -848,7 +1049,8 @@ fn run_work_singlethreaded(sess: &Session,
 
 fn run_work_multithreaded(sess: &Session,
                           work_items: Vec<WorkItem>,
-                          num_workers: uint) {
+                          num_workers: uint,
+                          remark_sink: Option<RemarkSink>) {
     // Run some workers to process the work items.
     let work_items_arc = Arc::new(Mutex::new(work_items));
     let mut diag_emitter = SharedEmitter::new();
@@ -858,6 +1060,7 @@ fn run_work_multithreaded(sess: &Session,
         let work_items_arc = work_items_arc.clone();
         let diag_emitter = diag_emitter.clone();
         let remark = sess.opts.cg.remark.clone();
+        let remark_sink = remark_sink.clone();
 
         let future = TaskBuilder::new().named(format!("codegen-{}", i)).try_future(proc() {
             let diag_handler = mk_handler(box diag_emitter);
@@ -868,6 +1071,7 @@ fn run_work_multithreaded(sess: &Session,
                 lto_ctxt: None,
                 handler: &diag_handler,
                 remark: remark,
+                remark_sink: remark_sink,
             };
 
             loop {
@@ -959,6 +1163,7 @@ unsafe fn configure_llvm(sess: &Session) {
         if vectorize_slp  { add("-vectorize-slp");   }
         if sess.time_llvm_passes() { add("-time-passes"); }
         if sess.print_llvm_passes() { add("-debug-pass=Structure"); }
+        if sess.split_debuginfo() { add("-split-dwarf=Enable"); }
 
         for arg in sess.opts.cg.llvm_args.iter() {
             add((*arg).as_slice());
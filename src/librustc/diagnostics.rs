@@ -150,5 +150,13 @@ register_diagnostics!(
     E0161,
     E0162,
     E0163,
-    E0164
+    E0164,
+    E0165,
+    E0166,
+    E0167,
+    E0168,
+    E0169,
+    E0170,
+    E0171,
+    E0172
 )
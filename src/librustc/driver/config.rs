@@ -72,6 +72,8 @@ pub struct Options {
     pub optimize: OptLevel,
     pub debuginfo: DebugInfoLevel,
     pub lint_opts: Vec<(String, lint::Level)>,
+    /// File to load additional lint levels from, see `lint::config`.
+    pub lint_config: Option<Path>,
     pub describe_lints: bool,
     pub output_types: Vec<back::write::OutputType> ,
     // This was mutable for rustpkg, which updates search paths based on the
@@ -90,6 +92,8 @@ pub struct Options {
     pub no_trans: bool,
     pub no_analysis: bool,
     pub debugging_opts: u64,
+    /// The sanitizer to instrument the crate with (`-Z sanitizer`).
+    pub sanitizer: Option<Sanitizer>,
     /// Whether to write dependency files. It's (enabled, optional filename).
     pub write_dependency_info: (bool, Option<Path>),
     /// Crate id-related things to maybe print. It's (crate_name, crate_file_name).
@@ -112,6 +116,7 @@ pub fn basic_options() -> Options {
         optimize: No,
         debuginfo: NoDebugInfo,
         lint_opts: Vec::new(),
+        lint_config: None,
         describe_lints: false,
         output_types: Vec::new(),
         addl_lib_search_paths: RefCell::new(Vec::new()),
@@ -123,6 +128,7 @@ pub fn basic_options() -> Options {
         no_trans: false,
         no_analysis: false,
         debugging_opts: 0,
+        sanitizer: None,
         write_dependency_info: (false, None),
         print_metas: (false, false),
         cg: basic_codegen_options(),
@@ -184,10 +190,12 @@ debugging_opts!(
         AST_JSON_NOEXPAND,
         LS,
         SAVE_ANALYSIS,
+        UNSAFE_AUDIT,
         FLOWGRAPH_PRINT_LOANS,
         FLOWGRAPH_PRINT_MOVES,
         FLOWGRAPH_PRINT_ASSIGNS,
-        FLOWGRAPH_PRINT_ALL
+        FLOWGRAPH_PRINT_ALL,
+        COVERAGE
     ]
     0
 )
@@ -222,6 +230,8 @@ pub fn debugging_opts_map() -> Vec<(&'static str, &'static str, u64)> {
      ("ls", "List the symbols defined by a library crate", LS),
      ("save-analysis", "Write syntax and type analysis information \
                         in addition to normal output", SAVE_ANALYSIS),
+     ("unsafe-audit", "Write a JSON inventory of unsafe blocks and functions \
+                       and the unsafe operations inside them", UNSAFE_AUDIT),
      ("flowgraph-print-loans", "Include loan analysis data in \
                        --pretty flowgraph output", FLOWGRAPH_PRINT_LOANS),
      ("flowgraph-print-moves", "Include move analysis data in \
@@ -229,7 +239,87 @@ pub fn debugging_opts_map() -> Vec<(&'static str, &'static str, u64)> {
      ("flowgraph-print-assigns", "Include assignment analysis data in \
                        --pretty flowgraph output", FLOWGRAPH_PRINT_ASSIGNS),
      ("flowgraph-print-all", "Include all dataflow analysis data in \
-                       --pretty flowgraph output", FLOWGRAPH_PRINT_ALL))
+                       --pretty flowgraph output", FLOWGRAPH_PRINT_ALL),
+     ("coverage", "Instrument the crate to count how often each line runs, \
+                   in gcov format", COVERAGE))
+}
+
+/// The `-C debuginfo` sub-options, controlling how the debuginfo requested with
+/// `--debuginfo` is laid out in the output files.
+#[deriving(Clone)]
+pub struct DebugInfoOptions {
+    /// Move the bulk of the debuginfo into `.dwo` files next to the objects,
+    /// so the linker never has to copy it.
+    pub split: bool,
+    /// Compress the debug sections of the objects and the linked output with
+    /// zlib.
+    pub compress: bool,
+}
+
+impl DebugInfoOptions {
+    pub fn is_empty(&self) -> bool {
+        !self.split && !self.compress
+    }
+}
+
+/// The runtime checkers that `-Z sanitizer` can build into a crate. Each one
+/// has an LLVM instrumentation pass and a compiler-rt runtime library.
+#[deriving(Clone, PartialEq, Show)]
+pub enum Sanitizer {
+    /// Out-of-bounds accesses, use-after-free and double frees.
+    AddressSanitizer,
+    /// Data races between threads.
+    ThreadSanitizer,
+    /// Reads of uninitialized memory.
+    MemorySanitizer,
+}
+
+impl Sanitizer {
+    /// The name `-Z sanitizer` takes.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            AddressSanitizer => "address",
+            ThreadSanitizer => "thread",
+            MemorySanitizer => "memory",
+        }
+    }
+
+    /// The abbreviation compiler-rt uses, which also names the runtime
+    /// library, e.g. `libasan-rt.a`.
+    pub fn short_name(&self) -> &'static str {
+        match *self {
+            AddressSanitizer => "asan",
+            ThreadSanitizer => "tsan",
+            MemorySanitizer => "msan",
+        }
+    }
+}
+
+/// How generated code detects that a task has run out of stack.
+#[deriving(Clone, PartialEq)]
+pub enum StackCheck {
+    /// Rely on the guard page at the end of the stack, and probe each page of
+    /// frames that are large enough to jump over it.
+    StackProbes,
+    /// Compare the stack pointer against the task's limit in every function
+    /// prologue, calling `__morestack` when it is exceeded.
+    SplitStack,
+}
+
+/// The stack check used when `-C stack-check` isn't given. Guard pages only
+/// turn into a stack overflow report where the runtime has a handler for
+/// faults in them (see `rt::stack_overflow`), so everywhere else this stays
+/// with `__morestack`. On Windows LLVM always probes large frames, and
+/// split stacks aren't supported at all.
+pub fn default_stack_check(cfg: &Config) -> StackCheck {
+    match (cfg.os, cfg.arch) {
+        (abi::OsLinux, abi::X86) |
+        (abi::OsLinux, abi::X86_64) |
+        (abi::OsLinux, abi::Arm) |
+        (abi::OsMacos, _) |
+        (abi::OsWindows, _) => StackProbes,
+        _ => SplitStack,
+    }
 }
 
 #[deriving(Clone)]
@@ -273,7 +363,8 @@ macro_rules! cgoptions(
         &[ $( (stringify!($opt), cgsetters::$opt, $desc) ),* ];
 
     mod cgsetters {
-        use super::{CodegenOptions, Passes, SomePasses, AllPasses};
+        use super::{CodegenOptions, Passes, SomePasses, AllPasses, DebugInfoOptions};
+        use super::{StackCheck, StackProbes, SplitStack};
 
         $(
             pub fn $opt(cg: &mut CodegenOptions, v: Option<&str>) -> bool {
@@ -323,6 +414,31 @@ macro_rules! cgoptions(
             }
         }
 
+        fn parse_debuginfo_options(slot: &mut DebugInfoOptions, v: Option<&str>) -> bool {
+            match v {
+                Some(s) => {
+                    for option in s.split(',') {
+                        match option {
+                            "split" => slot.split = true,
+                            "compress" => slot.compress = true,
+                            _ => return false,
+                        }
+                    }
+                    true
+                }
+                None => false,
+            }
+        }
+
+        fn parse_stack_check(slot: &mut Option<StackCheck>, v: Option<&str>) -> bool {
+            match v {
+                Some("probe") => *slot = Some(StackProbes),
+                Some("morestack") => *slot = Some(SplitStack),
+                _ => return false,
+            }
+            true
+        }
+
         fn parse_passes(slot: &mut Passes, v: Option<&str>) -> bool {
             match v {
                 Some("all") => {
@@ -390,6 +506,19 @@ cgoptions!(
         "divide crate into N units to optimize in parallel"),
     remark: Passes = (SomePasses(Vec::new()), parse_passes,
         "print remarks for these optimization passes (space separated, or \"all\")"),
+    remark_report: bool = (false, parse_bool,
+        "write the remarks of `-C remark` (default all passes) to <crate>.remarks.json"),
+    profile_generate: bool = (false, parse_bool,
+        "instrument the crate to record an execution profile for `-C profile-use`"),
+    profile_use: Option<String> = (None, parse_opt_string,
+        "optimize the crate using a profile merged by `llvm-profdata`"),
+    debuginfo: DebugInfoOptions = (DebugInfoOptions { split: false, compress: false },
+        parse_debuginfo_options,
+        "how to emit debuginfo (comma separated): `split` into .dwo files, \
+         `compress` debug sections"),
+    stack_check: Option<StackCheck> = (None, parse_stack_check,
+        "how to detect stack overflow: `probe` (guard pages and stack probes, the \
+         default on Linux, OS X and Windows) or `morestack` (split-stack prologues)"),
 )
 
 pub fn build_codegen_options(matches: &getopts::Matches) -> CodegenOptions
@@ -616,6 +745,8 @@ pub fn optgroups() -> Vec<getopts::OptGroup> {
         optmulti("A", "allow", "Set lint allowed", "OPT"),
         optmulti("D", "deny", "Set lint denied", "OPT"),
         optmulti("F", "forbid", "Set lint forbidden", "OPT"),
+        optopt("", "lint-config", "Load lint levels from a configuration file, \
+                                   overridden by -A/-W/-D/-F", "FILE"),
         optmulti("C", "codegen", "Set a codegen option", "OPT[=VALUE]"),
         optmulti("Z", "", "Set internal debugging options", "FLAG"),
         optflagopt("v", "version", "Print version info and exit", "verbose"),
@@ -662,10 +793,24 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         }
     }
 
+    let lint_config = matches.opt_str("lint-config").map(|s| Path::new(s));
+
     let mut debugging_opts = 0;
+    let mut sanitizer = None;
     let debug_flags = matches.opt_strs("Z");
     let debug_map = debugging_opts_map();
     for debug_flag in debug_flags.iter() {
+        if debug_flag.as_slice().starts_with("sanitizer=") {
+            sanitizer = match debug_flag.as_slice().slice_from("sanitizer=".len()) {
+                "address" => Some(AddressSanitizer),
+                "thread" => Some(ThreadSanitizer),
+                "memory" => Some(MemorySanitizer),
+                s => early_error(format!("unknown sanitizer `{}`, expected one of \
+                                          `address`, `thread` or `memory`",
+                                         s).as_slice())
+            };
+            continue
+        }
         let mut this_bit = 0;
         for tuple in debug_map.iter() {
             let (name, bit) = match *tuple { (ref a, _, b) => (a, b) };
@@ -758,6 +903,13 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
     } else {
         NoDebugInfo
     };
+    // Coverage counters are mapped back to source lines through the line
+    // tables, so at least those have to be emitted.
+    let debuginfo = if debugging_opts & COVERAGE != 0 && debuginfo == NoDebugInfo {
+        LimitedDebugInfo
+    } else {
+        debuginfo
+    };
 
     let addl_lib_search_paths = matches.opt_strs("L").iter().map(|s| {
         Path::new(s.as_slice())
@@ -776,10 +928,25 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         early_warn("the --crate-file-name argument has been renamed to \
                     --print-file-name");
     }
-    let cg = build_codegen_options(matches);
+    let mut cg = build_codegen_options(matches);
+
+    if cg.remark_report && cg.remark.is_empty() {
+        cg.remark = AllPasses;
+    }
+    // LLVM finds the source of a remark through the line tables, so at least
+    // those have to be emitted.
+    let debuginfo = if !cg.remark.is_empty() && debuginfo == NoDebugInfo {
+        LimitedDebugInfo
+    } else {
+        debuginfo
+    };
+
+    if !cg.debuginfo.is_empty() && debuginfo == NoDebugInfo {
+        early_warn("-C debuginfo has no effect without --debuginfo");
+    }
 
-    if !cg.remark.is_empty() && debuginfo == NoDebugInfo {
-        early_warn("-C remark will not show source locations without --debuginfo");
+    if cg.profile_generate && cg.profile_use.is_some() {
+        early_error("-C profile-generate and -C profile-use both provided");
     }
 
     let color = match matches.opt_str("color").as_ref().map(|s| s.as_slice()) {
@@ -822,6 +989,7 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         optimize: opt_level,
         debuginfo: debuginfo,
         lint_opts: lint_opts,
+        lint_config: lint_config,
         describe_lints: describe_lints,
         output_types: output_types,
         addl_lib_search_paths: RefCell::new(addl_lib_search_paths),
@@ -833,6 +1001,7 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         no_trans: no_trans,
         no_analysis: no_analysis,
         debugging_opts: debugging_opts,
+        sanitizer: sanitizer,
         write_dependency_info: write_dependency_info,
         print_metas: print_metas,
         cg: cg,
@@ -95,8 +95,9 @@ pub fn compile_input(sess: Session,
         let type_arena = TypedArena::new();
         let analysis = phase_3_run_analysis_passes(sess, ast_map, &type_arena, id);
         phase_save_analysis(&analysis.ty_cx.sess, analysis.ty_cx.map.krate(), &analysis, outdir);
+        phase_unsafe_audit(&analysis.ty_cx.sess, analysis.ty_cx.map.krate(), &analysis, outdir);
         if stop_after_phase_3(&analysis.ty_cx.sess) { return; }
-        let (tcx, trans) = phase_4_translate_to_llvm(analysis);
+        let (tcx, trans) = phase_4_translate_to_llvm(analysis, &outputs);
 
         // Discard interned strings as they are no longer required.
         token::get_ident_interner().clear();
@@ -449,6 +450,9 @@ pub fn phase_3_run_analysis_passes<'tcx>(sess: Session,
     time(time_passes, "intrinsic checking", (), |_|
          middle::intrinsicck::check_crate(&ty_cx));
 
+    time(time_passes, "inline assembly checking", (), |_|
+         middle::check_asm::check_crate(&ty_cx));
+
     time(time_passes, "effect checking", (), |_|
          middle::effect::check_crate(&ty_cx));
 
@@ -505,6 +509,17 @@ pub fn phase_save_analysis(sess: &Session,
          middle::save::process_crate(sess, krate, analysis, odir));
 }
 
+pub fn phase_unsafe_audit(sess: &Session,
+                          krate: &ast::Crate,
+                          analysis: &CrateAnalysis,
+                          odir: &Option<Path>) {
+    if (sess.opts.debugging_opts & config::UNSAFE_AUDIT) == 0 {
+        return;
+    }
+    time(sess.time_passes(), "unsafe audit", krate, |krate|
+         middle::unsafe_audit::process_crate(sess, krate, analysis, odir));
+}
+
 pub struct ModuleTranslation {
     pub llcx: ContextRef,
     pub llmod: ModuleRef,
@@ -522,7 +537,8 @@ pub struct CrateTranslation {
 
 /// Run the translation phase to LLVM, after which the AST and analysis can
 /// be discarded.
-pub fn phase_4_translate_to_llvm<'tcx>(analysis: CrateAnalysis<'tcx>)
+pub fn phase_4_translate_to_llvm<'tcx>(analysis: CrateAnalysis<'tcx>,
+                                       outputs: &OutputFilenames)
                                        -> (ty::ctxt<'tcx>, CrateTranslation) {
     let time_passes = analysis.ty_cx.sess.time_passes();
 
@@ -531,7 +547,7 @@ pub fn phase_4_translate_to_llvm<'tcx>(analysis: CrateAnalysis<'tcx>)
 
     // Option dance to work around the lack of stack once closures.
     time(time_passes, "translation", analysis, |analysis|
-         trans::base::trans_crate(analysis))
+         trans::base::trans_crate(analysis, outputs))
 }
 
 /// Run LLVM itself, producing a bitcode file, assembly file or object file
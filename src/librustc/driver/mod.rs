@@ -290,6 +290,8 @@ fn describe_debug_flags() {
             }
         }
     }
+    println!("    -Z {:>20s} -- {}", "sanitizer=val",
+             "Instrument the crate with a sanitizer (address, thread or memory)");
 }
 
 fn describe_codegen_flags() {
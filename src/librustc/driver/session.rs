@@ -176,6 +176,18 @@ impl Session {
     pub fn show_span(&self) -> bool {
         self.debugging_opt(config::SHOW_SPAN)
     }
+    pub fn coverage(&self) -> bool {
+        self.debugging_opt(config::COVERAGE)
+    }
+    pub fn split_debuginfo(&self) -> bool {
+        self.opts.cg.debuginfo.split && self.opts.debuginfo != config::NoDebugInfo
+    }
+    pub fn compress_debuginfo(&self) -> bool {
+        self.opts.cg.debuginfo.compress && self.opts.debuginfo != config::NoDebugInfo
+    }
+    pub fn stack_check(&self) -> config::StackCheck {
+        self.opts.cg.stack_check.unwrap_or(config::default_stack_check(&self.targ_cfg))
+    }
     pub fn sysroot<'a>(&'a self) -> &'a Path {
         match self.opts.maybe_sysroot {
             Some (ref sysroot) => sysroot,
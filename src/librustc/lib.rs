@@ -71,6 +71,7 @@ pub mod back {
 
     pub mod link;
     pub mod lto;
+    pub mod remark;
     pub mod write;
 
 }
@@ -79,6 +80,7 @@ pub mod middle {
     pub mod astencode;
     pub mod borrowck;
     pub mod cfg;
+    pub mod check_asm;
     pub mod check_const;
     pub mod check_static_recursion;
     pub mod check_loop;
@@ -112,6 +114,7 @@ pub mod middle {
     pub mod ty;
     pub mod ty_fold;
     pub mod typeck;
+    pub mod unsafe_audit;
     pub mod weak_lang_items;
 }
 
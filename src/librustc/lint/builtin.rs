@@ -25,25 +25,27 @@
 //! Use the former for unit-like structs and the latter for structs with
 //! a `pub fn new()`.
 
+use driver::config;
 use metadata::csearch;
 use middle::def::*;
 use middle::typeck::astconv::ast_ty_to_ty;
 use middle::typeck::infer;
-use middle::{typeck, ty, def, pat_util, stability};
+use middle::{cfg, subst, typeck, ty, def, pat_util, stability};
 use util::ppaux::{ty_to_string};
-use util::nodemap::NodeSet;
-use lint::{Context, LintPass, LintArray};
+use util::nodemap::{NodeMap, NodeSet};
+use lint::{Context, LintPass, LintArray, Allow};
 
 use std::cmp;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::collections::hashmap::{Occupied, Vacant};
 use std::slice;
 use std::{i8, i16, i32, i64, u8, u16, u32, u64, f32, f64};
 use syntax::abi;
 use syntax::ast_map;
+use syntax::ast_util::PostExpansionMethod;
 use syntax::attr::AttrMetaMethods;
 use syntax::attr;
-use syntax::codemap::{Span, NO_EXPANSION};
+use syntax::codemap::{BytePos, Span, NO_EXPANSION};
 use syntax::parse::token;
 use syntax::{ast, ast_util, visit};
 use syntax::ptr::P;
@@ -587,6 +589,7 @@ impl LintPass for UnusedAttribute {
 
             // FIXME: #14407 these are only looked at on-demand so we can't
             // guarantee they'll have already been checked
+            "default_impl",
             "deprecated",
             "experimental",
             "frozen",
@@ -661,11 +664,85 @@ declare_lint!(pub UNUSED_MUST_USE, Warn,
 declare_lint!(pub UNUSED_RESULT, Allow,
               "unused result of an expression in a statement")
 
+declare_lint!(pub LET_UNDERSCORE_MUST_USE, Allow,
+              "result of a type flagged as #[must_use] dropped by `let _ = ...`")
+
 pub struct UnusedResult;
 
+impl UnusedResult {
+    /// Returns the explanation given by `#[must_use = "..."]`, or an empty
+    /// string for a bare `#[must_use]`, if the type `did` is marked.
+    fn must_use_explanation(cx: &Context, did: ast::DefId) -> Option<String> {
+        fn find(attrs: &[ast::Attribute]) -> Option<String> {
+            attrs.iter().find(|attr| attr.check_name("must_use")).map(|attr| {
+                attr.value_str().map_or(String::new(), |s| s.get().to_string())
+            })
+        }
+
+        if ast_util::is_local(did) {
+            match cx.tcx.map.get(did.node) {
+                ast_map::NodeItem(it) => find(it.attrs.as_slice()),
+                _ => None,
+            }
+        } else {
+            let mut explanation = None;
+            csearch::get_item_attrs(&cx.sess().cstore, did, |attrs| {
+                explanation = find(attrs.as_slice());
+            });
+            explanation
+        }
+    }
+
+    /// Looks for a type marked `#[must_use]` in `t`, also searching the
+    /// elements of tuples and fixed-length arrays and the contents of
+    /// `Box`, `Option` and `Vec`. Returns the message to report.
+    fn must_use_msg(cx: &Context, t: ty::t, wrapped: bool) -> Option<String> {
+        match ty::get(t).sty {
+            ty::ty_uniq(t) | ty::ty_vec(t, Some(_)) => {
+                UnusedResult::must_use_msg(cx, t, true)
+            }
+            ty::ty_tup(ref ts) => {
+                ts.iter().filter_map(|&t| UnusedResult::must_use_msg(cx, t, true)).next()
+            }
+            ty::ty_struct(did, ref substs) |
+            ty::ty_enum(did, ref substs) => {
+                match UnusedResult::must_use_explanation(cx, did) {
+                    Some(explanation) => {
+                        let mut msg = if wrapped {
+                            "unused result containing a value which must be used"
+                        } else {
+                            "unused result which must be used"
+                        }.to_string();
+                        // check for #[must_use="..."]
+                        if !explanation.is_empty() {
+                            msg.push_str(": ");
+                            msg.push_str(explanation.as_slice());
+                        }
+                        Some(msg)
+                    }
+                    None if item_path_ends_with(cx.tcx, did, "option::Option") ||
+                            item_path_ends_with(cx.tcx, did, "vec::Vec") => {
+                        substs.types.get_slice(subst::TypeSpace).iter()
+                              .filter_map(|&t| UnusedResult::must_use_msg(cx, t, true))
+                              .next()
+                    }
+                    None => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Checks whether the path of the item `did` ends with `suffix`. This is
+/// used to recognize library types without a lang item, such as `Option`.
+fn item_path_ends_with(tcx: &ty::ctxt, did: ast::DefId, suffix: &str) -> bool {
+    ty::item_path_str(tcx, did).as_slice().ends_with(suffix)
+}
+
 impl LintPass for UnusedResult {
     fn get_lints(&self) -> LintArray {
-        lint_array!(UNUSED_MUST_USE, UNUSED_RESULT)
+        lint_array!(UNUSED_MUST_USE, UNUSED_RESULT, LET_UNDERSCORE_MUST_USE)
     }
 
     fn check_stmt(&mut self, cx: &Context, s: &ast::Stmt) {
@@ -680,47 +757,67 @@ impl LintPass for UnusedResult {
         }
 
         let t = ty::expr_ty(cx.tcx, expr);
-        let mut warned = false;
         match ty::get(t).sty {
             ty::ty_nil | ty::ty_bot | ty::ty_bool => return,
-            ty::ty_struct(did, _) |
-            ty::ty_enum(did, _) => {
-                if ast_util::is_local(did) {
-                    match cx.tcx.map.get(did.node) {
-                        ast_map::NodeItem(it) => {
-                            warned |= check_must_use(cx, it.attrs.as_slice(), s.span);
+            _ => {}
+        }
+
+        // A closure whose result must be used, passed to an adaptor such as
+        // `map` whose own result is then thrown away, discards every value
+        // the closure produces. This is checked first because the adaptors
+        // are usually `#[must_use]` themselves, and the closure is the more
+        // useful thing to point at.
+        match expr.node {
+            ast::ExprMethodCall(_, _, ref args) => {
+                for arg in args.iter() {
+                    let body = match arg.node {
+                        ast::ExprFnBlock(_, _, ref body) |
+                        ast::ExprProc(_, ref body) |
+                        ast::ExprUnboxedFn(_, _, _, ref body) => body,
+                        _ => continue
+                    };
+                    let tail = match body.expr {
+                        Some(ref tail) => tail,
+                        None => continue
+                    };
+                    match UnusedResult::must_use_msg(cx, ty::expr_ty(cx.tcx, &**tail), false) {
+                        Some(msg) => {
+                            cx.span_lint(UNUSED_MUST_USE, tail.span,
+                                         format!("{}, but the closure's result \
+                                                  is discarded", msg).as_slice());
+                            return;
                         }
-                        _ => {}
+                        None => {}
                     }
-                } else {
-                    csearch::get_item_attrs(&cx.sess().cstore, did, |attrs| {
-                        warned |= check_must_use(cx, attrs.as_slice(), s.span);
-                    });
                 }
             }
             _ => {}
         }
-        if !warned {
-            cx.span_lint(UNUSED_RESULT, s.span, "unused result");
+
+        match UnusedResult::must_use_msg(cx, t, false) {
+            Some(msg) => {
+                cx.span_lint(UNUSED_MUST_USE, s.span, msg.as_slice());
+                return;
+            }
+            None => {}
         }
 
-        fn check_must_use(cx: &Context, attrs: &[ast::Attribute], sp: Span) -> bool {
-            for attr in attrs.iter() {
-                if attr.check_name("must_use") {
-                    let mut msg = "unused result which must be used".to_string();
-                    // check for #[must_use="..."]
-                    match attr.value_str() {
-                        None => {}
-                        Some(s) => {
-                            msg.push_str(": ");
-                            msg.push_str(s.get());
-                        }
-                    }
-                    cx.span_lint(UNUSED_MUST_USE, sp, msg.as_slice());
-                    return true;
+        cx.span_lint(UNUSED_RESULT, s.span, "unused result");
+    }
+
+    fn check_local(&mut self, cx: &Context, l: &ast::Local) {
+        // `let _ = expr;` drops the value immediately, just like `expr;`,
+        // but is also the usual way of ignoring a result on purpose, so it
+        // has its own lint.
+        match (&l.pat.node, &l.init) {
+            (&ast::PatWild(ast::PatWildSingle), &Some(ref init)) => {
+                let t = ty::expr_ty(cx.tcx, &**init);
+                match UnusedResult::must_use_msg(cx, t, false) {
+                    Some(msg) => cx.span_lint(LET_UNDERSCORE_MUST_USE, l.span, msg.as_slice()),
+                    None => {}
                 }
             }
-            false
+            _ => {}
         }
     }
 }
@@ -1080,7 +1177,8 @@ impl LintPass for UnnecessaryParens {
             ast::ExprWhile(ref cond, _, _) => (cond, "`while` condition", true),
             ast::ExprMatch(ref head, _, source) => match source {
                 ast::MatchNormal => (head, "`match` head expression", true),
-                ast::MatchIfLetDesugar => (head, "`if let` head expression", true)
+                ast::MatchIfLetDesugar => (head, "`if let` head expression", true),
+                ast::MatchWhileLetDesugar => (head, "`while let` head expression", true),
             },
             ast::ExprRet(Some(ref value)) => (value, "`return` value", false),
             ast::ExprAssign(_, ref value) => (value, "assigned value", false),
@@ -1450,6 +1548,413 @@ impl LintPass for MissingDoc {
     }
 }
 
+declare_lint!(CYCLOMATIC_COMPLEXITY, Allow,
+              "detects functions whose control flow has too many branches")
+
+declare_lint!(NESTING_DEPTH, Allow,
+              "detects functions with deeply nested control flow")
+
+declare_lint!(FUNCTION_LENGTH, Allow,
+              "detects functions spanning too many source lines")
+
+/// Limits applied by the `FunctionComplexity` lint pass.
+#[deriving(Clone)]
+struct ComplexityThresholds {
+    /// Maximum cyclomatic complexity, i.e. one plus the number of
+    /// additional paths introduced by branches in the CFG.
+    cyclomatic: uint,
+
+    /// Maximum nesting of `if`, `match` and loop expressions.
+    nesting: uint,
+
+    /// Maximum number of source lines, including the signature.
+    lines: uint,
+}
+
+pub struct FunctionComplexity {
+    /// Stack of thresholds in effect at each level which has lint
+    /// attributes. They are overridden by `#[complexity_threshold = "N"]`,
+    /// `#[nesting_threshold = "N"]` and `#[fn_length_threshold = "N"]`.
+    threshold_stack: Vec<ComplexityThresholds>,
+}
+
+impl FunctionComplexity {
+    pub fn new() -> FunctionComplexity {
+        FunctionComplexity {
+            threshold_stack: vec!(ComplexityThresholds {
+                cyclomatic: 25,
+                nesting: 5,
+                lines: 150,
+            }),
+        }
+    }
+
+    fn thresholds(&self) -> &ComplexityThresholds {
+        self.threshold_stack.last().expect("empty threshold_stack")
+    }
+
+    fn check_complexity(&self, cx: &Context, body: &ast::Block, span: Span) {
+        let thresholds = self.thresholds();
+
+        if cx.current_level(CYCLOMATIC_COMPLEXITY) != Allow {
+            let cfg = cfg::CFG::new(cx.tcx, body);
+
+            // Every node with more than one successor adds a path through
+            // the function. This is `E - N + 2` for a connected graph, but
+            // also copes with the unreachable nodes left behind by `return`
+            // and `break`.
+            let mut out_degree = Vec::from_elem(cfg.graph.all_nodes().len(), 0u);
+            for edge in cfg.graph.all_edges().iter() {
+                *out_degree.get_mut(edge.source().node_id()) += 1;
+            }
+            let complexity = out_degree.iter().fold(1u, |acc, &n| {
+                if n > 1 { acc + n - 1 } else { acc }
+            });
+
+            if complexity > thresholds.cyclomatic {
+                cx.span_lint(CYCLOMATIC_COMPLEXITY, span,
+                             format!("function has a cyclomatic complexity of {}, \
+                                      the threshold is {}",
+                                     complexity, thresholds.cyclomatic).as_slice());
+            }
+        }
+
+        if cx.current_level(NESTING_DEPTH) != Allow {
+            let mut visitor = NestingDepthVisitor { depth: 0, max_depth: 0 };
+            visitor.visit_block(body);
+
+            if visitor.max_depth > thresholds.nesting {
+                cx.span_lint(NESTING_DEPTH, span,
+                             format!("function nests control flow {} levels deep, \
+                                      the threshold is {}",
+                                     visitor.max_depth, thresholds.nesting).as_slice());
+            }
+        }
+
+        if cx.current_level(FUNCTION_LENGTH) != Allow {
+            let codemap = cx.sess().codemap();
+            let lo = codemap.lookup_char_pos(span.lo).line;
+            let hi = codemap.lookup_char_pos(span.hi).line;
+            let lines = hi - lo + 1;
+
+            if lines > thresholds.lines {
+                cx.span_lint(FUNCTION_LENGTH, span,
+                             format!("function spans {} lines, the threshold is {}",
+                                     lines, thresholds.lines).as_slice());
+            }
+        }
+    }
+}
+
+/// Computes the deepest nesting of branching expressions in a function
+/// body, without descending into closures or nested items.
+struct NestingDepthVisitor {
+    depth: uint,
+    max_depth: uint,
+}
+
+impl<'v> Visitor<'v> for NestingDepthVisitor {
+    fn visit_expr(&mut self, e: &ast::Expr) {
+        match e.node {
+            ast::ExprFnBlock(..) | ast::ExprProc(..) | ast::ExprUnboxedFn(..) => {}
+            ast::ExprIf(..) | ast::ExprWhile(..) | ast::ExprForLoop(..) |
+            ast::ExprLoop(..) | ast::ExprMatch(..) => {
+                self.depth += 1;
+                self.max_depth = cmp::max(self.max_depth, self.depth);
+                visit::walk_expr(self, e);
+                self.depth -= 1;
+            }
+            _ => visit::walk_expr(self, e)
+        }
+    }
+
+    fn visit_item(&mut self, _: &ast::Item) {}
+}
+
+impl LintPass for FunctionComplexity {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(CYCLOMATIC_COMPLEXITY, NESTING_DEPTH, FUNCTION_LENGTH)
+    }
+
+    fn enter_lint_attrs(&mut self, cx: &Context, attrs: &[ast::Attribute]) {
+        let mut thresholds = self.thresholds().clone();
+        for attr in attrs.iter() {
+            let slot = if attr.check_name("complexity_threshold") {
+                &mut thresholds.cyclomatic
+            } else if attr.check_name("nesting_threshold") {
+                &mut thresholds.nesting
+            } else if attr.check_name("fn_length_threshold") {
+                &mut thresholds.lines
+            } else {
+                continue
+            };
+            attr::mark_used(attr);
+
+            match attr.value_str().and_then(|s| from_str::<uint>(s.get())) {
+                Some(n) => *slot = n,
+                None => {
+                    cx.sess().span_err(attr.span,
+                                       format!("malformed `{}` attribute, expected \
+                                                `#[{} = \"N\"]`",
+                                               attr.name(), attr.name()).as_slice());
+                }
+            }
+        }
+        self.threshold_stack.push(thresholds);
+    }
+
+    fn exit_lint_attrs(&mut self, _: &Context, _: &[ast::Attribute]) {
+        self.threshold_stack.pop().expect("empty threshold_stack");
+    }
+
+    fn check_fn(&mut self, cx: &Context,
+                fk: visit::FnKind, _: &ast::FnDecl,
+                body: &ast::Block, span: Span, _: ast::NodeId) {
+        match fk {
+            // Closures are not checked on their own, and their bodies don't
+            // count toward the enclosing function either: the CFG has a
+            // single node for a closure expression, and `NestingDepthVisitor`
+            // doesn't descend into them.
+            visit::FkItemFn(..) | visit::FkMethod(..) => {
+                self.check_complexity(cx, body, span);
+            }
+            visit::FkFnBlock => {}
+        }
+    }
+}
+
+declare_lint!(REACHABLE_FAILURE, Allow,
+              "detects `unwrap()`, `fail!` and indexing reachable from a library's public API")
+
+/// An operation which may fail the task, found by `ReachableFailure`.
+struct FailureSite {
+    span: Span,
+    desc: String,
+    /// Functions on the call chain, starting at the public entry point.
+    chain: Vec<ast::NodeId>,
+}
+
+/// Starting from the items exported by a library crate, follows calls to
+/// local functions and records every operation which may fail the task,
+/// together with the call chain that reaches it.
+///
+/// Like `middle::reachable`, calls through trait objects and type
+/// parameters are not followed, since their targets are not known here.
+pub struct ReachableFailure {
+    /// Failure sites, indexed by the expression they are reported on.
+    sites: NodeMap<FailureSite>,
+}
+
+impl ReachableFailure {
+    pub fn new() -> ReachableFailure {
+        ReachableFailure {
+            sites: NodeMap::new(),
+        }
+    }
+}
+
+/// Scans the body of a single function for failure sites and for calls
+/// to other local functions.
+struct FailureVisitor<'a, 'tcx: 'a> {
+    tcx: &'a ty::ctxt<'tcx>,
+    callees: Vec<ast::NodeId>,
+    sites: Vec<(ast::NodeId, Span, String)>,
+    /// Call sites of the `fail!` invocations already recorded, since every
+    /// expression in the expansion points back to the same invocation.
+    fail_call_sites: HashSet<BytePos>,
+}
+
+impl<'a, 'tcx> FailureVisitor<'a, 'tcx> {
+    fn add_callee(&mut self, def_id: ast::DefId) {
+        if ast_util::is_local(def_id) {
+            self.callees.push(def_id.node);
+        }
+    }
+
+    /// If `def_id` is `unwrap` on `Option` or `Result`, returns the
+    /// name to report it under.
+    fn unwrap_method_name(&self, def_id: ast::DefId) -> Option<String> {
+        let name = ty::impl_or_trait_item(self.tcx, def_id).ident();
+        if token::get_ident(name).get() != "unwrap" {
+            return None;
+        }
+        let impl_did = match ty::impl_of_method(self.tcx, def_id) {
+            Some(did) => did,
+            None => return None,
+        };
+        match ty::get(ty::lookup_item_type(self.tcx, impl_did).ty).sty {
+            ty::ty_enum(did, _) => {
+                if item_path_ends_with(self.tcx, did, "option::Option") {
+                    Some("Option::unwrap".to_string())
+                } else if item_path_ends_with(self.tcx, did, "result::Result") {
+                    Some("Result::unwrap".to_string())
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the outermost `fail!` invocation that `span` was expanded
+    /// from, if any.
+    fn fail_call_site(&self, span: Span) -> Option<Span> {
+        let mut found = None;
+        let mut expn_id = span.expn_id;
+        loop {
+            let call_site = self.tcx.sess.codemap().with_expn_info(expn_id, |info| {
+                info.map(|info| (info.callee.name.clone(), info.call_site))
+            });
+            match call_site {
+                Some((name, call_site)) => {
+                    if name.as_slice() == "fail" {
+                        found = Some(call_site);
+                    }
+                    expn_id = call_site.expn_id;
+                }
+                None => return found,
+            }
+        }
+    }
+}
+
+impl<'a, 'tcx, 'v> Visitor<'v> for FailureVisitor<'a, 'tcx> {
+    fn visit_expr(&mut self, e: &ast::Expr) {
+        match self.fail_call_site(e.span) {
+            Some(call_site) => {
+                if self.fail_call_sites.insert(call_site.lo) {
+                    self.sites.push((e.id, call_site, "`fail!`".to_string()));
+                }
+                // Only the macro arguments can contain further failure sites.
+                visit::walk_expr(self, e);
+                return;
+            }
+            None => {}
+        }
+
+        match e.node {
+            ast::ExprPath(..) => {
+                match self.tcx.def_map.borrow().find(&e.id) {
+                    Some(&def::DefFn(did, _, false)) |
+                    Some(&def::DefStaticMethod(did, _, _)) => self.add_callee(did),
+                    _ => {}
+                }
+            }
+            ast::ExprMethodCall(..) => {
+                let method_call = typeck::MethodCall::expr(e.id);
+                let origin = self.tcx.method_map.borrow().find(&method_call)
+                                                          .map(|m| m.origin.clone());
+                match origin {
+                    Some(typeck::MethodStatic(did)) => {
+                        match self.unwrap_method_name(did) {
+                            Some(name) => {
+                                let desc = format!("call to `{}`", name);
+                                self.sites.push((e.id, e.span, desc));
+                            }
+                            None => self.add_callee(did),
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            ast::ExprIndex(..) => {
+                self.sites.push((e.id, e.span, "indexing".to_string()));
+            }
+            _ => {}
+        }
+
+        visit::walk_expr(self, e);
+    }
+
+    fn visit_item(&mut self, _: &ast::Item) {
+        // Nested items are only scanned if they are called.
+    }
+}
+
+impl LintPass for ReachableFailure {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(REACHABLE_FAILURE)
+    }
+
+    fn check_crate(&mut self, cx: &Context, _: &ast::Crate) {
+        // Executables have no public API to protect.
+        let any_library = cx.sess().crate_types.borrow().iter().any(|ty| {
+            *ty != config::CrateTypeExecutable
+        });
+        if !any_library { return }
+
+        // Breadth-first, so that the shortest call chain is reported.
+        let mut worklist: Vec<ast::NodeId> = cx.exported_items.iter().map(|&id| id).collect();
+        let mut callers = NodeMap::new();
+        let mut scanned = NodeSet::new();
+        let mut next = 0;
+        while next < worklist.len() {
+            let fn_id = worklist[next];
+            next += 1;
+            if !scanned.insert(fn_id) { continue }
+
+            let body = match cx.tcx.map.find(fn_id) {
+                Some(ast_map::NodeItem(item)) => match item.node {
+                    ast::ItemFn(_, _, _, _, ref body) => &**body,
+                    _ => continue,
+                },
+                Some(ast_map::NodeImplItem(&ast::MethodImplItem(ref m))) => m.pe_body(),
+                Some(ast_map::NodeTraitItem(&ast::ProvidedMethod(ref m))) => m.pe_body(),
+                _ => continue,
+            };
+
+            let mut visitor = FailureVisitor {
+                tcx: cx.tcx,
+                callees: vec!(),
+                sites: vec!(),
+                fail_call_sites: HashSet::new(),
+            };
+            visitor.visit_block(body);
+
+            let mut chain = vec!(fn_id);
+            let mut caller = fn_id;
+            loop {
+                match callers.find(&caller) {
+                    Some(&id) => {
+                        chain.push(id);
+                        caller = id;
+                    }
+                    None => break,
+                }
+            }
+            chain.reverse();
+
+            for (id, span, desc) in visitor.sites.into_iter() {
+                self.sites.insert(id, FailureSite {
+                    span: span,
+                    desc: desc,
+                    chain: chain.clone(),
+                });
+            }
+            for callee in visitor.callees.into_iter() {
+                if !scanned.contains(&callee) && !callers.contains_key(&callee) {
+                    callers.insert(callee, fn_id);
+                    worklist.push(callee);
+                }
+            }
+        }
+    }
+
+    fn check_expr(&mut self, cx: &Context, e: &ast::Expr) {
+        let site = match self.sites.find(&e.id) {
+            Some(site) => site,
+            None => return,
+        };
+        let chain: Vec<String> = site.chain.iter().map(|&id| {
+            ty::item_path_str(cx.tcx, ast_util::local_def(id))
+        }).collect();
+        cx.span_lint(REACHABLE_FAILURE, site.span,
+                     format!("{} may fail the task, reachable from the public API \
+                              through `{}`",
+                             site.desc, chain.connect("` -> `")).as_slice());
+    }
+}
+
 declare_lint!(DEPRECATED, Warn,
               "detects use of #[deprecated] items")
 
@@ -0,0 +1,143 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Lint configuration files, as loaded by `--lint-config`.
+//!
+//! A configuration file sets lint levels in the same way as the `-A`, `-W`,
+//! `-D` and `-F` flags, so that a single policy can be shared between many
+//! crates. Each line holds a level followed by a comma-separated list of
+//! lint or lint group names. A line of the form `[path]` starts a section
+//! whose levels only apply to source files under `path`:
+//!
+//! ```text
+//! # Shared lint policy
+//! deny bad_style
+//! warn missing_doc, unused_result
+//!
+//! [tests/]
+//! allow missing_doc
+//! ```
+//!
+//! Everything after a `#` is a comment. Names may use dashes or
+//! underscores, as on the command line.
+
+use lint::Level;
+
+use std::tuple::Tuple2;
+
+/// The levels set by a lint configuration file, before the lint names
+/// have been checked against the `LintStore`.
+pub struct LintConfig {
+    /// Levels which apply to the whole crate.
+    pub levels: Vec<(String, Level)>,
+
+    /// Levels which only apply to files under a path, in the order the
+    /// sections appear in the file.
+    pub path_levels: Vec<(String, Vec<(String, Level)>)>,
+}
+
+/// Parse the contents of a lint configuration file. Errors are reported
+/// with the line number they occur on.
+pub fn parse(src: &str) -> Result<LintConfig, String> {
+    let mut config = LintConfig {
+        levels: vec!(),
+        path_levels: vec!(),
+    };
+
+    for (i, line) in src.lines().enumerate() {
+        let line = match line.find('#') {
+            Some(pos) => line.slice_to(pos),
+            None => line,
+        }.trim();
+        if line.is_empty() { continue }
+
+        if line.starts_with("[") {
+            if !line.ends_with("]") || line.len() < 3 {
+                return Err(format!("line {}: malformed section header `{}`",
+                                   i + 1, line));
+            }
+            let path = line.slice(1, line.len() - 1).trim().to_string();
+            config.path_levels.push((path, vec!()));
+            continue;
+        }
+
+        let (level, names) = match line.find(|c: char| c.is_whitespace()) {
+            Some(pos) => (line.slice_to(pos), line.slice_from(pos)),
+            None => (line, ""),
+        };
+        let level = match Level::from_str(level) {
+            Some(level) => level,
+            None => {
+                return Err(format!("line {}: unknown lint level `{}`, expected \
+                                    one of `allow`, `warn`, `deny` or `forbid`",
+                                   i + 1, level));
+            }
+        };
+
+        let levels = match config.path_levels.last_mut() {
+            Some(section) => section.mut1(),
+            None => &mut config.levels,
+        };
+        let mut any = false;
+        for name in names.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()) {
+            levels.push((name.replace("-", "_"), level));
+            any = true;
+        }
+        if !any {
+            return Err(format!("line {}: expected lint names after `{}`",
+                               i + 1, level.as_str()));
+        }
+    }
+
+    Ok(config)
+}
+
+/// Whether the levels of a `[path]` section apply to `file`. The path
+/// matches files below it, whether it is given relative to the directory
+/// rustc runs in or to some directory in between.
+pub fn path_matches(file: &str, path: &str) -> bool {
+    file.starts_with(path) || file.contains(format!("/{}", path).as_slice())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse, path_matches};
+    use lint::{Allow, Warn, Deny};
+
+    #[test]
+    fn test_parse() {
+        let config = parse("# policy\n\
+                            deny bad_style\n\
+                            warn missing-doc, unused_result # trailing\n\
+                            \n\
+                            [tests/]\n\
+                            allow missing_doc\n").unwrap();
+        assert!(config.levels == vec!(("bad_style".to_string(), Deny),
+                                      ("missing_doc".to_string(), Warn),
+                                      ("unused_result".to_string(), Warn)));
+        assert!(config.path_levels ==
+                vec!(("tests/".to_string(), vec!(("missing_doc".to_string(), Allow)))));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("deny").is_err());
+        assert!(parse("ignore missing_doc").is_err());
+        assert!(parse("[tests/\nallow missing_doc").is_err());
+    }
+
+    #[test]
+    fn test_path_matches() {
+        assert!(path_matches("tests/foo.rs", "tests/"));
+        assert!(path_matches("src/tests/foo.rs", "tests/"));
+        assert!(!path_matches("src/lib.rs", "tests/"));
+        assert!(!path_matches("src/mytests/foo.rs", "tests/"));
+    }
+}
@@ -32,10 +32,11 @@ use middle::typeck::infer;
 use driver::session::Session;
 use driver::early_error;
 use lint::{Level, LevelSource, Lint, LintId, LintArray, LintPass, LintPassObject};
-use lint::{Default, CommandLine, Node, Allow, Warn, Deny, Forbid};
-use lint::builtin;
+use lint::{Default, CommandLine, ConfigFile, Node, Allow, Warn, Deny, Forbid};
+use lint::{builtin, config};
 
 use std::collections::HashMap;
+use std::io::File;
 use std::rc::Rc;
 use std::cell::RefCell;
 use std::tuple::Tuple2;
@@ -71,6 +72,10 @@ pub struct LintStore {
     /// Map of registered lint groups to what lints they expand to. The bool
     /// is true if the lint group was added by a plugin.
     lint_groups: HashMap<&'static str, (Vec<LintId>, bool)>,
+
+    /// Levels from the `[path]` sections of the lint configuration file,
+    /// applied to the items of source files under each path.
+    path_levels: Vec<(String, Vec<(LintId, Level)>)>,
 }
 
 impl LintStore {
@@ -96,6 +101,7 @@ impl LintStore {
             by_name: HashMap::new(),
             levels: HashMap::new(),
             lint_groups: HashMap::new(),
+            path_levels: vec!(),
         }
     }
 
@@ -196,6 +202,8 @@ impl LintStore {
                               TypeLimits,
                               RawPointerDeriving,
                               MissingDoc,
+                              FunctionComplexity,
+                              ReachableFailure,
         )
 
         add_lint_group!(sess, "bad_style",
@@ -210,24 +218,73 @@ impl LintStore {
         self.register_pass(sess, false, box GatherNodeLevels as LintPassObject);
     }
 
+    /// Look up a lint or a lint group by name, returning the lints it
+    /// stands for.
+    fn find_lints(&self, name: &str) -> Option<Vec<LintId>> {
+        match self.by_name.find_equiv(&name) {
+            Some(&lint_id) => Some(vec!(lint_id)),
+            None => self.lint_groups.find_equiv(&name).map(|&(ref v, _)| v.clone()),
+        }
+    }
+
+    fn process_lint_config(&mut self, sess: &Session, path: &Path) {
+        let src = match File::open(path).read_to_string() {
+            Ok(src) => src,
+            Err(e) => {
+                sess.fatal(format!("couldn't read lint configuration `{}`: {}",
+                                   path.display(), e).as_slice())
+            }
+        };
+        let config = match config::parse(src.as_slice()) {
+            Ok(config) => config,
+            Err(msg) => {
+                sess.err(format!("{}: {}", path.display(), msg).as_slice());
+                return;
+            }
+        };
+
+        for &(ref lint_name, level) in config.levels.iter() {
+            match self.find_lints(lint_name.as_slice()) {
+                Some(v) => {
+                    for &lint_id in v.iter() {
+                        self.set_level(lint_id, (level, ConfigFile));
+                    }
+                }
+                None => sess.err(format!("{}: unknown lint `{}`",
+                                         path.display(), lint_name).as_slice()),
+            }
+        }
+
+        for &(ref prefix, ref levels) in config.path_levels.iter() {
+            let mut resolved = vec!();
+            for &(ref lint_name, level) in levels.iter() {
+                match self.find_lints(lint_name.as_slice()) {
+                    Some(v) => resolved.extend(v.into_iter().map(|lint_id| (lint_id, level))),
+                    None => sess.err(format!("{}: unknown lint `{}`",
+                                             path.display(), lint_name).as_slice()),
+                }
+            }
+            self.path_levels.push((prefix.clone(), resolved));
+        }
+    }
+
     pub fn process_command_line(&mut self, sess: &Session) {
+        // The configuration file is processed first, so that command-line
+        // flags can override it.
+        match sess.opts.lint_config {
+            Some(ref path) => self.process_lint_config(sess, path),
+            None => {}
+        }
+
         for &(ref lint_name, level) in sess.opts.lint_opts.iter() {
-            match self.by_name.find_equiv(&lint_name.as_slice()) {
-                Some(&lint_id) => self.set_level(lint_id, (level, CommandLine)),
-                None => {
-                    match self.lint_groups.iter().map(|(&x, pair)| (x, pair.ref0().clone()))
-                                                 .collect::<HashMap<&'static str, Vec<LintId>>>()
-                                                 .find_equiv(&lint_name.as_slice()) {
-                        Some(v) => {
-                            v.iter()
-                             .map(|lint_id: &LintId|
-                                     self.set_level(*lint_id, (level, CommandLine)))
-                             .collect::<Vec<()>>();
-                        }
-                        None => sess.err(format!("unknown {} flag: {}",
-                                                 level.as_str(), lint_name).as_slice()),
+            match self.find_lints(lint_name.as_slice()) {
+                Some(v) => {
+                    for &lint_id in v.iter() {
+                        self.set_level(lint_id, (level, CommandLine));
                     }
                 }
+                None => sess.err(format!("unknown {} flag: {}",
+                                         level.as_str(), lint_name).as_slice()),
             }
         }
     }
@@ -255,6 +312,10 @@ pub struct Context<'a, 'tcx: 'a> {
     /// Level of lints for certain NodeIds, stored here because the body of
     /// the lint needs to run in trans.
     node_levels: RefCell<HashMap<(ast::NodeId, LintId), LevelSource>>,
+
+    /// The source file of the item being checked, used to decide when the
+    /// `[path]` sections of the lint configuration apply.
+    cur_file: Option<String>,
 }
 
 /// Convenience macro for calling a `LintPass` method on every pass in the context.
@@ -326,6 +387,13 @@ pub fn raw_emit_lint(sess: &Session, lint: &'static Lint,
                         Allow => fail!()
                     }, name.replace("_", "-"))
         },
+        ConfigFile => {
+            let file = sess.opts.lint_config.as_ref().map_or(String::new(), |p| {
+                p.display().to_string()
+            });
+            format!("{}, #[{}({})] set in {}", msg,
+                    level.as_str(), name, file)
+        },
         Node(src) => {
             note = Some(src);
             msg.to_string()
@@ -363,6 +431,7 @@ impl<'a, 'tcx> Context<'a, 'tcx> {
             lints: lint_store,
             level_stack: vec![],
             node_levels: RefCell::new(HashMap::new()),
+            cur_file: None,
         }
     }
 
@@ -469,6 +538,56 @@ impl<'a, 'tcx> Context<'a, 'tcx> {
         }
     }
 
+    /**
+     * Apply the levels from the lint configuration for the source file
+     * containing `span`, if it differs from the file of the enclosing item,
+     * call the provided function, then reset the lints in effect to their
+     * previous state. The configuration only fills in for lints that haven't
+     * been set otherwise: levels from the command line and from attributes,
+     * including those of enclosing items and the crate root, take precedence.
+     */
+    fn with_path_levels(&mut self, span: Span, f: |&mut Context|) {
+        if self.lints.path_levels.is_empty() {
+            return f(self);
+        }
+        let file = self.tcx.sess.codemap().span_to_filename(span);
+        if self.cur_file.as_ref() == Some(&file) {
+            return f(self);
+        }
+
+        let mut changes = vec!();
+        for &(ref prefix, ref levels) in self.lints.path_levels.iter() {
+            if config::path_matches(file.as_slice(), prefix.as_slice()) {
+                changes.push_all(levels.as_slice());
+            }
+        }
+
+        let mut pushed = 0u;
+        for &(lint_id, level) in changes.iter() {
+            let (now, src) = self.lints.get_level_source(lint_id);
+            match src {
+                CommandLine | Node(..) => continue,
+                Default | ConfigFile => {}
+            }
+            // Like a command-line forbid, a forbid from the top of the
+            // configuration can't be overruled by one of its sections.
+            if now == level || (now == Forbid && level != Forbid) { continue }
+            self.level_stack.push((lint_id, (now, src)));
+            pushed += 1;
+            self.lints.set_level(lint_id, (level, ConfigFile));
+        }
+
+        let prev_file = mem::replace(&mut self.cur_file, Some(file));
+        f(self);
+        self.cur_file = prev_file;
+
+        // rollback
+        for _ in range(0, pushed) {
+            let (lint, lvlsrc) = self.level_stack.pop().unwrap();
+            self.lints.set_level(lint, lvlsrc);
+        }
+    }
+
     fn visit_ids(&self, f: |&mut ast_util::IdVisitor<Context>|) {
         let mut v = ast_util::IdVisitor {
             operation: self,
@@ -517,10 +636,17 @@ impl<'a, 'tcx> AstConv<'tcx> for Context<'a, 'tcx>{
 
 impl<'a, 'tcx, 'v> Visitor<'v> for Context<'a, 'tcx> {
     fn visit_item(&mut self, it: &ast::Item) {
-        self.with_lint_attrs(it.attrs.as_slice(), |cx| {
-            run_lints!(cx, check_item, it);
-            cx.visit_ids(|v| v.visit_item(it));
-            visit::walk_item(cx, it);
+        // The contents of an out-of-line module live in another file.
+        let span = match it.node {
+            ast::ItemMod(ref m) => m.inner,
+            _ => it.span,
+        };
+        self.with_path_levels(span, |cx| {
+            cx.with_lint_attrs(it.attrs.as_slice(), |cx| {
+                run_lints!(cx, check_item, it);
+                cx.visit_ids(|v| v.visit_item(it));
+                visit::walk_item(cx, it);
+            })
         })
     }
 
@@ -737,18 +863,20 @@ pub fn check_crate(tcx: &ty::ctxt,
     let mut cx = Context::new(tcx, krate, exported_items);
 
     // Visit the whole crate.
-    cx.with_lint_attrs(krate.attrs.as_slice(), |cx| {
-        cx.visit_id(ast::CRATE_NODE_ID);
-        cx.visit_ids(|v| {
-            v.visited_outermost = true;
-            visit::walk_crate(v, krate);
-        });
-
-        // since the root module isn't visited as an item (because it isn't an
-        // item), warn for it here.
-        run_lints!(cx, check_crate, krate);
-
-        visit::walk_crate(cx, krate);
+    cx.with_path_levels(krate.span, |cx| {
+        cx.with_lint_attrs(krate.attrs.as_slice(), |cx| {
+            cx.visit_id(ast::CRATE_NODE_ID);
+            cx.visit_ids(|v| {
+                v.visited_outermost = true;
+                visit::walk_crate(v, krate);
+            });
+
+            // since the root module isn't visited as an item (because it isn't an
+            // item), warn for it here.
+            run_lints!(cx, check_crate, krate);
+
+            visit::walk_crate(cx, krate);
+        })
     });
 
     // If we missed any lints added to the session, then there's a bug somewhere
@@ -225,6 +225,9 @@ impl<'a, 'tcx> CFGBuilder<'a, 'tcx> {
             ast::ExprIfLet(..) => {
                 self.tcx.sess.span_bug(expr.span, "non-desugared ExprIfLet");
             }
+            ast::ExprWhileLet(..) => {
+                self.tcx.sess.span_bug(expr.span, "non-desugared ExprWhileLet");
+            }
 
             ast::ExprWhile(ref cond, ref body, _) => {
                 //
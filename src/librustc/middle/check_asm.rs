@@ -0,0 +1,393 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Checks the operands of inline assembly against the register classes of
+//! the target. `trans::asm` hands the constraint strings to LLVM as they
+//! are, and LLVM tends to assert or silently miscompile when they are
+//! malformed, name registers the target does not have, or are given
+//! operands that do not fit.
+
+use middle::ty;
+use util::ppaux::ty_to_string;
+
+use syntax::abi;
+use syntax::ast;
+use syntax::codemap::Span;
+use syntax::visit::Visitor;
+use syntax::visit;
+
+/// Where an operand may be placed by one alternative of a constraint.
+#[deriving(PartialEq)]
+enum Location {
+    /// In a register holding at most the given number of bits.
+    Register(uint),
+    /// In an immediate encoded in the instruction.
+    Immediate,
+    /// In memory, or wherever LLVM sees fit.
+    Anywhere,
+}
+
+/// How many bits an operand of a particular type takes up.
+enum OperandSize {
+    Bits(uint),
+    /// Structs, enums, fat pointers and the like, which do not fit in a
+    /// single register whatever its width.
+    Aggregate,
+    /// The size depends on type parameters, so it is checked by LLVM
+    /// after monomorphization.
+    Unknown,
+}
+
+struct AsmCheckingVisitor<'a, 'tcx: 'a> {
+    tcx: &'a ty::ctxt<'tcx>,
+    arch: abi::Architecture,
+    word_bits: uint,
+}
+
+impl<'a, 'tcx> AsmCheckingVisitor<'a, 'tcx> {
+    fn arch_name(&self) -> &'static str {
+        match self.arch {
+            abi::X86 => "x86",
+            abi::X86_64 => "x86_64",
+            abi::Arm => "arm",
+            abi::Mips => "mips",
+            abi::Mipsel => "mipsel",
+        }
+    }
+
+    fn check_inline_asm(&self, expr: &ast::Expr, ia: &ast::InlineAsm) {
+        let mut output_sizes = Vec::new();
+        for &(ref constraint, ref out, _) in ia.outputs.iter() {
+            let out_ty = ty::expr_ty(self.tcx, &**out);
+            let size = self.operand_size(out_ty);
+            // `ext::asm` has already rewritten `+` to `=`.
+            let code = strip_modifiers(constraint.get().slice_from(1));
+            self.check_operand(out.span, constraint.get(), code, true, out_ty, &size);
+            output_sizes.push(size);
+        }
+
+        for &(ref constraint, ref input) in ia.inputs.iter() {
+            let input_ty = ty::expr_ty(self.tcx, &**input);
+            let size = self.operand_size(input_ty);
+            let code = constraint.get();
+            match from_str::<uint>(code) {
+                Some(tied) => {
+                    self.check_tied_operand(input.span, tied, input_ty, &size,
+                                            output_sizes.as_slice());
+                }
+                None => {
+                    self.check_operand(input.span, code, strip_modifiers(code), false,
+                                       input_ty, &size);
+                }
+            }
+        }
+
+        for clobber in ia.clobbers.get().split(',').filter(|c| !c.is_empty()) {
+            // `ext::asm` wraps each clobber as `~{name}`.
+            let name = clobber.slice(2, clobber.len() - 1);
+            if !self.is_known_clobber(name) {
+                span_err!(self.tcx.sess, expr.span, E0168,
+                          "unknown register `{}` in clobber list for the `{}` target",
+                          name, self.arch_name());
+            }
+        }
+    }
+
+    fn check_operand(&self, span: Span, constraint: &str, code: &str, is_output: bool,
+                     operand_ty: ty::t, size: &OperandSize) {
+        let location = if code.starts_with("{") && code.ends_with("}") {
+            let register = code.slice(1, code.len() - 1);
+            match self.register_bits(register) {
+                Some(bits) => Register(bits),
+                None => {
+                    span_err!(self.tcx.sess, span, E0168,
+                              "unknown register `{}` for the `{}` target",
+                              register, self.arch_name());
+                    return;
+                }
+            }
+        } else {
+            match self.constraint_location(code) {
+                Some(location) => location,
+                None => {
+                    span_err!(self.tcx.sess, span, E0167,
+                              "invalid constraint `{}` for the `{}` target",
+                              constraint, self.arch_name());
+                    return;
+                }
+            }
+        };
+
+        match location {
+            Anywhere => {}
+            Immediate => {
+                if is_output {
+                    span_err!(self.tcx.sess, span, E0171,
+                              "output operand cannot use the immediate constraint `{}`",
+                              constraint);
+                } else if !ty::type_is_integral(operand_ty) && !ty::type_is_error(operand_ty) {
+                    span_err!(self.tcx.sess, span, E0171,
+                              "immediate operand must be an integer, found `{}`",
+                              ty_to_string(self.tcx, operand_ty));
+                }
+            }
+            Register(max_bits) => {
+                match *size {
+                    Bits(bits) if bits > max_bits => {
+                        span_err!(self.tcx.sess, span, E0169,
+                                  "operand of type `{}` is too wide for constraint `{}`: \
+                                   it has {} bits, but the register holds {}",
+                                  ty_to_string(self.tcx, operand_ty), constraint,
+                                  bits, max_bits);
+                    }
+                    Aggregate => {
+                        span_err!(self.tcx.sess, span, E0170,
+                                  "operand of type `{}` cannot be passed in a register; \
+                                   pass a pointer to it instead",
+                                  ty_to_string(self.tcx, operand_ty));
+                    }
+                    Bits(..) | Unknown => {}
+                }
+            }
+        }
+    }
+
+    fn check_tied_operand(&self, span: Span, tied: uint, input_ty: ty::t,
+                          size: &OperandSize, output_sizes: &[OperandSize]) {
+        if tied >= output_sizes.len() {
+            span_err!(self.tcx.sess, span, E0172,
+                      "input operand is tied to output operand {}, but there {} only {}",
+                      tied,
+                      if output_sizes.len() == 1 { "is" } else { "are" },
+                      output_sizes.len());
+            return;
+        }
+
+        match (size, &output_sizes[tied]) {
+            (&Bits(input_bits), &Bits(output_bits)) if input_bits != output_bits => {
+                span_err!(self.tcx.sess, span, E0172,
+                          "input operand of type `{}` is tied to output operand {}, \
+                           which has a different size",
+                          ty_to_string(self.tcx, input_ty), tied);
+            }
+            _ => {}
+        }
+    }
+
+    /// The location allowed by a constraint made of single-letter codes,
+    /// such as `r` or `rm`. An operand may go wherever any of the
+    /// alternatives allows, so the widest wins.
+    fn constraint_location(&self, code: &str) -> Option<Location> {
+        if code.is_empty() {
+            return None;
+        }
+
+        let mut location = Immediate;
+        for c in code.chars() {
+            match (self.letter_location(c), location) {
+                (None, _) => return None,
+                (Some(Anywhere), _) | (_, Anywhere) => location = Anywhere,
+                (Some(Register(a)), Register(b)) if b > a => {}
+                (Some(Register(a)), _) => location = Register(a),
+                (Some(Immediate), _) => {}
+            }
+        }
+        Some(location)
+    }
+
+    fn letter_location(&self, c: char) -> Option<Location> {
+        let word = self.word_bits;
+        match (self.arch, c) {
+            (_, 'm') | (_, 'X') => Some(Anywhere),
+            (_, 'i') | (_, 'n') => Some(Immediate),
+
+            (abi::X86, c) | (abi::X86_64, c) => match c {
+                'r' | 'q' | 'Q' | 'R' | 'l' | 'p' |
+                'a' | 'b' | 'c' | 'd' | 'S' | 'D' => Some(Register(word)),
+                'A' => Some(Register(word * 2)),
+                'x' => Some(Register(128)),
+                'y' => Some(Register(64)),
+                'f' | 't' | 'u' => Some(Register(80)),
+                'g' | 'o' | 'V' => Some(Anywhere),
+                'e' | 'Z' | 'I' | 'J' | 'K' | 'L' | 'M' | 'N' | 'O' => Some(Immediate),
+                _ => None
+            },
+
+            (abi::Arm, c) => match c {
+                'r' | 'l' | 'h' | 't' => Some(Register(32)),
+                'w' => Some(Register(128)),
+                'Q' | 'o' => Some(Anywhere),
+                'I' | 'J' | 'K' | 'L' | 'M' => Some(Immediate),
+                _ => None
+            },
+
+            (abi::Mips, c) | (abi::Mipsel, c) => match c {
+                'r' | 'd' | 'y' | 'c' | 'l' => Some(Register(32)),
+                'x' | 'f' => Some(Register(64)),
+                'R' => Some(Anywhere),
+                'I' | 'J' | 'K' | 'L' | 'N' | 'O' | 'P' => Some(Immediate),
+                _ => None
+            },
+        }
+    }
+
+    /// The width of an explicitly named register, as in `{eax}`.
+    fn register_bits(&self, name: &str) -> Option<uint> {
+        fn numbered(name: &str, prefix: &str, count: uint) -> bool {
+            name.starts_with(prefix) &&
+                from_str::<uint>(name.slice_from(prefix.len())).map_or(false, |n| n < count)
+        }
+
+        match self.arch {
+            abi::X86 | abi::X86_64 => {
+                let is_64 = self.arch == abi::X86_64;
+                // A named general purpose register holds its own width. A
+                // narrower operand is widened to fit, but a wider one would
+                // be silently truncated.
+                if one_of(name, ["al", "ah", "bl", "bh", "cl", "ch", "dl", "dh"]) {
+                    return Some(8);
+                }
+                if one_of(name, ["ax", "bx", "cx", "dx", "si", "di", "bp", "sp"]) {
+                    return Some(16);
+                }
+                if one_of(name, ["eax", "ebx", "ecx", "edx", "esi", "edi", "ebp", "esp"]) {
+                    return Some(32);
+                }
+                if is_64 {
+                    if one_of(name, ["sil", "dil", "bpl", "spl"]) {
+                        return Some(8);
+                    }
+                    if one_of(name, ["rax", "rbx", "rcx", "rdx", "rsi", "rdi", "rbp", "rsp"]) {
+                        return Some(64);
+                    }
+                    // `r8` to `r15`, and their `b`, `w` and `d` sub-registers.
+                    let (base, bits) = match name.slice_from(name.len().saturating_sub(1)) {
+                        "b" => (name.slice_to(name.len() - 1), 8),
+                        "w" => (name.slice_to(name.len() - 1), 16),
+                        "d" => (name.slice_to(name.len() - 1), 32),
+                        _ => (name, 64),
+                    };
+                    if numbered(base, "r", 16) && !numbered(base, "r", 8) {
+                        return Some(bits);
+                    }
+                }
+                let vector_regs = if is_64 { 16 } else { 8 };
+                if numbered(name, "xmm", vector_regs) {
+                    Some(128)
+                } else if numbered(name, "ymm", vector_regs) {
+                    Some(256)
+                } else if numbered(name, "mm", 8) {
+                    Some(64)
+                } else {
+                    None
+                }
+            }
+
+            abi::Arm => {
+                if numbered(name, "r", 16) ||
+                   one_of(name, ["sp", "lr", "pc", "fp", "ip", "sl", "sb"]) ||
+                   numbered(name, "s", 32) {
+                    Some(32)
+                } else if numbered(name, "d", 32) {
+                    Some(64)
+                } else if numbered(name, "q", 16) {
+                    Some(128)
+                } else {
+                    None
+                }
+            }
+
+            abi::Mips | abi::Mipsel => {
+                let name = if name.starts_with("$") { name.slice_from(1) } else { name };
+                if numbered(name, "", 32) ||
+                   one_of(name, ["zero", "at", "gp", "sp", "fp", "ra", "hi", "lo", "k0", "k1"]) ||
+                   numbered(name, "v", 2) || numbered(name, "a", 4) ||
+                   numbered(name, "t", 10) || numbered(name, "s", 8) {
+                    Some(32)
+                } else if numbered(name, "f", 32) {
+                    Some(64)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    fn is_known_clobber(&self, name: &str) -> bool {
+        let is_flags = match self.arch {
+            abi::X86 | abi::X86_64 => one_of(name, ["flags", "eflags", "dirflag", "fpsr", "fpcr"]),
+            abi::Arm => one_of(name, ["apsr", "fpscr"]),
+            abi::Mips | abi::Mipsel => false,
+        };
+        is_flags || one_of(name, ["cc", "memory"]) || self.register_bits(name).is_some()
+    }
+
+    fn operand_size(&self, t: ty::t) -> OperandSize {
+        match ty::get(t).sty {
+            ty::ty_bool | ty::ty_int(ast::TyI8) | ty::ty_uint(ast::TyU8) => Bits(8),
+            ty::ty_int(ast::TyI16) | ty::ty_uint(ast::TyU16) => Bits(16),
+            ty::ty_char | ty::ty_int(ast::TyI32) | ty::ty_uint(ast::TyU32) |
+            ty::ty_float(ast::TyF32) => Bits(32),
+            ty::ty_int(ast::TyI64) | ty::ty_uint(ast::TyU64) |
+            ty::ty_float(ast::TyF64) => Bits(64),
+            ty::ty_int(ast::TyI) | ty::ty_uint(ast::TyU) | ty::ty_bare_fn(..) => {
+                Bits(self.word_bits)
+            }
+            ty::ty_ptr(ty::mt { ty: pointee, .. }) |
+            ty::ty_rptr(_, ty::mt { ty: pointee, .. }) |
+            ty::ty_uniq(pointee) => {
+                if ty::type_is_sized(self.tcx, pointee) {
+                    Bits(self.word_bits)
+                } else {
+                    Aggregate
+                }
+            }
+            ty::ty_param(..) | ty::ty_err => Unknown,
+            _ => Aggregate,
+        }
+    }
+}
+
+/// Removes the early clobber (`&`) and indirect (`*`) modifiers from the
+/// front of a constraint, leaving the codes that pick the location. An
+/// indirect operand is a pointer to the memory operand, so its own type
+/// is what gets checked against the location.
+fn strip_modifiers(code: &str) -> &str {
+    let modifiers: &[char] = &['&', '*'];
+    code.trim_left_chars(modifiers)
+}
+
+fn one_of(name: &str, names: &[&str]) -> bool {
+    names.iter().any(|&n| n == name)
+}
+
+impl<'a, 'tcx, 'v> Visitor<'v> for AsmCheckingVisitor<'a, 'tcx> {
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        match expr.node {
+            ast::ExprInlineAsm(ref ia) => self.check_inline_asm(expr, ia),
+            _ => {}
+        }
+
+        visit::walk_expr(self, expr);
+    }
+}
+
+pub fn check_crate(tcx: &ty::ctxt) {
+    let word_bits = match tcx.sess.targ_cfg.uint_type {
+        ast::TyU64 => 64,
+        _ => 32,
+    };
+    let mut visitor = AsmCheckingVisitor {
+        tcx: tcx,
+        arch: tcx.sess.targ_cfg.arch,
+        word_bits: word_bits,
+    };
+    visit::walk_crate(&mut visitor, tcx.map.krate());
+}
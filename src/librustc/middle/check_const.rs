@@ -9,6 +9,7 @@
 // except according to those terms.
 
 
+use middle::const_eval;
 use middle::def::*;
 use middle::ty;
 use middle::typeck;
@@ -158,31 +159,29 @@ fn check_expr(v: &mut CheckCrateVisitor, e: &Expr) {
             }
           }
           ExprBlock(ref block) => {
-            // Check all statements in the block
-            for stmt in block.stmts.iter() {
-                let block_span_err = |span|
-                    span_err!(v.tcx.sess, span, E0016,
-                        "blocks in constants are limited to items and tail expressions");
-                match stmt.node {
-                    StmtDecl(ref span, _) => {
-                        match span.node {
-                            DeclLocal(_) => block_span_err(span.span),
-
-                            // Item statements are allowed
-                            DeclItem(_) => {}
-                        }
-                    }
-                    StmtExpr(ref expr, _) => block_span_err(expr.span),
-                    StmtSemi(ref semi, _) => block_span_err(semi.span),
-                    StmtMac(..) => v.tcx.sess.span_bug(e.span,
-                        "unexpanded statement macro in const?!")
-                }
-            }
+            check_block_stmts(v, &**block);
             match block.expr {
                 Some(ref expr) => check_expr(v, &**expr),
                 None => {}
             }
           }
+          ExprIf(ref cond, ref then, _) => {
+            // The branch is chosen when the constant is evaluated, so
+            // the condition has to be evaluable too.
+            match const_eval::eval_const_expr_partial(v.tcx, &**cond) {
+                Ok(const_eval::const_bool(_)) => {}
+                Ok(ref val) => {
+                    span_err!(v.tcx.sess, cond.span, E0166,
+                        "expected boolean condition in constant `if`, found {}",
+                        const_eval::const_val_description(val));
+                }
+                Err(ref err) => {
+                    span_err!(v.tcx.sess, cond.span, E0166,
+                        "condition of constant `if` is not a constant: {}", *err);
+                }
+            }
+            check_block_stmts(v, &**then);
+          }
           ExprVec(_) |
           ExprAddrOf(MutImmutable, _) |
           ExprParen(..) |
@@ -211,3 +210,25 @@ fn check_expr(v: &mut CheckCrateVisitor, e: &Expr) {
     }
     visit::walk_expr(v, e);
 }
+
+fn check_block_stmts(v: &mut CheckCrateVisitor, block: &Block) {
+    for stmt in block.stmts.iter() {
+        let block_span_err = |span|
+            span_err!(v.tcx.sess, span, E0016,
+                "blocks in constants are limited to items and tail expressions");
+        match stmt.node {
+            StmtDecl(ref span, _) => {
+                match span.node {
+                    DeclLocal(_) => block_span_err(span.span),
+
+                    // Item statements are allowed
+                    DeclItem(_) => {}
+                }
+            }
+            StmtExpr(ref expr, _) => block_span_err(expr.span),
+            StmtSemi(ref semi, _) => block_span_err(semi.span),
+            StmtMac(..) => v.tcx.sess.span_bug(stmt.span,
+                "unexpanded statement macro in const?!")
+        }
+    }
+}
@@ -18,6 +18,7 @@ use middle::mem_categorization::cmt;
 use middle::pat_util::*;
 use middle::ty::*;
 use middle::ty;
+use std::cmp;
 use std::fmt;
 use std::iter::AdditiveIterator;
 use std::iter::range_inclusive;
@@ -261,20 +262,32 @@ fn check_arms(cx: &MatchCheckCtxt, arms: &[(Vec<P<Pat>>, Option<&Expr>)], source
 
             match is_useful(cx, &seen, v.as_slice(), LeaveOutWitness) {
                 NotUseful => {
-                    if source == MatchIfLetDesugar {
-                        if printed_if_let_err {
-                            // we already printed an irrefutable if-let pattern error.
-                            // We don't want two, that's just confusing.
-                        } else {
+                    match source {
+                        MatchIfLetDesugar => {
+                            if printed_if_let_err {
+                                // we already printed an irrefutable if-let pattern error.
+                                // We don't want two, that's just confusing.
+                            } else {
+                                // find the first arm pattern so we can use its span
+                                let &(ref first_arm_pats, _) = &arms[0];
+                                let first_pat = first_arm_pats.get(0);
+                                let span = first_pat.span;
+                                span_err!(cx.tcx.sess, span, E0162, "irrefutable if-let pattern");
+                                printed_if_let_err = true;
+                            }
+                        },
+
+                        MatchWhileLetDesugar => {
                             // find the first arm pattern so we can use its span
                             let &(ref first_arm_pats, _) = &arms[0];
                             let first_pat = first_arm_pats.get(0);
                             let span = first_pat.span;
-                            span_err!(cx.tcx.sess, span, E0162, "irrefutable if-let pattern");
-                            printed_if_let_err = true;
-                        }
-                    } else {
-                        span_err!(cx.tcx.sess, pat.span, E0001, "unreachable pattern");
+                            span_err!(cx.tcx.sess, span, E0165, "irrefutable while-let pattern");
+                        },
+
+                        MatchNormal => {
+                            span_err!(cx.tcx.sess, pat.span, E0001, "unreachable pattern")
+                        },
                     }
                 }
                 Useful => (),
@@ -534,16 +547,24 @@ fn is_useful(cx: &MatchCheckCtxt,
         None if v.len() == 0 => return NotUseful,
         None => v[0]
     };
-    let left_ty = if real_pat.id == DUMMY_NODE_ID {
-        ty::mk_nil()
-    } else {
-        ty::pat_ty(cx.tcx, &*real_pat)
-    };
+    if real_pat.id == DUMMY_NODE_ID {
+        // Every pattern in this column is a wildcard filled in by
+        // specialization, so we don't know its type. The column can't
+        // affect usefulness, and the witness for it is a wildcard.
+        let matrix = Matrix(rows.iter().map(|r| Vec::from_slice(r.tail())).collect());
+        return match is_useful(cx, &matrix, v.tail(), witness) {
+            UsefulWithWitness(pats) => {
+                let mut new_pats = vec![P(DUMMY_WILD_PAT.clone())];
+                new_pats.extend(pats.into_iter());
+                UsefulWithWitness(new_pats)
+            },
+            result => result
+        };
+    }
+    let left_ty = ty::pat_ty(cx.tcx, &*real_pat);
 
-    let max_slice_length = rows.iter().filter_map(|row| match row.get(0).node {
-        PatVec(ref before, _, ref after) => Some(before.len() + after.len()),
-        _ => None
-    }).max().map_or(0, |v| v + 1);
+    let max_slice_length = max_slice_length(rows.iter().map(|row| *row.get(0))
+                                                .chain(Some(v[0]).into_iter()));
 
     let constructors = pat_constructors(cx, v[0], left_ty, max_slice_length);
     if constructors.is_empty() {
@@ -596,6 +617,33 @@ fn is_useful(cx: &MatchCheckCtxt,
     }
 }
 
+/// Computes the slice length which stands for all slices at least that long
+/// in a column of patterns.
+///
+/// A slice of at least `max_prefix_len + max_suffix_len` elements matches the
+/// elements before and after the `..` of every variable-length pattern at
+/// distinct indices, and it is longer than every fixed-length pattern, so all
+/// such slices are matched by exactly the same rows. Checking `Slice(n)` for
+/// every `n` up to this length is then equivalent to checking every length.
+fn max_slice_length<'a, I: Iterator<&'a Pat>>(patterns: I) -> uint {
+    let mut max_prefix_len = 0;
+    let mut max_suffix_len = 0;
+    let mut max_fixed_len = 0;
+    for pat in patterns {
+        match raw_pat(pat).node {
+            PatVec(ref before, Some(_), ref after) => {
+                max_prefix_len = cmp::max(max_prefix_len, before.len());
+                max_suffix_len = cmp::max(max_suffix_len, after.len());
+            }
+            PatVec(ref before, None, ref after) => {
+                max_fixed_len = cmp::max(max_fixed_len, before.len() + after.len());
+            }
+            _ => {}
+        }
+    }
+    cmp::max(max_fixed_len + 1, max_prefix_len + max_suffix_len)
+}
+
 fn is_useful_specialized(cx: &MatchCheckCtxt, &Matrix(ref m): &Matrix,
                          v: &[&Pat], ctor: Constructor, lty: ty::t,
                          witness: WitnessPreference) -> Usefulness {
@@ -254,6 +254,18 @@ impl<'a, 'tcx> ConstEvalVisitor<'a, 'tcx> {
                 }
             }
 
+            ast::ExprIf(ref cond, ref then, ref els) => {
+                let then = match then.expr {
+                    Some(ref e) => self.classify(&**e),
+                    None => integral_const
+                };
+                let els = match *els {
+                    Some(ref e) => self.classify(&**e),
+                    None => integral_const
+                };
+                join_all(vec!(self.classify(&**cond), then, els).into_iter())
+            }
+
             _ => non_const
         };
         self.ccache.insert(did, cn);
@@ -377,7 +389,7 @@ pub fn eval_const_expr(tcx: &ty::ctxt, e: &Expr) -> const_val {
 }
 
 pub fn eval_const_expr_partial(tcx: &ty::ctxt, e: &Expr) -> Result<const_val, String> {
-    fn fromb(b: bool) -> Result<const_val, String> { Ok(const_int(b as i64)) }
+    fn fromb(b: bool) -> Result<const_val, String> { Ok(const_bool(b)) }
     match e.node {
       ExprUnary(UnNeg, ref inner) => {
         match eval_const_expr_partial(tcx, &**inner) {
@@ -398,8 +410,20 @@ pub fn eval_const_expr_partial(tcx: &ty::ctxt, e: &Expr) -> Result<const_val, St
         }
       }
       ExprBinary(op, ref a, ref b) => {
-        match (eval_const_expr_partial(tcx, &**a),
-               eval_const_expr_partial(tcx, &**b)) {
+        let operands = match (eval_const_expr_partial(tcx, &**a),
+                              eval_const_expr_partial(tcx, &**b)) {
+          // An unsuffixed integer literal evaluates to `const_int` even
+          // where inference makes it unsigned, as in `BUF_SIZE * 2`, so
+          // line such an operand up with an unsigned one.
+          (Ok(const_int(a)), Ok(const_uint(b))) if a >= 0 && !ast_util::is_shift_binop(op) => {
+            (Ok(const_uint(a as u64)), Ok(const_uint(b)))
+          }
+          (Ok(const_uint(a)), Ok(const_int(b))) if b >= 0 && !ast_util::is_shift_binop(op) => {
+            (Ok(const_uint(a)), Ok(const_uint(b as u64)))
+          }
+          operands => operands
+        };
+        match operands {
           (Ok(const_float(a)), Ok(const_float(b))) => {
             match op {
               BiAdd => Ok(const_float(a + b)),
@@ -553,21 +577,67 @@ pub fn eval_const_expr_partial(tcx: &ty::ctxt, e: &Expr) -> Result<const_val, St
       ExprPath(_) => {
           match lookup_const(tcx, e) {
               Some(actual_e) => eval_const_expr_partial(tcx, &*actual_e),
-              None => Err("non-constant path in constant expr".to_string())
+              None => Err(non_const_path_reason(tcx, e))
           }
       }
       ExprLit(ref lit) => Ok(lit_to_const(&**lit)),
       ExprParen(ref e)     => eval_const_expr_partial(tcx, &**e),
-      ExprBlock(ref block) => {
-        match block.expr {
-            Some(ref expr) => eval_const_expr_partial(tcx, &**expr),
-            None => Ok(const_int(0i64))
+      ExprBlock(ref block) => eval_const_block_partial(tcx, &**block),
+      ExprIf(ref cond, ref then, ref els) => {
+        match eval_const_expr_partial(tcx, &**cond) {
+          Ok(const_bool(true)) => eval_const_block_partial(tcx, &**then),
+          Ok(const_bool(false)) => {
+            match *els {
+              Some(ref els) => eval_const_expr_partial(tcx, &**els),
+              None => Err("`if` without an `else` in constant expr".to_string())
+            }
+          }
+          Ok(_) => Err("non-boolean condition in constant `if`".to_string()),
+          err => err
         }
       }
       _ => Err("unsupported constant expr".to_string())
     }
 }
 
+fn eval_const_block_partial(tcx: &ty::ctxt, block: &Block) -> Result<const_val, String> {
+    match block.expr {
+        Some(ref expr) => eval_const_expr_partial(tcx, &**expr),
+        None => Ok(const_int(0i64))
+    }
+}
+
+fn non_const_path_reason(tcx: &ty::ctxt, e: &Expr) -> String {
+    let opt_def = tcx.def_map.borrow().find_copy(&e.id);
+    match opt_def {
+        Some(def::DefStatic(def_id, false)) => {
+            // Foreign statics have no initializer to evaluate, nor do
+            // statics whose initializer was left out of their crate's
+            // metadata.
+            format!("the value of `{}` is not available in constant expr",
+                    ty::item_path_str(tcx, def_id))
+        }
+        Some(def::DefStatic(def_id, true)) => {
+            format!("mutable static `{}` in constant expr",
+                    ty::item_path_str(tcx, def_id))
+        }
+        _ => "non-constant path in constant expr".to_string()
+    }
+}
+
+/// A short description of the kind of value `val` is, for error messages.
+pub fn const_val_description(val: &const_val) -> &'static str {
+    match *val {
+        const_float(_) => "float",
+        const_int(_) => "signed integer",
+        const_uint(_) => "unsigned integer",
+        const_str(_) => "string",
+        const_binary(_) => "binary array",
+        const_bool(_) => "boolean",
+        const_nil => "()",
+    }
+}
+
 pub fn lit_to_const(lit: &Lit) -> const_val {
     match lit.node {
         LitStr(ref s, _) => const_str((*s).clone()),
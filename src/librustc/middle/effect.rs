@@ -16,6 +16,7 @@ use middle::ty;
 use middle::typeck::MethodCall;
 use util::ppaux;
 
+use syntax::abi;
 use syntax::ast;
 use syntax::ast_util::PostExpansionMethod;
 use syntax::codemap::Span;
@@ -37,6 +38,102 @@ fn type_is_unsafe_function(ty: ty::t) -> bool {
     }
 }
 
+/// An operation which is only allowed in an unsafe function or block.
+#[deriving(Clone, PartialEq, Show)]
+pub enum UnsafeOperation {
+    UnsafeMethodCall,
+    UnsafeFnCall,
+    /// A call to a function with a foreign ABI.
+    ForeignFnCall,
+    /// A call to the `transmute` intrinsic.
+    TransmuteCall,
+    RawPtrDeref,
+    InlineAsm,
+    MutStaticAccess,
+}
+
+impl UnsafeOperation {
+    /// The description used in the error for an operation outside of an
+    /// unsafe context.
+    pub fn description(&self) -> &'static str {
+        match *self {
+            UnsafeMethodCall => "invocation of unsafe method",
+            UnsafeFnCall | ForeignFnCall | TransmuteCall => "call to unsafe function",
+            RawPtrDeref => "dereference of unsafe pointer",
+            InlineAsm => "use of inline assembly",
+            MutStaticAccess => "use of mutable static",
+        }
+    }
+
+    /// A short, stable identifier for the operation, used in reports.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            UnsafeMethodCall => "unsafe_method_call",
+            UnsafeFnCall => "unsafe_fn_call",
+            ForeignFnCall => "ffi_call",
+            TransmuteCall => "transmute",
+            RawPtrDeref => "raw_deref",
+            InlineAsm => "inline_asm",
+            MutStaticAccess => "static_mut_access",
+        }
+    }
+}
+
+/// Determine whether `expr` itself (not counting its subexpressions)
+/// performs an operation which requires an unsafe context.
+pub fn unsafe_operation(tcx: &ty::ctxt, expr: &ast::Expr) -> Option<UnsafeOperation> {
+    match expr.node {
+        ast::ExprMethodCall(_, _, _) => {
+            let method_call = MethodCall::expr(expr.id);
+            let base_type = tcx.method_map.borrow().get(&method_call).ty;
+            debug!("effect: method call case, base type is {}",
+                   ppaux::ty_to_string(tcx, base_type));
+            if type_is_unsafe_function(base_type) {
+                return Some(UnsafeMethodCall);
+            }
+        }
+        ast::ExprCall(ref base, _) => {
+            let base_type = ty::node_id_to_type(tcx, base.id);
+            debug!("effect: call case, base type is {}",
+                   ppaux::ty_to_string(tcx, base_type));
+            if type_is_unsafe_function(base_type) {
+                return Some(match ty::get(base_type).sty {
+                    ty::ty_bare_fn(ref f) if f.abi == abi::RustIntrinsic => {
+                        match tcx.def_map.borrow().find(&base.id) {
+                            Some(&def::DefFn(did, _, _)) if
+                                    ty::item_path_str(tcx, did).as_slice()
+                                                               .ends_with("transmute") => {
+                                TransmuteCall
+                            }
+                            _ => UnsafeFnCall,
+                        }
+                    }
+                    ty::ty_bare_fn(ref f) if f.abi != abi::Rust => ForeignFnCall,
+                    _ => UnsafeFnCall,
+                });
+            }
+        }
+        ast::ExprUnary(ast::UnDeref, ref base) => {
+            let base_type = ty::node_id_to_type(tcx, base.id);
+            debug!("effect: unary case, base type is {}",
+                    ppaux::ty_to_string(tcx, base_type));
+            match ty::get(base_type).sty {
+                ty::ty_ptr(_) => return Some(RawPtrDeref),
+                _ => {}
+            }
+        }
+        ast::ExprInlineAsm(..) => return Some(InlineAsm),
+        ast::ExprPath(..) => {
+            match ty::resolve_expr(tcx, expr) {
+                def::DefStatic(_, true) => return Some(MutStaticAccess),
+                _ => {}
+            }
+        }
+        _ => {}
+    }
+    None
+}
+
 struct EffectCheckVisitor<'a, 'tcx: 'a> {
     tcx: &'a ty::ctxt<'tcx>,
 
@@ -142,54 +239,18 @@ impl<'a, 'tcx, 'v> Visitor<'v> for EffectCheckVisitor<'a, 'tcx> {
     }
 
     fn visit_expr(&mut self, expr: &ast::Expr) {
+        match unsafe_operation(self.tcx, expr) {
+            Some(op) => self.require_unsafe(expr.span, op.description()),
+            None => {}
+        }
+
         match expr.node {
-            ast::ExprMethodCall(_, _, _) => {
-                let method_call = MethodCall::expr(expr.id);
-                let base_type = self.tcx.method_map.borrow().get(&method_call).ty;
-                debug!("effect: method call case, base type is {}",
-                       ppaux::ty_to_string(self.tcx, base_type));
-                if type_is_unsafe_function(base_type) {
-                    self.require_unsafe(expr.span,
-                                        "invocation of unsafe method")
-                }
-            }
-            ast::ExprCall(ref base, _) => {
-                let base_type = ty::node_id_to_type(self.tcx, base.id);
-                debug!("effect: call case, base type is {}",
-                       ppaux::ty_to_string(self.tcx, base_type));
-                if type_is_unsafe_function(base_type) {
-                    self.require_unsafe(expr.span, "call to unsafe function")
-                }
-            }
-            ast::ExprUnary(ast::UnDeref, ref base) => {
-                let base_type = ty::node_id_to_type(self.tcx, base.id);
-                debug!("effect: unary case, base type is {}",
-                        ppaux::ty_to_string(self.tcx, base_type));
-                match ty::get(base_type).sty {
-                    ty::ty_ptr(_) => {
-                        self.require_unsafe(expr.span,
-                                            "dereference of unsafe pointer")
-                    }
-                    _ => {}
-                }
-            }
             ast::ExprAssign(ref base, _) | ast::ExprAssignOp(_, ref base, _) => {
                 self.check_str_index(&**base);
             }
             ast::ExprAddrOf(ast::MutMutable, ref base) => {
                 self.check_str_index(&**base);
             }
-            ast::ExprInlineAsm(..) => {
-                self.require_unsafe(expr.span, "use of inline assembly")
-            }
-            ast::ExprPath(..) => {
-                match ty::resolve_expr(self.tcx, expr) {
-                    def::DefStatic(_, true) => {
-                        self.require_unsafe(expr.span, "use of mutable static")
-                    }
-                    _ => {}
-                }
-            }
             _ => {}
         }
 
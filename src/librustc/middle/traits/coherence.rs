@@ -46,6 +46,58 @@ pub fn impl_can_satisfy(infcx: &InferCtxt,
     }
 }
 
+pub fn impl_specializes(infcx: &InferCtxt,
+                        impl1_def_id: ast::DefId,
+                        impl2_def_id: ast::DefId)
+                        -> bool
+{
+    /*!
+     * True if `impl1` specializes `impl2`: `impl2` is marked
+     * `#[default_impl]` and applies to every type that `impl1`
+     * applies to, but not the other way around.
+     */
+
+    debug!("impl_specializes({}, {})",
+           impl1_def_id.repr(infcx.tcx),
+           impl2_def_id.repr(infcx.tcx));
+
+    ty::impl_is_default(infcx.tcx, impl2_def_id) &&
+        impl_covers(infcx, impl2_def_id, impl1_def_id) &&
+        !impl_covers(infcx, impl1_def_id, impl2_def_id)
+}
+
+fn impl_covers(infcx: &InferCtxt,
+               impl1_def_id: ast::DefId,
+               impl2_def_id: ast::DefId)
+               -> bool
+{
+    // `impl2` provides an implementation of `Foo<X,Y> for Z`. Unlike
+    // `impl_can_satisfy`, the type parameters of `impl2` are left
+    // unsubstituted, so that `impl1` must match them for *any*
+    // choice of types rather than for some particular one. The bounds
+    // that `impl2` puts on them are in scope while doing so.
+    let free_id = if impl2_def_id.krate == ast::LOCAL_CRATE {
+        impl2_def_id.node
+    } else {
+        ast::DUMMY_NODE_ID
+    };
+    let impl2_generics = ty::lookup_item_type(infcx.tcx, impl2_def_id).generics;
+    let param_env = ty::construct_parameter_environment(infcx.tcx, DUMMY_SP,
+                                                        &impl2_generics, free_id);
+    let impl2_self_ty =
+        ty::impl_trait_ref(infcx.tcx, impl2_def_id).unwrap()
+            .self_ty()
+            .subst(infcx.tcx, &param_env.free_substs);
+
+    infcx.probe(|| {
+        match evaluate_impl(infcx, &param_env, infcx.tcx, ObligationCause::dummy(),
+                            impl1_def_id, impl2_self_ty) {
+            EvaluatedToMatch => true,
+            EvaluatedToAmbiguity | EvaluatedToUnmatch => false,
+        }
+    })
+}
+
 pub fn impl_is_local(tcx: &ty::ctxt,
                      impl_def_id: ast::DefId)
                      -> bool
@@ -313,6 +313,20 @@ pub fn overlapping_impls(infcx: &InferCtxt,
     coherence::impl_can_satisfy(infcx, impl2_def_id, impl1_def_id)
 }
 
+pub fn impl_specializes(infcx: &InferCtxt,
+                        impl1_def_id: ast::DefId,
+                        impl2_def_id: ast::DefId)
+                        -> bool
+{
+    /*!
+     * True if `impl1` is a strictly more specific implementation
+     * than the `#[default_impl]` `impl2`, and hence takes precedence
+     * over it wherever both apply.
+     */
+
+    coherence::impl_specializes(infcx, impl1_def_id, impl2_def_id)
+}
+
 pub fn obligations_for_generics(tcx: &ty::ctxt,
                                 cause: ObligationCause,
                                 generics: &ty::Generics,
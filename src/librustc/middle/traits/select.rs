@@ -90,6 +90,10 @@ enum MatchResult<T> {
  * matching where clause. Part of the reason for this is that where
  * clauses can give additional information (like, the types of output
  * parameters) that would have to be inferred from the impl.
+ *
+ * The other exception is specialization: an impl marked
+ * `#[default_impl]` may overlap with impls that are strictly more
+ * specific than it. When both match, the more specific impl wins.
  */
 #[deriving(Clone)]
 enum Candidate {
@@ -347,6 +351,12 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
         debug!("assembled {} candidates for {}",
                candidates.len(), stack.repr(self.tcx()));
 
+        // A `#[default_impl]` gives way to any impl that specializes
+        // it, so drop those before looking for ambiguity.
+        if candidates.len() > 1 {
+            self.prune_specialized_candidates(&mut candidates);
+        }
+
         // Examine candidates to determine outcome. Ideally we will
         // have exactly one candidate that is definitively applicable.
 
@@ -376,6 +386,37 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
         }
     }
 
+    fn prune_specialized_candidates(&self, candidates: &mut Vec<Candidate>) {
+        /*!
+         * Removes each matched impl candidate that is specialized by
+         * another matched impl candidate. Ambiguous candidates are
+         * left alone, and do not displace anything themselves, since
+         * a more specific impl may yet apply once the type variables
+         * involved are resolved.
+         */
+
+        let matched_impls: Vec<ast::DefId> = candidates.iter().filter_map(|c| {
+            match *c {
+                Impl(MatchedImplCandidate(impl_def_id)) => Some(impl_def_id),
+                _ => None,
+            }
+        }).collect();
+
+        candidates.retain(|c| {
+            match *c {
+                Impl(MatchedImplCandidate(impl_def_id)) => {
+                    !matched_impls.iter().any(|&other_def_id| {
+                        other_def_id != impl_def_id &&
+                            super::impl_specializes(self.infcx,
+                                                    other_def_id,
+                                                    impl_def_id)
+                    })
+                }
+                _ => true,
+            }
+        });
+    }
+
     fn pick_candidate_cache(&self,
                             _obligation: &Obligation,
                             skol_obligation_self_ty: ty::t)
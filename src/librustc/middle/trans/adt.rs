@@ -21,6 +21,23 @@
  * enum, so structs/tuples/etc. have one pseudo-variant with
  * discriminant 0; i.e., as if they were a univariant enum.
  *
+ * Unless a `#[repr]` attribute asks for something else, the layout is
+ * ours to choose, and two tricks keep it small:
+ *
+ * - The fields of a struct may be stored in a different order than
+ *   they are declared in, when that avoids alignment padding.  The
+ *   `memory_index` of a `Struct` maps each field to its position in
+ *   the LLVM type; everything here takes field indices in declaration
+ *   order.
+ *
+ * - An enum in which only one case has any data can keep its
+ *   discriminant in values which that data can never have: the other
+ *   cases are numbered past the end of the valid range of a `bool`, a
+ *   `char` or the discriminant of a nested enum in that case.  So
+ *   `Option<bool>` is a single byte, holding 2 for `None`.  (Nullable
+ *   pointers are the special case of this where the niche is the null
+ *   value of a pointer, and are represented separately.)
+ *
  * Having everything in one place will enable improvements to data
  * structure representation; possibilities include:
  *
@@ -45,12 +62,13 @@
 
 #![allow(unsigned_negate)]
 
-use libc::c_ulonglong;
+use libc::{c_uint, c_ulonglong};
+use std::cmp;
 use std::collections::Map;
 use std::num::Int;
 use std::rc::Rc;
 
-use llvm::{ValueRef, True, IntEQ, IntNE};
+use llvm::{ValueRef, True, IntEQ, IntNE, IntULT};
 use middle::subst;
 use middle::subst::Subst;
 use middle::trans::_match;
@@ -123,6 +141,25 @@ pub enum Repr {
         pub nndiscr: Disr,
         pub ptrfield: PointerField,
         pub nullfields: Vec<ty::t>,
+    },
+    /**
+     * Any number of cases, only one of which has data: the case with
+     * discriminant `dataful_discr` is represented by the struct `dataful`,
+     * and every other case `d` by the value `niche_start + d` (wrapping
+     * around) of the scalar described by `niche`, which that struct never
+     * holds.  The rest of the struct is undefined/unused in those cases.
+     *
+     * For example, `Option<bool>` is a single byte, 0 or 1 for `Some` and
+     * 2 for `None`.
+     */
+    NicheFilling {
+        pub dataful: Struct,
+        pub dataful_discr: Disr,
+        pub niche: Niche,
+        pub niche_start: u64,
+        /// The field types of every case, indexed by discriminant; all but
+        /// those of `dataful_discr` are zero sized.
+        pub nichefields: Vec<Vec<ty::t>>,
     }
 }
 
@@ -135,7 +172,41 @@ pub struct Struct {
     pub align: u64,
     pub sized: bool,
     pub packed: bool,
-    pub fields: Vec<ty::t>
+    pub fields: Vec<ty::t>,
+    // The position of each field (in declaration order) in the LLVM type.
+    pub memory_index: Vec<uint>
+}
+
+/// A scalar inside some value which is known to hold only part of the values
+/// of its type.
+#[deriving(Eq, PartialEq, Show)]
+pub struct Niche {
+    /// The GEP indices leading to the scalar, after the leading 0.
+    pub path: Vec<uint>,
+    pub ity: IntType,
+    /// The range of values it may hold, wrapping around if `valid_end` is
+    /// smaller than `valid_start`.
+    pub valid_start: u64,
+    pub valid_end: u64
+}
+
+impl Niche {
+    /// The number of values the scalar never holds.
+    pub fn available(&self, cx: &CrateContext) -> u64 {
+        let mask = int_mask(cx, self.ity);
+        mask - ((self.valid_end - self.valid_start) & mask)
+    }
+}
+
+impl Struct {
+    /// The field types, in the order they are laid out in memory.
+    pub fn fields_in_memory_order(&self) -> Vec<ty::t> {
+        let mut fields = self.fields.clone();
+        for (i, &ty) in self.fields.iter().enumerate() {
+            *fields.get_mut(*self.memory_index.get(i)) = ty;
+        }
+        fields
+    }
 }
 
 /**
@@ -175,7 +246,15 @@ fn represent_type_uncached(cx: &CrateContext, t: ty::t) -> Repr {
             let dtor = ty::ty_dtor(cx.tcx(), def_id).has_drop_flag();
             if dtor { ftys.push(ty::mk_bool()); }
 
-            return Univariant(mk_struct(cx, ftys.as_slice(), packed), dtor)
+            let reorder = !packed && !ty::lookup_simd(cx.tcx(), def_id) &&
+                ty::lookup_repr_hints(cx.tcx(), def_id).iter().all(|&h| h == attr::ReprAny);
+            let st = if reorder {
+                // The last declared field and the drop flag stay where they are.
+                mk_reordered_struct(cx, ftys.as_slice(), if dtor { 2 } else { 1 })
+            } else {
+                mk_struct(cx, ftys.as_slice(), packed)
+            };
+            return Univariant(st, dtor)
         }
         ty::ty_unboxed_closure(def_id, _) => {
             let upvars = ty::unboxed_closure_upvars(cx.tcx(), def_id);
@@ -258,6 +337,34 @@ fn represent_type_uncached(cx: &CrateContext, t: ty::t) -> Repr {
                 }
             }
 
+            if !dtor && hint == attr::ReprAny {
+                // Niche filling: if only one case has any data, the other
+                // cases can be told apart by values which that data never has.
+                let mut dataful = cases.iter().filter(|c| !c.is_zerolen(cx));
+                match (dataful.next(), dataful.next()) {
+                    (Some(case), None) => {
+                        let st = mk_struct(cx, case.tys.as_slice(), false);
+                        match find_struct_niche(cx, &st, 0) {
+                            Some(niche) => {
+                                if niche.available(cx) >= cases.len() as u64 {
+                                    let mask = int_mask(cx, niche.ity);
+                                    return NicheFilling {
+                                        dataful: st,
+                                        dataful_discr: case.discr,
+                                        niche_start: (niche.valid_end + 1) & mask,
+                                        niche: niche,
+                                        nichefields: cases.iter().map(|c| c.tys.clone())
+                                                          .collect()
+                                    };
+                                }
+                            }
+                            None => {}
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
             // The general case.
             assert!((cases.len() - 1) as i64 >= 0);
             let bounds = IntBounds { ulo: 0, uhi: (cases.len() - 1) as u64,
@@ -346,29 +453,132 @@ fn get_cases(tcx: &ty::ctxt, def_id: ast::DefId, substs: &subst::Substs) -> Vec<
 }
 
 fn mk_struct(cx: &CrateContext, tys: &[ty::t], packed: bool) -> Struct {
-    if tys.iter().all(|&ty| ty::type_is_sized(cx.tcx(), ty)) {
-        let lltys = tys.iter().map(|&ty| type_of::sizing_type_of(cx, ty)).collect::<Vec<_>>();
-        let llty_rec = Type::struct_(cx, lltys.as_slice(), packed);
-        Struct {
-            size: machine::llsize_of_alloc(cx, llty_rec),
-            align: machine::llalign_of_min(cx, llty_rec),
-            sized: true,
-            packed: packed,
-            fields: Vec::from_slice(tys),
+    mk_struct_in_order(cx, tys, packed, range(0, tys.len()).collect())
+}
+
+/// Lays out a struct with the fields `tys[order[0]]`, `tys[order[1]]`, ...
+fn mk_struct_in_order(cx: &CrateContext, tys: &[ty::t], packed: bool,
+                      order: Vec<uint>) -> Struct {
+    // Ignore any dynamically sized fields.
+    let lltys = order.iter().map(|&i| tys[i])
+        .filter(|&ty| ty::type_is_sized(cx.tcx(), ty))
+        .map(|ty| type_of::sizing_type_of(cx, ty)).collect::<Vec<_>>();
+    let llty_rec = Type::struct_(cx, lltys.as_slice(), packed);
+    let mut memory_index = Vec::from_elem(tys.len(), 0u);
+    for (memory_ix, &ix) in order.iter().enumerate() {
+        *memory_index.get_mut(ix) = memory_ix;
+    }
+    Struct {
+        size: machine::llsize_of_alloc(cx, llty_rec),
+        align: machine::llalign_of_min(cx, llty_rec),
+        sized: tys.iter().all(|&ty| ty::type_is_sized(cx.tcx(), ty)),
+        packed: packed,
+        fields: Vec::from_slice(tys),
+        memory_index: memory_index,
+    }
+}
+
+/**
+ * Lays out a struct whose representation is up to us, moving the fields
+ * around if that leaves less padding between them.  The last `pinned`
+ * fields stay at the end: the last declared field may be unsized, or be
+ * coerced to an unsized type, and the drop flag is found by its position.
+ */
+fn mk_reordered_struct(cx: &CrateContext, tys: &[ty::t], pinned: uint) -> Struct {
+    let movable = tys.len() - cmp::min(pinned, tys.len());
+    let declared = range(0, movable).collect::<Vec<_>>();
+    if movable < 2 || !tys.slice_to(movable).iter().all(|&ty| ty::type_is_sized(cx.tcx(), ty)) {
+        return mk_struct(cx, tys, false);
+    }
+
+    // Alignments are powers of two, so with the most aligned fields first
+    // every field starts right where the one before it ends.  The sort is
+    // stable, which keeps fields of equal alignment in declaration order.
+    let mut order = declared.clone();
+    order.sort_by(|&a, &b| type_of::align_of(cx, tys[b]).cmp(&type_of::align_of(cx, tys[a])));
+    if fields_end(cx, tys, order.as_slice()) >= fields_end(cx, tys, declared.as_slice()) {
+        return mk_struct(cx, tys, false);
+    }
+
+    order.extend(range(movable, tys.len()));
+    mk_struct_in_order(cx, tys, false, order)
+}
+
+/// The offset right after the fields `tys[order[0]]`, `tys[order[1]]`, ...
+fn fields_end(cx: &CrateContext, tys: &[ty::t], order: &[uint]) -> u64 {
+    order.iter().fold(0, |offset, &i| {
+        let align = type_of::align_of(cx, tys[i]) as u64;
+        let size = machine::llsize_of_alloc(cx, type_of::sizing_type_of(cx, tys[i]));
+        roundup(offset, align) + size
+    })
+}
+
+/// Finds the scalar with the most unused values in a value of type `t`.
+fn find_niche(cx: &CrateContext, t: ty::t) -> Option<Niche> {
+    match ty::get(t).sty {
+        ty::ty_bool => Some(Niche {
+            path: vec!(),
+            ity: attr::UnsignedInt(ast::TyU8),
+            valid_start: 0,
+            valid_end: 1
+        }),
+        ty::ty_char => Some(Niche {
+            path: vec!(),
+            ity: attr::UnsignedInt(ast::TyU32),
+            valid_start: 0,
+            valid_end: 0x10FFFF
+        }),
+        ty::ty_tup(..) | ty::ty_struct(..) | ty::ty_enum(..) | ty::ty_unboxed_closure(..) => {
+            match *represent_type(cx, t) {
+                CEnum(ity, min, max) => {
+                    let mask = int_mask(cx, ity);
+                    Some(Niche {
+                        path: vec!(),
+                        ity: ity,
+                        valid_start: min & mask,
+                        valid_end: max & mask
+                    })
+                }
+                General(ity, ref cases, _) => Some(Niche {
+                    path: vec!(0),
+                    ity: ity,
+                    valid_start: 0,
+                    valid_end: (cases.len() - 1) as u64
+                }),
+                Univariant(ref st, dtor) => find_struct_niche(cx, st, if dtor { 1 } else { 0 }),
+                NicheFilling { ref niche, niche_start, ref nichefields, .. } => {
+                    // The values this enum uses for its other cases are taken.
+                    let mask = int_mask(cx, niche.ity);
+                    Some(Niche {
+                        path: niche.path.clone(),
+                        ity: niche.ity,
+                        valid_start: niche.valid_start,
+                        valid_end: (niche_start + nichefields.len() as u64 - 1) & mask
+                    })
+                }
+                RawNullablePointer { .. } | StructWrappedNullablePointer { .. } => None
+            }
         }
-    } else {
-        // Ignore any dynamically sized fields.
-        let lltys = tys.iter().filter(|&ty| ty::type_is_sized(cx.tcx(), *ty))
-            .map(|&ty| type_of::sizing_type_of(cx, ty)).collect::<Vec<_>>();
-        let llty_rec = Type::struct_(cx, lltys.as_slice(), packed);
-        Struct {
-            size: machine::llsize_of_alloc(cx, llty_rec),
-            align: machine::llalign_of_min(cx, llty_rec),
-            sized: false,
-            packed: packed,
-            fields: Vec::from_slice(tys),
+        _ => None
+    }
+}
+
+/// Finds the best niche among the fields of a struct, leaving out the last
+/// `skip` of them (which might be a drop flag).
+fn find_struct_niche(cx: &CrateContext, st: &Struct, skip: uint) -> Option<Niche> {
+    let mut best: Option<Niche> = None;
+    for (i, &ty) in st.fields.iter().enumerate().take(st.fields.len() - skip) {
+        match find_niche(cx, ty) {
+            Some(mut niche) => {
+                if best.as_ref().map_or(true, |b| niche.available(cx) > b.available(cx)) {
+                    niche.path.insert(0, *st.memory_index.get(i));
+                    best = Some(niche);
+                }
+            }
+            None => {}
         }
     }
+    best
 }
 
 struct IntBounds {
@@ -439,6 +649,13 @@ pub fn ll_inttype(cx: &CrateContext, ity: IntType) -> Type {
     }
 }
 
+/// All the bits of an integer of type `ity`.
+fn int_mask(cx: &CrateContext, ity: IntType) -> u64 {
+    let bits = machine::llbitsize_of_real(cx, ll_inttype(cx, ity));
+    assert!(bits <= 64);
+    -1u64 >> (64 - bits as uint)
+}
+
 fn bounds_usable(cx: &CrateContext, ity: IntType, bounds: &IntBounds) -> bool {
     debug!("bounds_usable: {:?} {:?}", ity, bounds);
     match ity {
@@ -488,7 +705,8 @@ pub fn incomplete_type_of(cx: &CrateContext, r: &Repr, name: &str) -> Type {
 pub fn finish_type_of(cx: &CrateContext, r: &Repr, llty: &mut Type) {
     match *r {
         CEnum(..) | General(..) | RawNullablePointer { .. } => { }
-        Univariant(ref st, _) | StructWrappedNullablePointer { nonnull: ref st, .. } =>
+        Univariant(ref st, _) | StructWrappedNullablePointer { nonnull: ref st, .. } |
+        NicheFilling { dataful: ref st, .. } =>
             llty.set_struct_body(struct_llfields(cx, st, false, false).as_slice(),
                                  st.packed)
     }
@@ -502,7 +720,8 @@ fn generic_type_of(cx: &CrateContext,
     match *r {
         CEnum(ity, _, _) => ll_inttype(cx, ity),
         RawNullablePointer { nnty, .. } => type_of::sizing_type_of(cx, nnty),
-        Univariant(ref st, _) | StructWrappedNullablePointer { nonnull: ref st, .. } => {
+        Univariant(ref st, _) | StructWrappedNullablePointer { nonnull: ref st, .. } |
+        NicheFilling { dataful: ref st, .. } => {
             match name {
                 None => {
                     Type::struct_(cx, struct_llfields(cx, st, sizing, dst).as_slice(),
@@ -558,11 +777,12 @@ fn generic_type_of(cx: &CrateContext,
 }
 
 fn struct_llfields(cx: &CrateContext, st: &Struct, sizing: bool, dst: bool) -> Vec<Type> {
+    let fields = st.fields_in_memory_order();
     if sizing {
-        st.fields.iter().filter(|&ty| !dst || ty::type_is_sized(cx.tcx(), *ty))
+        fields.iter().filter(|&ty| !dst || ty::type_is_sized(cx.tcx(), *ty))
             .map(|&ty| type_of::sizing_type_of(cx, ty)).collect()
     } else {
-        st.fields.iter().map(|&ty| type_of::type_of(cx, ty)).collect()
+        fields.iter().map(|&ty| type_of::type_of(cx, ty)).collect()
     }
 }
 
@@ -576,7 +796,8 @@ pub fn trans_switch(bcx: Block, r: &Repr, scrutinee: ValueRef)
     -> (_match::BranchKind, Option<ValueRef>) {
     match *r {
         CEnum(..) | General(..) |
-        RawNullablePointer { .. } | StructWrappedNullablePointer { .. } => {
+        RawNullablePointer { .. } | StructWrappedNullablePointer { .. } |
+        NicheFilling { .. } => {
             (_match::Switch, Some(trans_get_discr(bcx, r, scrutinee, None)))
         }
         Univariant(..) => {
@@ -616,6 +837,11 @@ pub fn trans_get_discr(bcx: Block, r: &Repr, scrutinee: ValueRef, cast_to: Optio
             val = struct_wrapped_nullable_bitdiscr(bcx, nndiscr, ptrfield, scrutinee);
             signed = false;
         }
+        NicheFilling { dataful_discr, ref niche, niche_start, ref nichefields, .. } => {
+            val = niche_filling_discr(bcx, dataful_discr, niche, niche_start,
+                                      nichefields.len(), scrutinee);
+            signed = false;
+        }
     }
     match cast_to {
         None => val,
@@ -634,6 +860,18 @@ fn struct_wrapped_nullable_bitdiscr(bcx: Block, nndiscr: Disr, ptrfield: Pointer
     ICmp(bcx, cmp, llptr, C_null(val_ty(llptr)))
 }
 
+fn niche_filling_discr(bcx: Block, dataful_discr: Disr, niche: &Niche, niche_start: u64,
+                       ncases: uint, scrutinee: ValueRef) -> ValueRef {
+    let llty = ll_inttype(bcx.ccx(), niche.ity);
+    let path = (vec!(0)).append(niche.path.as_slice());
+    let llniche = Load(bcx, GEPi(bcx, scrutinee, path.as_slice()));
+    // Any value outside of `niche_start .. niche_start + ncases` belongs to
+    // the dataful case, as does the one for its own discriminant.
+    let rel = Sub(bcx, llniche, C_integral(llty, niche_start, false));
+    let in_niche = ICmp(bcx, IntULT, rel, C_integral(llty, ncases as u64, false));
+    Select(bcx, in_niche, rel, C_integral(llty, dataful_discr, false))
+}
+
 /// Helper for cases where the discriminant is simply loaded.
 fn load_discr(bcx: Block, ity: IntType, ptr: ValueRef, min: Disr, max: Disr)
     -> ValueRef {
@@ -683,6 +921,10 @@ pub fn trans_case<'blk, 'tcx>(bcx: Block<'blk, 'tcx>, r: &Repr, discr: Disr)
             assert!(discr == 0 || discr == 1);
             _match::SingleResult(Result::new(bcx, C_bool(bcx.ccx(), discr != 0)))
         }
+        NicheFilling { ref niche, .. } => {
+            _match::SingleResult(Result::new(bcx, C_integral(ll_inttype(bcx.ccx(), niche.ity),
+                                                              discr as u64, false)))
+        }
     }
 }
 
@@ -733,6 +975,14 @@ pub fn trans_set_discr(bcx: Block, r: &Repr, val: ValueRef, discr: Disr) {
                 Store(bcx, C_null(llptrty), llptrptr)
             }
         }
+        NicheFilling { dataful_discr, ref niche, niche_start, .. } => {
+            if discr != dataful_discr {
+                let llniche = C_integral(ll_inttype(bcx.ccx(), niche.ity),
+                                         niche_start + discr as u64, false);
+                let path = (vec!(0)).append(niche.path.as_slice());
+                Store(bcx, llniche, GEPi(bcx, val, path.as_slice()))
+            }
+        }
     }
 }
 
@@ -764,6 +1014,7 @@ pub fn num_args(r: &Repr, discr: Disr) -> uint {
                                        nullfields: ref nullfields, .. } => {
             if discr == nndiscr { nonnull.fields.len() } else { nullfields.len() }
         }
+        NicheFilling { ref nichefields, .. } => nichefields.get(discr as uint).len()
     }
 }
 
@@ -794,6 +1045,13 @@ pub fn trans_field_ptr(bcx: Block, r: &Repr, val: ValueRef, discr: Disr,
             // the value that's "reasonable" in case of pointer comparison.
             PointerCast(bcx, val, ty.ptr_to())
         }
+        NicheFilling { dataful_discr, ref nichefields, .. } if discr != dataful_discr => {
+            let ty = type_of::type_of(bcx.ccx(), *nichefields.get(discr as uint).get(ix));
+            assert_eq!(machine::llsize_of_alloc(bcx.ccx(), ty), 0);
+            // The contents of memory at this pointer can't matter, but use
+            // the value that's "reasonable" in case of pointer comparison.
+            PointerCast(bcx, val, ty.ptr_to())
+        }
         RawNullablePointer { nndiscr, nnty, .. } => {
             assert_eq!(ix, 0);
             assert_eq!(discr, nndiscr);
@@ -804,6 +1062,9 @@ pub fn trans_field_ptr(bcx: Block, r: &Repr, val: ValueRef, discr: Disr,
             assert_eq!(discr, nndiscr);
             struct_field_ptr(bcx, nonnull, val, ix, false)
         }
+        NicheFilling { ref dataful, .. } => {
+            struct_field_ptr(bcx, dataful, val, ix, false)
+        }
     }
 }
 
@@ -811,14 +1072,16 @@ pub fn struct_field_ptr(bcx: Block, st: &Struct, val: ValueRef,
                         ix: uint, needs_cast: bool) -> ValueRef {
     let val = if needs_cast {
         let ccx = bcx.ccx();
-        let fields = st.fields.iter().map(|&ty| type_of::type_of(ccx, ty)).collect::<Vec<_>>();
+        let fields = st.fields_in_memory_order().iter().map(|&ty| {
+            type_of::type_of(ccx, ty)
+        }).collect::<Vec<_>>();
         let real_ty = Type::struct_(ccx, fields.as_slice(), st.packed);
         PointerCast(bcx, val, real_ty.ptr_to())
     } else {
         val
     };
 
-    GEPi(bcx, val, [0, ix])
+    GEPi(bcx, val, [0, *st.memory_index.get(ix)])
 }
 
 pub fn fold_variants<'blk, 'tcx>(
@@ -846,7 +1109,7 @@ pub fn fold_variants<'blk, 'tcx>(
                 let rhs_val = C_integral(ll_inttype(ccx, ity), discr as u64, true);
                 AddCase(llswitch, rhs_val, variant_cx.llbb);
 
-                let fields = case.fields.iter().map(|&ty|
+                let fields = case.fields_in_memory_order().iter().map(|&ty|
                     type_of::type_of(bcx.ccx(), ty)).collect::<Vec<_>>();
                 let real_ty = Type::struct_(ccx, fields.as_slice(), case.packed);
                 let variant_value = PointerCast(variant_cx, value, real_ty.ptr_to());
@@ -960,17 +1223,29 @@ pub fn trans_const(ccx: &CrateContext, r: &Repr, discr: Disr,
                          false)
             }
         }
+        NicheFilling { ref dataful, dataful_discr, ref niche, niche_start, .. } => {
+            if discr == dataful_discr {
+                C_struct(ccx, build_const_struct(ccx, dataful, vals).as_slice(), false)
+            } else {
+                let llty = Type::struct_(ccx, struct_llfields(ccx, dataful, true, false).as_slice(),
+                                         false);
+                let llniche = C_integral(ll_inttype(ccx, niche.ity),
+                                         niche_start + discr as u64, false);
+                let path = niche.path.iter().map(|&i| i as c_uint).collect::<Vec<_>>();
+                const_insert_elt(ccx, C_null(llty), llniche, path.as_slice())
+            }
+        }
     }
 }
 
 /**
- * Compute struct field offsets relative to struct begin.
+ * Compute struct field offsets relative to struct begin, in memory order.
  */
 fn compute_struct_field_offsets(ccx: &CrateContext, st: &Struct) -> Vec<u64> {
     let mut offsets = vec!();
 
     let mut offset = 0;
-    for &ty in st.fields.iter() {
+    for &ty in st.fields_in_memory_order().iter() {
         let llty = type_of::sizing_type_of(ccx, ty);
         if !st.packed {
             let type_align = type_of::align_of(ccx, ty) as u64;
@@ -997,12 +1272,16 @@ fn build_const_struct(ccx: &CrateContext, st: &Struct, vals: &[ValueRef])
     -> Vec<ValueRef> {
     assert_eq!(vals.len(), st.fields.len());
 
+    let mut ordered_vals = Vec::from_slice(vals);
+    for (i, &val) in vals.iter().enumerate() {
+        *ordered_vals.get_mut(*st.memory_index.get(i)) = val;
+    }
     let target_offsets = compute_struct_field_offsets(ccx, st);
 
     // offset of current value
     let mut offset = 0;
     let mut cfields = Vec::new();
-    for (&val, &target_offset) in vals.iter().zip(target_offsets.iter()) {
+    for (&val, &target_offset) in ordered_vals.iter().zip(target_offsets.iter()) {
         if !st.packed {
             let val_align = machine::llalign_of_min(ccx, val_ty(val))
                 /*bad*/as u64;
@@ -1070,6 +1349,13 @@ pub fn const_get_discrim(ccx: &CrateContext, r: &Repr, val: ValueRef)
                 nndiscr
             }
         }
+        NicheFilling { dataful_discr, ref niche, niche_start, ref nichefields, .. } => {
+            let llniche = niche.path.iter().fold(val, |v, &ix| {
+                const_struct_field(ccx, v, ix, None)
+            });
+            let rel = (const_to_uint(llniche) - niche_start) & int_mask(ccx, niche.ity);
+            if rel < nichefields.len() as u64 { rel as Disr } else { dataful_discr }
+        }
     }
 }
 
@@ -1084,13 +1370,16 @@ pub fn const_get_field(ccx: &CrateContext, r: &Repr, val: ValueRef,
                        _discr: Disr, ix: uint) -> ValueRef {
     match *r {
         CEnum(..) => ccx.sess().bug("element access in C-like enum const"),
-        Univariant(..) => const_struct_field(ccx, val, ix, None),
+        Univariant(ref st, _) => const_struct_field(ccx, val, *st.memory_index.get(ix), None),
         General(..) => const_struct_field(ccx, val, ix + 1, None),
         RawNullablePointer { .. } => {
             assert_eq!(ix, 0);
             val
         }
-        StructWrappedNullablePointer{ .. } => const_struct_field(ccx, val, ix, None)
+        StructWrappedNullablePointer{ .. } => const_struct_field(ccx, val, ix, None),
+        NicheFilling { ref dataful, .. } => {
+            const_struct_field(ccx, val, *dataful.memory_index.get(ix), None)
+        }
     }
 }
 
@@ -29,7 +29,7 @@ use back::link::{mangle_exported_name};
 use back::{link, abi};
 use driver::config;
 use driver::config::{NoDebugInfo, FullDebugInfo};
-use driver::driver::{CrateAnalysis, CrateTranslation, ModuleTranslation};
+use driver::driver::{CrateAnalysis, CrateTranslation, ModuleTranslation, OutputFilenames};
 use driver::session::Session;
 use lint;
 use llvm::{BasicBlockRef, ModuleRef, ValueRef, Vector, get_param};
@@ -204,10 +204,16 @@ pub fn decl_fn(ccx: &CrateContext, name: &str, cc: llvm::CallConv,
     // Function addresses in Rust are never significant, allowing functions to be merged.
     llvm::SetUnnamedAddr(llfn, true);
 
-    if ccx.is_split_stack_supported() {
+    if ccx.uses_split_stack() {
         set_split_stack(llfn);
     }
 
+    // The sanitizer passes only instrument functions that ask for it.
+    match ccx.sess().opts.sanitizer {
+        Some(sanitizer) => set_sanitize(llfn, sanitizer),
+        None => {}
+    }
+
     llfn
 }
 
@@ -460,6 +466,17 @@ pub fn set_llvm_fn_attrs(attrs: &[ast::Attribute], llfn: ValueRef) {
     }
 }
 
+pub fn set_sanitize(f: ValueRef, sanitizer: config::Sanitizer) {
+    let attr = match sanitizer {
+        config::AddressSanitizer => llvm::SanitizeAddressAttribute,
+        config::ThreadSanitizer => llvm::SanitizeThreadAttribute,
+        config::MemorySanitizer => llvm::SanitizeMemoryAttribute,
+    };
+    unsafe {
+        llvm::LLVMAddFunctionAttribute(f, llvm::FunctionIndex as c_uint, attr as uint64_t)
+    }
+}
+
 pub fn set_always_inline(f: ValueRef) {
     llvm::SetFunctionAttribute(f, llvm::AlwaysInlineAttribute)
 }
@@ -476,6 +493,69 @@ pub fn unset_split_stack(f: ValueRef) {
     })
 }
 
+// The smallest page size of any supported target, and hence the largest stride
+// at which a frame can be probed without stepping over a guard page.
+static STACK_PROBE_INTERVAL: u64 = 4096;
+
+/// Touches every page of the function's frame, highest address first, so that
+/// a frame larger than the guard page at the end of the stack faults on the
+/// guard page rather than silently reaching past it, as `__chkstk` does.
+///
+/// This is called from `finish_fn`, once the whole body has been translated,
+/// and rests on a few assumptions:
+///
+/// * The frame is sized from the allocas in the entry block only. Trans puts
+///   every alloca there, at `alloca_insert_pt`, however deeply nested the
+///   expression that needs it, so a large array in a loop or a branch still
+///   ends up in the count. Allocas with a non-constant count are not counted,
+///   and neither are the ones LLVM's inliner adds to the frame later on; an
+///   inlined callee's own probes only cover its own slots.
+/// * Spill slots, saved registers and outgoing arguments come on top of the
+///   allocas, so the probes reach a page further up, and frames whose allocas
+///   take up at most half a page (2048 bytes) are assumed to fit in one page
+///   and are not probed at all.
+/// * The probes are volatile loads relative to the stack pointer, which at
+///   that point is the bottom of the frame, emitted after the allocas so that
+///   they run in the function's prologue. LLVM only keeps volatile accesses
+///   in order with respect to each other, so nothing stops it from moving an
+///   ordinary store to the frame ahead of them; in practice no store to a
+///   slot precedes the probes in the entry block, but that is not guaranteed.
+pub fn probe_stack_frame(fcx: &FunctionContext) {
+    let ccx = fcx.ccx;
+    if !ccx.uses_stack_probes() {
+        return;
+    }
+
+    let mut size = 0;
+    unsafe {
+        let entry = llvm::LLVMGetEntryBasicBlock(fcx.llfn);
+        let mut insn = llvm::LLVMGetFirstInstruction(entry);
+        while insn.is_not_null() {
+            if llvm::LLVMIsAAllocaInst(insn).is_not_null() {
+                let ty = Type::from_ref(llvm::LLVMGetElementType(val_ty(insn).to_ref()));
+                let count = llvm::LLVMGetOperand(insn, 0);
+                if llvm::LLVMIsConstant(count) == llvm::True {
+                    size += machine::llsize_of_alloc(ccx, ty) *
+                            llvm::LLVMConstIntGetZExtValue(count);
+                }
+            }
+            insn = llvm::LLVMGetNextInstruction(insn);
+        }
+    }
+    if size <= STACK_PROBE_INTERVAL / 2 {
+        return;
+    }
+
+    let b = ccx.builder();
+    b.position_before(fcx.alloca_insert_pt.get().unwrap());
+    let sp = b.call(ccx.get_intrinsic(&("llvm.stacksave")), [], None);
+    let mut offset = size + STACK_PROBE_INTERVAL;
+    while offset > 0 {
+        offset = if offset > STACK_PROBE_INTERVAL { offset - STACK_PROBE_INTERVAL } else { 0 };
+        b.volatile_load(b.gep(sp, [C_u64(ccx, offset)]));
+    }
+}
+
 // Double-check that we never ask LLVM to declare the same symbol twice. It
 // silently mangles such symbols, breaking our linkage model.
 pub fn note_unique_llvm_symbol(ccx: &CrateContext, sym: String) {
@@ -1695,6 +1775,7 @@ pub fn finish_fn<'blk, 'tcx>(fcx: &'blk FunctionContext<'blk, 'tcx>,
     };
     build_return_block(fcx, ret_cx, substd_retty);
     debuginfo::clear_source_location(fcx);
+    probe_stack_frame(fcx);
     fcx.cleanup();
 }
 
@@ -2573,6 +2654,8 @@ pub fn create_entry_wrapper(ccx: &CrateContext,
         unsafe {
             llvm::LLVMPositionBuilderAtEnd(bld, llbb);
 
+            debuginfo::insert_reference_to_gdb_debug_scripts_section_global(ccx, bld);
+
             let (start_fn, args) = if use_start_lang_item {
                 let start_def_id = match ccx.tcx().lang_items.require(StartFnLangItem) {
                     Ok(id) => id,
@@ -3033,7 +3116,8 @@ fn internalize_symbols(cx: &SharedCrateContext, reachable: &HashSet<String>) {
     }
 }
 
-pub fn trans_crate<'tcx>(analysis: CrateAnalysis<'tcx>)
+pub fn trans_crate<'tcx>(analysis: CrateAnalysis<'tcx>,
+                         outputs: &OutputFilenames)
                          -> (ty::ctxt<'tcx>, CrateTranslation) {
     let CrateAnalysis { ty_cx: tcx, exp_map2, reachable, name, .. } = analysis;
     let krate = tcx.map.krate();
@@ -3065,7 +3149,8 @@ pub fn trans_crate<'tcx>(analysis: CrateAnalysis<'tcx>)
                                              exp_map2,
                                              Sha256::new(),
                                              link_meta.clone(),
-                                             reachable);
+                                             reachable,
+                                             outputs.clone());
 
     {
         let ccx = shared_ccx.get_ccx(0);
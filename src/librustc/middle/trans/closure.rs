@@ -282,13 +282,17 @@ fn load_environment<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
 
         bcx.fcx.llupvars.borrow_mut().insert(def_id.node, upvarptr);
         for &env_pointer_alloca in env_pointer_alloca.iter() {
+            let captured_by_ref = match store {
+                ty::RegionTraitStore(..) => true,
+                ty::UniqTraitStore => false
+            };
             debuginfo::create_captured_var_metadata(
                 bcx,
                 def_id.node,
                 cdata_ty,
                 env_pointer_alloca,
                 i,
-                store,
+                captured_by_ref,
                 freevar.span);
         }
 
@@ -326,6 +330,17 @@ fn load_unboxed_closure_environment<'blk, 'tcx>(
         bcx.fcx.llenv.unwrap()
     };
 
+    // As for boxed closures, store the pointer to the environment in an
+    // alloca for the captured variables' debug info.
+    let env_type = ty::mk_unboxed_closure(bcx.tcx(), closure_id, ty::ReStatic);
+    let env_pointer_alloca = if bcx.sess().opts.debuginfo == FullDebugInfo {
+        let alloc = alloc_ty(bcx, ty::mk_mut_ptr(bcx.tcx(), env_type), "__debuginfo_env_ptr");
+        Store(bcx, llenv, alloc);
+        Some(alloc)
+    } else {
+        None
+    };
+
     for (i, freevar) in freevars.iter().enumerate() {
         let mut upvar_ptr = GEPi(bcx, llenv, [0, i]);
         if freevar_mode == ast::CaptureByRef {
@@ -334,6 +349,17 @@ fn load_unboxed_closure_environment<'blk, 'tcx>(
         let def_id = freevar.def.def_id();
         bcx.fcx.llupvars.borrow_mut().insert(def_id.node, upvar_ptr);
 
+        for &env_pointer_alloca in env_pointer_alloca.iter() {
+            debuginfo::create_captured_var_metadata(
+                bcx,
+                def_id.node,
+                env_type,
+                env_pointer_alloca,
+                i,
+                freevar_mode == ast::CaptureByRef,
+                freevar.span);
+        }
+
         if kind == ty::FnOnceUnboxedClosureKind && freevar_mode == ast::CaptureByValue {
             bcx.fcx.schedule_drop_mem(arg_scope_id,
                                       upvar_ptr,
@@ -688,6 +688,12 @@ pub fn C_array(ty: Type, elts: &[ValueRef]) -> ValueRef {
     }
 }
 
+pub fn C_vector(elts: &[ValueRef]) -> ValueRef {
+    unsafe {
+        return llvm::LLVMConstVector(elts.as_ptr(), elts.len() as c_uint);
+    }
+}
+
 pub fn C_bytes(cx: &CrateContext, bytes: &[u8]) -> ValueRef {
     C_bytes_in_context(cx.llcx(), bytes)
 }
@@ -711,6 +717,19 @@ pub fn const_get_elt(cx: &CrateContext, v: ValueRef, us: &[c_uint])
     }
 }
 
+pub fn const_insert_elt(cx: &CrateContext, agg: ValueRef, v: ValueRef, us: &[c_uint])
+                        -> ValueRef {
+    unsafe {
+        let r = llvm::LLVMConstInsertValue(agg, v, us.as_ptr(), us.len() as c_uint);
+
+        debug!("const_insert_elt(agg={}, v={}, us={:?}, r={})",
+               cx.tn().val_to_string(agg), cx.tn().val_to_string(v), us,
+               cx.tn().val_to_string(r));
+
+        return r;
+    }
+}
+
 pub fn is_const(v: ValueRef) -> bool {
     unsafe {
         llvm::LLVMIsConstant(v) == True
@@ -688,6 +688,18 @@ fn const_expr_unadjusted(cx: &CrateContext, e: &ast::Expr,
                 None => (C_nil(cx), true)
             }
           }
+          ast::ExprIf(ref cond, ref then, ref els) => {
+            let branch = match const_eval::eval_const_expr(cx.tcx(), &**cond) {
+                const_eval::const_bool(true) => then.expr.as_ref(),
+                const_eval::const_bool(false) => els.as_ref(),
+                _ => cx.sess().span_bug(cond.span,
+                                        "non-boolean condition in constant `if`")
+            };
+            match branch {
+                Some(expr) => first_two(const_expr(cx, &**expr, is_local)),
+                None => (C_nil(cx), true)
+            }
+          }
           _ => cx.sess().span_bug(e.span,
                   "bad constant expression type in consts::const_expr")
         };
@@ -8,7 +8,9 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use driver::config::NoDebugInfo;
+use back::write;
+use driver::config::{NoDebugInfo, SplitStack, StackProbes};
+use driver::driver::OutputFilenames;
 use driver::session::Session;
 use llvm;
 use llvm::{ContextRef, ModuleRef, ValueRef, BuilderRef};
@@ -77,6 +79,10 @@ pub struct SharedCrateContext<'tcx> {
     available_monomorphizations: RefCell<HashSet<String>>,
     available_drop_glues: RefCell<HashMap<ty::t, String>>,
     available_visit_glues: RefCell<HashMap<ty::t, String>>,
+    /// Cache of the answers of `write::target_has_feature`.
+    target_features: RefCell<HashMap<String, bool>>,
+
+    output_filenames: OutputFilenames,
 }
 
 /// The local portion of a `CrateContext`.  There is one `LocalCrateContext`
@@ -245,7 +251,8 @@ impl<'tcx> SharedCrateContext<'tcx> {
                emap2: resolve::ExportMap2,
                symbol_hasher: Sha256,
                link_meta: LinkMeta,
-               reachable: NodeSet)
+               reachable: NodeSet,
+               output_filenames: OutputFilenames)
                -> SharedCrateContext<'tcx> {
         let (metadata_llcx, metadata_llmod) = unsafe {
             create_context_and_module(&tcx.sess, "metadata")
@@ -278,6 +285,8 @@ impl<'tcx> SharedCrateContext<'tcx> {
             available_monomorphizations: RefCell::new(HashSet::new()),
             available_drop_glues: RefCell::new(HashMap::new()),
             available_visit_glues: RefCell::new(HashMap::new()),
+            target_features: RefCell::new(HashMap::new()),
+            output_filenames: output_filenames,
         };
 
         for i in range(0, local_count) {
@@ -549,6 +558,17 @@ impl<'b, 'tcx> CrateContext<'b, 'tcx> {
         (cfg.os != abi::OsiOS || cfg.arch != abi::Arm) && cfg.os != abi::OsWindows
     }
 
+    pub fn uses_split_stack(&self) -> bool {
+        self.sess().stack_check() == SplitStack && self.is_split_stack_supported()
+    }
+
+    // LLVM already probes frames larger than a page on Windows (via
+    // `__chkstk`), so we only need to emit our own probes elsewhere.
+    pub fn uses_stack_probes(&self) -> bool {
+        self.sess().stack_check() == StackProbes &&
+            self.sess().targ_cfg.os != abi::OsWindows
+    }
+
 
     pub fn llmod(&self) -> ModuleRef {
         self.local.llmod
@@ -590,6 +610,12 @@ impl<'b, 'tcx> CrateContext<'b, 'tcx> {
         &self.shared.link_meta
     }
 
+    /// The .dwo file that the debuginfo of this codegen unit goes into with
+    /// `-C debuginfo=split`.
+    pub fn dwo_path(&self) -> Path {
+        write::dwo_path(&self.shared.output_filenames, format!("{}", self.index).as_slice())
+    }
+
     pub fn drop_glues<'a>(&'a self) -> &'a RefCell<HashMap<ty::t, ValueRef>> {
         &self.local.drop_glues
     }
@@ -691,6 +717,19 @@ impl<'b, 'tcx> CrateContext<'b, 'tcx> {
         &self.shared.available_visit_glues
     }
 
+    /// Whether code for the target may use the LLVM subtarget feature
+    /// `feature`, going by `-C target-cpu` and `-C target-feature`.
+    pub fn has_target_feature(&self, feature: &str) -> bool {
+        let mut features = self.shared.target_features.borrow_mut();
+        match features.find_equiv(&feature) {
+            Some(&has) => return has,
+            None => {}
+        }
+        let has = write::target_has_feature(self.sess(), feature);
+        features.insert(feature.to_string(), has);
+        has
+    }
+
     pub fn int_type(&self) -> Type {
         self.local.int_type
     }
@@ -766,6 +805,7 @@ fn declare_intrinsic(ccx: &CrateContext, key: & &'static str) -> Option<ValueRef
     ifn!("llvm.trap" fn() -> void);
     ifn!("llvm.debugtrap" fn() -> void);
     ifn!("llvm.frameaddress" fn(t_i32) -> i8p);
+    ifn!("llvm.stacksave" fn() -> i8p);
 
     ifn!("llvm.powi.f32" fn(t_f32, t_i32) -> t_f32);
     ifn!("llvm.powi.f64" fn(t_f64, t_i32) -> t_f64);
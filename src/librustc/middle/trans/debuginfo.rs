@@ -244,6 +244,10 @@ static UNKNOWN_SCOPE_METADATA: DIScope = (0 as DIScope);
 static FLAGS_NONE: c_uint = 0;
 static FLAGS_ARTIFICAL: c_uint = llvm::debuginfo::FlagArtificial as c_uint;
 
+// The name of the discriminant field at the start of each variant of an enum.
+// Pretty printers rely on it.
+static ENUM_DISCRIMINANT_FIELD_NAME: &'static str = "RUST$ENUM$DISR";
+
 //=-----------------------------------------------------------------------------
 //  Public Interface of debuginfo module
 //=-----------------------------------------------------------------------------
@@ -461,12 +465,15 @@ impl TypeMap {
                                                         closure_ty.clone(),
                                                         &mut unique_type_id);
             },
-            ty::ty_unboxed_closure(ref def_id, _) => {
-                let closure_ty = cx.tcx().unboxed_closures.borrow()
-                                   .find(def_id).unwrap().closure_type.clone();
-                self.get_unique_type_id_of_closure_type(cx,
-                                                        closure_ty,
-                                                        &mut unique_type_id);
+            ty::ty_unboxed_closure(def_id, _) => {
+                // Unboxed closures are described by their environment, which
+                // differs between closures of the same signature.
+                unique_type_id.push_str("closure ");
+                from_def_id_and_substs(self,
+                                       cx,
+                                       def_id,
+                                       &subst::Substs::empty(),
+                                       &mut unique_type_id);
             },
             _ => {
                 cx.sess().bug(format!("get_unique_type_id_of_type() - unexpected type: {}, {:?}",
@@ -751,6 +758,79 @@ pub fn finalize(cx: &CrateContext) {
             |s| llvm::LLVMRustAddModuleFlag(cx.llmod(), s,
                                             llvm::LLVMRustDebugMetadataVersion));
     };
+
+    if needs_gdb_debug_scripts_section(cx) {
+        get_or_insert_gdb_debug_scripts_section_global(cx);
+    }
+}
+
+/// Inserts a side-effect free instruction sequence that makes sure that the
+/// .debug_gdb_scripts global is referenced, so it isn't removed by the linker.
+///
+/// Must be called with the builder positioned inside of the entry function.
+pub fn insert_reference_to_gdb_debug_scripts_section_global(cx: &CrateContext,
+                                                            builder: llvm::BuilderRef) {
+    if !needs_gdb_debug_scripts_section(cx) {
+        return;
+    }
+
+    let gdb_debug_scripts_section_global = get_or_insert_gdb_debug_scripts_section_global(cx);
+    unsafe {
+        let indices = [C_i32(cx, 0), C_i32(cx, 0)];
+        let element = llvm::LLVMConstInBoundsGEP(gdb_debug_scripts_section_global,
+                                                 indices.as_ptr(),
+                                                 indices.len() as c_uint);
+        let volatile_load_instruction = "".with_c_str(|name| {
+            llvm::LLVMBuildLoad(builder, element, name)
+        });
+        llvm::LLVMSetVolatile(volatile_load_instruction, llvm::True);
+    }
+}
+
+/// Allocates the global variable responsible for the .debug_gdb_scripts
+/// binary section. GDB reads the names of Python scripts to load for the
+/// executable from this section, which is how the Rust pretty printers get
+/// loaded automatically.
+fn get_or_insert_gdb_debug_scripts_section_global(cx: &CrateContext) -> ValueRef {
+    let section_var_name = "__rustc_debug_gdb_scripts_section__";
+
+    let section_var = section_var_name.with_c_str(|name| {
+        unsafe { llvm::LLVMGetNamedGlobal(cx.llmod(), name) }
+    });
+
+    if section_var.is_not_null() {
+        return section_var;
+    }
+
+    // The entry consists of a type byte (1 means "the name of a Python
+    // script") followed by the NUL terminated script name.
+    let section_contents = b"\x01gdb_load_rust_pretty_printers.py\0";
+
+    unsafe {
+        let llvm_type = Type::array(&Type::i8(cx), section_contents.len() as u64);
+        let section_var = section_var_name.with_c_str(|name| {
+            llvm::LLVMAddGlobal(cx.llmod(), llvm_type.to_ref(), name)
+        });
+        ".debug_gdb_scripts".with_c_str(|section_name| {
+            llvm::LLVMSetSection(section_var, section_name)
+        });
+        llvm::LLVMSetInitializer(section_var, C_bytes(cx, section_contents));
+        llvm::LLVMSetGlobalConstant(section_var, llvm::True);
+        llvm::LLVMSetUnnamedAddr(section_var, llvm::True);
+        llvm::SetLinkage(section_var, llvm::LinkOnceODRLinkage);
+        // This should make sure that the whole section is not larger than
+        // the string it contains. Otherwise we get a warning from GDB.
+        llvm::LLVMSetAlignment(section_var, 1);
+        section_var
+    }
+}
+
+fn needs_gdb_debug_scripts_section(cx: &CrateContext) -> bool {
+    // Only ELF targets have a .debug_gdb_scripts section for GDB to read.
+    cx.dbg_cx().is_some() &&
+    cx.sess().targ_cfg.os != abi::OsMacos &&
+    cx.sess().targ_cfg.os != abi::OsiOS &&
+    cx.sess().targ_cfg.os != abi::OsWindows
 }
 
 /// Creates debug information for the given global variable.
@@ -865,13 +945,18 @@ pub fn create_local_var_metadata(bcx: Block, local: &ast::Local) {
 
 /// Creates debug information for a variable captured in a closure.
 ///
+/// `env_pointer` is an alloca holding a pointer to the closure's environment,
+/// of type `env_data_type`, in which the variable is field `env_index`.
+/// `captured_by_ref` says whether that field holds a pointer to the variable
+/// rather than the variable itself.
+///
 /// Adds the created metadata nodes directly to the crate's IR.
 pub fn create_captured_var_metadata(bcx: Block,
                                     node_id: ast::NodeId,
                                     env_data_type: ty::t,
                                     env_pointer: ValueRef,
                                     env_index: uint,
-                                    closure_store: ty::TraitStore,
+                                    captured_by_ref: bool,
                                     span: Span) {
     if fn_should_be_ignored(bcx.fcx) {
         return;
@@ -879,38 +964,7 @@ pub fn create_captured_var_metadata(bcx: Block,
 
     let cx = bcx.ccx();
 
-    let ast_item = cx.tcx().map.find(node_id);
-
-    let variable_ident = match ast_item {
-        None => {
-            cx.sess().span_bug(span, "debuginfo::create_captured_var_metadata: node not found");
-        }
-        Some(ast_map::NodeLocal(pat)) | Some(ast_map::NodeArg(pat)) => {
-            match pat.node {
-                ast::PatIdent(_, ref path1, _) => {
-                    path1.node
-                }
-                _ => {
-                    cx.sess()
-                      .span_bug(span,
-                                format!(
-                                "debuginfo::create_captured_var_metadata() - \
-                                 Captured var-id refers to unexpected \
-                                 ast_map variant: {:?}",
-                                 ast_item).as_slice());
-                }
-            }
-        }
-        _ => {
-            cx.sess()
-              .span_bug(span,
-                        format!("debuginfo::create_captured_var_metadata() - \
-                                 Captured var-id refers to unexpected \
-                                 ast_map variant: {:?}",
-                                ast_item).as_slice());
-        }
-    };
-
+    let variable_ident = captured_var_ident(cx, node_id, span);
     let variable_type = node_id_type(bcx, node_id);
     let scope_metadata = bcx.fcx.debug_context.get_ref(cx, span).fn_metadata;
 
@@ -926,13 +980,10 @@ pub fn create_captured_var_metadata(bcx: Block,
          llvm::LLVMDIBuilderCreateOpDeref(Type::i64(cx).to_ref())]
     };
 
-    let address_op_count = match closure_store {
-        ty::RegionTraitStore(..) => {
-            address_operations.len()
-        }
-        ty::UniqTraitStore => {
-            address_operations.len() - 1
-        }
+    let address_op_count = if captured_by_ref {
+        address_operations.len()
+    } else {
+        address_operations.len() - 1
     };
 
     let variable_access = IndirectVariable {
@@ -949,6 +1000,42 @@ pub fn create_captured_var_metadata(bcx: Block,
                   span);
 }
 
+// Returns the name of the local variable or argument with the given id, as
+// captured by a closure.
+fn captured_var_ident(cx: &CrateContext, node_id: ast::NodeId, span: Span) -> ast::Ident {
+    let ast_item = cx.tcx().map.find(node_id);
+
+    match ast_item {
+        None => {
+            cx.sess().span_bug(span, "debuginfo::captured_var_ident: node not found");
+        }
+        Some(ast_map::NodeLocal(pat)) | Some(ast_map::NodeArg(pat)) => {
+            match pat.node {
+                ast::PatIdent(_, ref path1, _) => {
+                    path1.node
+                }
+                _ => {
+                    cx.sess()
+                      .span_bug(span,
+                                format!(
+                                "debuginfo::captured_var_ident() - \
+                                 Captured var-id refers to unexpected \
+                                 ast_map variant: {:?}",
+                                 ast_item).as_slice());
+                }
+            }
+        }
+        _ => {
+            cx.sess()
+              .span_bug(span,
+                        format!("debuginfo::captured_var_ident() - \
+                                 Captured var-id refers to unexpected \
+                                 ast_map variant: {:?}",
+                                ast_item).as_slice());
+        }
+    }
+}
+
 /// Creates debug information for a local variable introduced in the head of a
 /// match-statement arm.
 ///
@@ -1546,11 +1633,19 @@ fn compile_unit_metadata(cx: &CrateContext) {
     let producer = format!("rustc version {}",
                            (option_env!("CFG_VERSION")).expect("CFG_VERSION"));
 
+    // With split DWARF, the skeleton compile unit left in the object file
+    // names the .dwo file that has the rest of the debuginfo.
+    let split_name = if cx.sess().split_debuginfo() {
+        cx.dwo_path().to_c_str()
+    } else {
+        "".to_c_str()
+    };
+
     let compile_unit_name = compile_unit_name.as_ptr();
     work_dir.as_vec().with_c_str(|work_dir| {
         producer.with_c_str(|producer| {
             "".with_c_str(|flags| {
-                "".with_c_str(|split_name| {
+                split_name.with_ref(|split_name| {
                     unsafe {
                         llvm::LLVMDIBuilderCreateCompileUnit(
                             debug_context(cx).builder,
@@ -1916,6 +2011,10 @@ impl RecursiveTypeDescription {
 // Creates MemberDescriptions for the fields of a struct
 struct StructMemberDescriptionFactory {
     fields: Vec<ty::field>,
+    // The struct's LLVM type and the positions of its fields in it, which
+    // need not be the same as in the declaration.
+    llvm_type: Type,
+    memory_index: Vec<uint>,
     is_simd: bool,
     span: Span,
 }
@@ -1943,7 +2042,10 @@ impl StructMemberDescriptionFactory {
                 assert!(field_size != 0xdeadbeef);
                 FixedMemberOffset { bytes: i * field_size }
             } else {
-                ComputedMemberOffset
+                let ix = *self.memory_index.get(i);
+                FixedMemberOffset {
+                    bytes: machine::llelement_offset(cx, self.llvm_type, ix) as uint
+                }
             };
 
             MemberDescription {
@@ -1977,8 +2079,12 @@ fn prepare_struct_metadata(cx: &CrateContext,
                                                   containing_scope);
 
     let fields = ty::struct_fields(cx.tcx(), def_id, substs);
+    let memory_index = match *adt::represent_type(cx, struct_type) {
+        adt::Univariant(ref st, _) => st.memory_index.clone(),
+        _ => cx.sess().span_bug(span, "struct not represented as a univariant")
+    };
 
-    create_and_register_recursive_type_forward_declaration(
+    let description = create_and_register_recursive_type_forward_declaration(
         cx,
         struct_type,
         unique_type_id,
@@ -1986,10 +2092,63 @@ fn prepare_struct_metadata(cx: &CrateContext,
         struct_llvm_type,
         StructMDF(StructMemberDescriptionFactory {
             fields: fields,
+            llvm_type: struct_llvm_type,
+            memory_index: memory_index,
             is_simd: ty::type_is_simd(cx.tcx(), struct_type),
             span: span,
         })
-    )
+    );
+
+    // Describe the type parameters too. Some of them, like the key and value
+    // types of a HashMap, don't show up in any field type and the pretty
+    // printers in src/etc need them to make sense of the struct's contents.
+    // This has to happen after the stub has been registered, as the
+    // parameters may refer back to the struct.
+    set_template_params_of_composite_type(cx,
+                                          struct_metadata_stub,
+                                          def_id,
+                                          substs,
+                                          containing_scope);
+    description
+}
+
+fn set_template_params_of_composite_type(cx: &CrateContext,
+                                         composite_type_metadata: DICompositeType,
+                                         def_id: ast::DefId,
+                                         substs: &subst::Substs,
+                                         containing_scope: DIScope) {
+    let actual_types = substs.types.get_slice(subst::TypeSpace);
+    if actual_types.is_empty() || cx.sess().opts.debuginfo != FullDebugInfo {
+        return;
+    }
+
+    let generics = ty::lookup_item_type(cx.tcx(), def_id).generics;
+    let type_params = generics.types.get_slice(subst::TypeSpace);
+
+    let template_params: Vec<DIDescriptor> = type_params.iter()
+        .zip(actual_types.iter())
+        .map(|(param_def, &actual_type)| {
+            let actual_type_metadata = type_metadata(cx, actual_type, codemap::DUMMY_SP);
+            token::get_ident(param_def.ident).get().with_c_str(|name| {
+                unsafe {
+                    llvm::LLVMDIBuilderCreateTemplateTypeParameter(
+                        DIB(cx),
+                        containing_scope,
+                        name,
+                        actual_type_metadata,
+                        ptr::null_mut(),
+                        0,
+                        0)
+                }
+            })
+        })
+        .collect();
+
+    unsafe {
+        let template_params = create_DIArray(DIB(cx), template_params.as_slice());
+        llvm::LLVMRustDICompositeTypeSetTemplateParams(composite_type_metadata,
+                                                       template_params);
+    }
 }
 
 
@@ -2053,7 +2212,10 @@ fn prepare_tuple_metadata(cx: &CrateContext,
 // structs in DWARF. This MemberDescriptionFactory provides the description for
 // the members of this union; so for every variant of the given enum, this factory
 // will produce one MemberDescription (all with no name and a fixed offset of
-// zero bytes).
+// zero bytes). Unless the enum is univariant or uses the nullable pointer
+// optimization, each variant struct starts with the discriminant, an
+// artificial field named `RUST$ENUM$DISR` whose type is the enumeration of the
+// variants, so debuggers print it as the variant's name.
 struct EnumMemberDescriptionFactory {
     enum_type: ty::t,
     type_rep: Rc<adt::Repr>,
@@ -2253,11 +2415,107 @@ impl EnumMemberDescriptionFactory {
                     }
                 ]
             },
+            adt::NicheFilling { ref dataful, dataful_discr, ref niche, niche_start,
+                                ref nichefields } => {
+                // Every variant gets a member, as for the general case, but
+                // the member for the dataful variant carries the information
+                // needed to tell them apart in its name: the value the niche
+                // holds for the first variant, and the path to the niche,
+                // as field indices from that member.
+                nichefields.iter().enumerate().map(|(i, fields)| {
+                    let variant_struct_def = adt::Struct {
+                        size: 0,
+                        align: 1,
+                        sized: true,
+                        packed: false,
+                        fields: fields.clone(),
+                        memory_index: range(0, fields.len()).collect()
+                    };
+                    let struct_def = if i as ty::Disr == dataful_discr {
+                        dataful
+                    } else {
+                        &variant_struct_def
+                    };
+                    let (variant_type_metadata,
+                         variant_llvm_type,
+                         member_desc_factory) =
+                        describe_enum_variant(cx,
+                                              self.enum_type,
+                                              struct_def,
+                                              &**self.variants.get(i),
+                                              NoDiscriminant,
+                                              self.containing_scope,
+                                              self.span);
+
+                    let member_descriptions = member_desc_factory
+                        .create_member_descriptions(cx);
+
+                    set_members_of_composite_type(cx,
+                                                  variant_type_metadata,
+                                                  variant_llvm_type,
+                                                  member_descriptions.as_slice());
+
+                    let name = if i as ty::Disr == dataful_discr {
+                        let path = niche_debug_path(cx, dataful, niche.path.as_slice(),
+                                                    self.span);
+                        let path = path.iter().map(|ix| ix.to_string()).collect::<Vec<_>>();
+                        format!("RUST$ENCODED$NICHE${}${}", niche_start, path.connect("$"))
+                    } else {
+                        "".to_string()
+                    };
+                    MemberDescription {
+                        name: name,
+                        llvm_type: variant_llvm_type,
+                        type_metadata: variant_type_metadata,
+                        offset: FixedMemberOffset { bytes: 0 },
+                        flags: FLAGS_NONE
+                    }
+                }).collect()
+            },
             adt::CEnum(..) => cx.sess().span_bug(self.span, "This should be unreachable.")
         }
     }
 }
 
+// Translates the GEP indices leading to a niche in `struct_def` into the
+// indices of the fields a debugger goes through to get there: fields are
+// described in declaration order, and an enum is described as a union with a
+// member for each variant.
+fn niche_debug_path(cx: &CrateContext,
+                    struct_def: &adt::Struct,
+                    path: &[uint],
+                    span: Span)
+                 -> Vec<uint> {
+    let field = struct_def.memory_index.iter().position(|&ix| ix == path[0]).unwrap();
+    let mut debug_path = vec![field];
+    let rest = path.slice_from(1);
+    if rest.len() == 0 {
+        // A bool, a char or a C-like enum.
+        return debug_path;
+    }
+
+    let field_type = *struct_def.fields.get(field);
+    match *adt::represent_type(cx, field_type) {
+        adt::Univariant(ref inner, _) => {
+            match ty::get(field_type).sty {
+                ty::ty_enum(..) => debug_path.push(0),
+                _ => {}
+            }
+            debug_path.push_all(niche_debug_path(cx, inner, rest, span).as_slice());
+        }
+        adt::General(..) => {
+            // The discriminant is the first field of every variant.
+            debug_path.push_all([0, 0]);
+        }
+        adt::NicheFilling { ref dataful, dataful_discr, .. } => {
+            debug_path.push(dataful_discr as uint);
+            debug_path.push_all(niche_debug_path(cx, dataful, rest, span).as_slice());
+        }
+        _ => cx.sess().span_bug(span, "unexpected representation of a niche")
+    }
+    debug_path
+}
+
 // Creates MemberDescriptions for the fields of a single enum variant.
 struct VariantMemberDescriptionFactory {
     args: Vec<(String, ty::t)> ,
@@ -2339,9 +2597,11 @@ fn describe_enum_variant(cx: &CrateContext,
         None => variant_info.args.iter().map(|_| "".to_string()).collect()
     };
 
-    // If this is not a univariant enum, there is also the (unnamed) discriminant field.
+    // If this is not a univariant enum, there is also the discriminant field.
+    // It is artificial, but named so that debuggers and pretty printers can
+    // tell it apart from the variant's own (possibly unnamed) fields.
     match discriminant_info {
-        RegularDiscriminant(_) => arg_names.insert(0, "".to_string()),
+        RegularDiscriminant(_) => arg_names.insert(0, ENUM_DISCRIMINANT_FIELD_NAME.to_string()),
         _ => { /* do nothing */ }
     };
 
@@ -2445,6 +2705,7 @@ fn prepare_enum_metadata(cx: &CrateContext,
         },
         adt::RawNullablePointer { .. }           |
         adt::StructWrappedNullablePointer { .. } |
+        adt::NicheFilling { .. }                 |
         adt::Univariant(..)                      => None,
         adt::General(inttype, _, _) => Some(discriminant_type_metadata(inttype)),
     };
@@ -2790,9 +3051,9 @@ fn trait_pointer_metadata(cx: &CrateContext,
                           trait_object_type: Option<ty::t>,
                           unique_type_id: UniqueTypeId)
                        -> DIType {
-    // The implementation provided here is a stub. It makes sure that the trait
-    // type is assigned the correct name, size, namespace, and source location.
-    // But it does not describe the trait's methods.
+    // The trait's methods are not described. For an actual trait pointer the
+    // data and vtable pointers are; the vtable's symbol names the concrete
+    // type behind the pointer (see `create_vtable_metadata()`).
 
     let def_id = match ty::get(trait_type).sty {
         ty::ty_trait(box ty::TyTrait { def_id, .. }) => def_id,
@@ -2804,6 +3065,30 @@ fn trait_pointer_metadata(cx: &CrateContext,
         }
     };
 
+    let member_descriptions = match trait_object_type {
+        Some(_) => {
+            let data_ptr_type = ty::mk_mut_ptr(cx.tcx(), ty::mk_nil());
+            let vtable_ptr_type = ty::mk_imm_ptr(cx.tcx(), ty::mk_uint());
+            vec![
+                MemberDescription {
+                    name: "pointer".to_string(),
+                    llvm_type: Type::opaque_trait_data(cx).ptr_to(),
+                    type_metadata: type_metadata(cx, data_ptr_type, codemap::DUMMY_SP),
+                    offset: ComputedMemberOffset,
+                    flags: FLAGS_ARTIFICAL
+                },
+                MemberDescription {
+                    name: "vtable".to_string(),
+                    llvm_type: Type::vtable_ptr(cx),
+                    type_metadata: type_metadata(cx, vtable_ptr_type, codemap::DUMMY_SP),
+                    offset: ComputedMemberOffset,
+                    flags: FLAGS_ARTIFICAL
+                },
+            ]
+        }
+        None => vec![]
+    };
+
     let trait_object_type = trait_object_type.unwrap_or(trait_type);
     let trait_type_name =
         compute_debuginfo_type_name(cx, trait_object_type, false);
@@ -2816,12 +3101,147 @@ fn trait_pointer_metadata(cx: &CrateContext,
                             trait_llvm_type,
                             trait_type_name.as_slice(),
                             unique_type_id,
-                            [],
+                            member_descriptions.as_slice(),
                             containing_scope,
                             UNKNOWN_FILE_METADATA,
                             codemap::DUMMY_SP)
 }
 
+/// Describes an unboxed closure as a struct of the variables it captures,
+/// each named after the variable.
+fn unboxed_closure_metadata(cx: &CrateContext,
+                            closure_type: ty::t,
+                            closure_def_id: ast::DefId,
+                            unique_type_id: UniqueTypeId,
+                            span: Span)
+                         -> MetadataCreationResult {
+    let upvars = ty::unboxed_closure_upvars(cx.tcx(), closure_def_id);
+
+    let member_descriptions: Vec<MemberDescription> = upvars
+        .iter()
+        .map(|upvar| {
+            MemberDescription {
+                name: token::get_ident(captured_var_ident(cx,
+                                                          upvar.def.def_id().node,
+                                                          upvar.span)).get().to_string(),
+                llvm_type: type_of::type_of(cx, upvar.ty),
+                type_metadata: type_metadata(cx, upvar.ty, span),
+                offset: ComputedMemberOffset,
+                flags: FLAGS_NONE
+            }
+        })
+        .collect();
+
+    return_if_metadata_created_in_meantime!(cx, unique_type_id);
+
+    let closure_llvm_type = type_of::type_of(cx, closure_type);
+    let closure_type_name = compute_debuginfo_type_name(cx, closure_type, false);
+
+    MetadataCreationResult::new(
+        composite_type_metadata(cx,
+                                closure_llvm_type,
+                                closure_type_name.as_slice(),
+                                unique_type_id,
+                                member_descriptions.as_slice(),
+                                UNKNOWN_SCOPE_METADATA,
+                                UNKNOWN_FILE_METADATA,
+                                span),
+        false)
+}
+
+/// Creates debug information for the vtable of `concrete_type`'s
+/// implementation of a trait.
+///
+/// The vtable gets a symbol name of the form
+/// `vtable for <Concrete as Trait>`, so that a debugger shows the concrete type
+/// behind a trait object when printing its vtable pointer, e.g. with
+/// `info symbol obj.vtable` in GDB. It is also described as a static variable
+/// of a `vtable` type whose containing type is the concrete type.
+pub fn create_vtable_metadata(cx: &CrateContext,
+                              concrete_type: ty::t,
+                              trait_ref: &ty::TraitRef,
+                              vtable: ValueRef) {
+    if cx.dbg_cx().is_none() {
+        return;
+    }
+
+    let mut trait_substs = trait_ref.substs.clone().erase_regions();
+    trait_substs.types.pop(subst::SelfSpace);
+    let trait_type = ty::mk_trait(cx.tcx(),
+                                  trait_ref.def_id,
+                                  trait_substs,
+                                  ty::region_existential_bound(ty::ReStatic));
+
+    let symbol_name = format!("vtable for <{} as {}>",
+                              compute_debuginfo_type_name(cx, concrete_type, true),
+                              compute_debuginfo_type_name(cx, trait_type, true));
+    symbol_name.with_c_str(|name| unsafe { llvm::LLVMSetValueName(vtable, name) });
+
+    let concrete_type_metadata = type_metadata(cx, concrete_type, codemap::DUMMY_SP);
+
+    // Only the header common to all vtables is described: the drop glue, and
+    // the size and alignment of the concrete type. The methods follow it.
+    let vtable_llvm_type = val_ty(vtable).element_type();
+    let header_types = [ty::mk_nil_ptr(cx.tcx()), ty::mk_uint(), ty::mk_uint()];
+    let header_names = ["drop_glue", "size", "align"];
+    let member_descriptions: Vec<MemberDescription> = header_names
+        .iter()
+        .zip(header_types.iter())
+        .zip(vtable_llvm_type.field_types().into_iter())
+        .map(|((&name, &t), llvm_type)| {
+            MemberDescription {
+                name: name.to_string(),
+                llvm_type: llvm_type,
+                type_metadata: type_metadata(cx, t, codemap::DUMMY_SP),
+                offset: ComputedMemberOffset,
+                flags: FLAGS_NONE
+            }
+        })
+        .collect();
+
+    let (vtable_size, vtable_align) = size_and_align_of(cx, vtable_llvm_type);
+    let vtable_type_metadata = "vtable".with_c_str(|name| {
+        unsafe {
+            let empty_array = create_DIArray(DIB(cx), []);
+            llvm::LLVMDIBuilderCreateStructType(
+                DIB(cx),
+                UNKNOWN_SCOPE_METADATA,
+                name,
+                UNKNOWN_FILE_METADATA,
+                UNKNOWN_LINE_NUMBER,
+                bytes_to_bits(vtable_size),
+                bytes_to_bits(vtable_align),
+                0,
+                ptr::null_mut(),
+                empty_array,
+                0,
+                concrete_type_metadata,
+                ptr::null())
+        }
+    });
+    set_members_of_composite_type(cx,
+                                  vtable_type_metadata,
+                                  vtable_llvm_type,
+                                  member_descriptions.as_slice());
+
+    "vtable".with_c_str(|name| {
+        symbol_name.with_c_str(|linkage_name| {
+            unsafe {
+                llvm::LLVMDIBuilderCreateStaticVariable(DIB(cx),
+                                                        UNKNOWN_SCOPE_METADATA,
+                                                        name,
+                                                        linkage_name,
+                                                        UNKNOWN_FILE_METADATA,
+                                                        UNKNOWN_LINE_NUMBER,
+                                                        vtable_type_metadata,
+                                                        true,
+                                                        vtable,
+                                                        ptr::null_mut());
+            }
+        })
+    });
+}
+
 fn type_metadata(cx: &CrateContext,
                  t: ty::t,
                  usage_site_span: Span)
@@ -2920,10 +3340,8 @@ fn type_metadata(cx: &CrateContext,
         ty::ty_closure(ref closurety) => {
             subroutine_type_metadata(cx, unique_type_id, &closurety.sig, usage_site_span)
         }
-        ty::ty_unboxed_closure(ref def_id, _) => {
-            let sig = cx.tcx().unboxed_closures.borrow()
-                        .find(def_id).unwrap().closure_type.sig.clone();
-            subroutine_type_metadata(cx, unique_type_id, &sig, usage_site_span)
+        ty::ty_unboxed_closure(def_id, _) => {
+            unboxed_closure_metadata(cx, t, def_id, unique_type_id, usage_site_span)
         }
         ty::ty_struct(def_id, ref substs) => {
             prepare_struct_metadata(cx,
@@ -3488,6 +3906,11 @@ fn populate_scope_map(cx: &CrateContext,
                                               Found unexpanded if-let.");
             }
 
+            ast::ExprWhileLet(..) => {
+                cx.sess().span_bug(exp.span, "debuginfo::populate_scope_map() - \
+                                              Found unexpanded while-let.");
+            }
+
             ast::ExprWhile(ref cond_exp, ref loop_body, _) => {
                 walk_expr(cx, &**cond_exp, scope_stack, scope_map);
 
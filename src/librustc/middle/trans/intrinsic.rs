@@ -12,6 +12,7 @@
 
 use llvm;
 use llvm::{SequentiallyConsistent, Acquire, Release, AtomicXchg, ValueRef};
+use middle::const_eval;
 use middle::subst;
 use middle::subst::FnSpace;
 use middle::trans::base::*;
@@ -29,8 +30,9 @@ use middle::trans::machine;
 use middle::trans::machine::llsize_of;
 use middle::trans::type_::Type;
 use middle::ty;
-use syntax::abi::RustIntrinsic;
+use syntax::abi::{RustIntrinsic, X86, X86_64};
 use syntax::ast;
+use syntax::codemap::Span;
 use syntax::parse::token;
 use util::ppaux::ty_to_string;
 
@@ -204,6 +206,17 @@ pub fn trans_intrinsic_call<'blk, 'tcx>(mut bcx: Block<'blk, 'tcx>, node: ast::N
         }
     };
 
+    // The lanes picked by `simd_shuffle*` have to be known at compile time, so
+    // they are read off the argument expression instead of being translated.
+    let shuffle_indices = if name.get().starts_with("simd_shuffle") {
+        match args {
+            callee::ArgExprs(ref arg_exprs) => simd_shuffle_indices(tcx, &*arg_exprs[2]),
+            _ => ccx.sess().bug("expected expr as argument for simd_shuffle")
+        }
+    } else {
+        None
+    };
+
     // Push the arguments.
     let mut llargs = Vec::new();
     bcx = callee::trans_args(bcx,
@@ -534,6 +547,11 @@ pub fn trans_intrinsic_call<'blk, 'tcx>(mut bcx: Block<'blk, 'tcx>, node: ast::N
 
         }
 
+        (_, name) if name.starts_with("simd_") => {
+            trans_simd_intrinsic(bcx, name, &substs, call_info.span, llret_ty,
+                                 llargs.as_slice(), shuffle_indices)
+        }
+
         (_, _) => ccx.sess().span_bug(foreign_item.span, "unknown intrinsic")
     };
 
@@ -621,3 +639,199 @@ fn with_overflow_intrinsic(bcx: Block, name: &'static str, t: ty::t,
 
     ret
 }
+
+/// Reads the indices given to `simd_shuffle*`, which must be an array literal
+/// of constants.
+fn simd_shuffle_indices(tcx: &ty::ctxt, expr: &ast::Expr) -> Option<Vec<uint>> {
+    match expr.node {
+        ast::ExprVec(ref elems) => {
+            elems.iter().map(|elem| {
+                match const_eval::eval_const_expr_partial(tcx, &**elem) {
+                    Ok(const_eval::const_uint(i)) => Some(i as uint),
+                    Ok(const_eval::const_int(i)) if i >= 0 => Some(i as uint),
+                    _ => None
+                }
+            }).collect()
+        }
+        _ => None
+    }
+}
+
+/// Whether code for the target may use the x86 instructions of `feature`.
+/// SSE and SSE2 are part of the x86_64 baseline; otherwise the target machine
+/// works it out from the CPU given by `-C target-cpu` (so that
+/// `-C target-cpu=native` picks up what the host has) and `-C target-feature`.
+fn has_target_feature(ccx: &CrateContext, feature: &str) -> bool {
+    match ccx.sess().targ_cfg.arch {
+        X86_64 if feature == "sse" || feature == "sse2" => true,
+        X86 | X86_64 => ccx.has_target_feature(feature),
+        _ => false
+    }
+}
+
+/**
+ * Translates the `simd_*` intrinsics, whose arguments and results are
+ * `#[simd]` structs (and their elements).  Type checking only knows their
+ * type parameters, so whether the types they are used with fit together is
+ * checked here.
+ *
+ * Operations with a direct equivalent in LLVM IR are translated to that, and
+ * left for LLVM to lower for the target.  The others use the instruction for
+ * them if the target has it and are expanded to portable IR otherwise.
+ */
+fn trans_simd_intrinsic(bcx: Block, name: &str, substs: &subst::Substs, span: Span,
+                        llret_ty: Type, llargs: &[ValueRef],
+                        shuffle_indices: Option<Vec<uint>>) -> ValueRef {
+    let ccx = bcx.ccx();
+    let tcx = bcx.tcx();
+
+    macro_rules! require(
+        ($cond:expr, $($fmt:expr),+) => (
+            if !$cond {
+                tcx.sess.span_err(span, format!("invalid use of `{}` intrinsic: {}",
+                                                name, format!($($fmt),+)).as_slice());
+                return C_undef(llret_ty);
+            }
+        )
+    )
+
+    let in_ty = *substs.types.get(FnSpace, 0);
+    require!(ty::type_is_simd(tcx, in_ty),
+             "expected a SIMD type, found `{}`", ty_to_string(tcx, in_ty));
+    let in_elem = ty::simd_type(tcx, in_ty);
+    let in_len = ty::simd_size(tcx, in_ty);
+
+    // The result is a vector of the same length, if it is a vector at all.
+    let out_ty = if substs.types.len(FnSpace) > 1 {
+        *substs.types.get(FnSpace, 1)
+    } else {
+        in_ty
+    };
+    let is_vector_of = |ty: ty::t, len: uint| {
+        ty::type_is_simd(tcx, ty) && ty::simd_size(tcx, ty) == len
+    };
+
+    match name {
+        "simd_eq" | "simd_ne" | "simd_lt" | "simd_le" | "simd_gt" | "simd_ge" => {
+            require!(is_vector_of(out_ty, in_len) &&
+                     ty::type_is_integral(ty::simd_type(tcx, out_ty)),
+                     "expected a SIMD type of {} integers, found `{}`",
+                     in_len, ty_to_string(tcx, out_ty));
+            // Each lane of the result is all ones if the comparison holds
+            // for it, and zero otherwise.
+            let cmp = if ty::type_is_fp(in_elem) {
+                let op = match name {
+                    "simd_eq" => llvm::RealOEQ,
+                    "simd_ne" => llvm::RealUNE,
+                    "simd_lt" => llvm::RealOLT,
+                    "simd_le" => llvm::RealOLE,
+                    "simd_gt" => llvm::RealOGT,
+                    _ => llvm::RealOGE
+                };
+                FCmp(bcx, op, llargs[0], llargs[1])
+            } else {
+                let signed = ty::type_is_signed(in_elem);
+                let op = match name {
+                    "simd_eq" => llvm::IntEQ,
+                    "simd_ne" => llvm::IntNE,
+                    "simd_lt" => if signed { llvm::IntSLT } else { llvm::IntULT },
+                    "simd_le" => if signed { llvm::IntSLE } else { llvm::IntULE },
+                    "simd_gt" => if signed { llvm::IntSGT } else { llvm::IntUGT },
+                    _ => if signed { llvm::IntSGE } else { llvm::IntUGE }
+                };
+                ICmp(bcx, op, llargs[0], llargs[1])
+            };
+            SExt(bcx, cmp, llret_ty)
+        }
+        name if name.starts_with("simd_shuffle") => {
+            let out_len = from_str::<uint>(name.slice_from("simd_shuffle".len())).unwrap();
+            require!(is_vector_of(out_ty, out_len) && ty::simd_type(tcx, out_ty) == in_elem,
+                     "expected a SIMD type of {} `{}`, found `{}`",
+                     out_len, ty_to_string(tcx, in_elem), ty_to_string(tcx, out_ty));
+            require!(shuffle_indices.is_some(),
+                     "the lane indices must be an array literal of constants");
+            let indices = shuffle_indices.unwrap();
+            // Lanes of the second vector are numbered after those of the first.
+            for &ix in indices.iter() {
+                require!(ix < 2 * in_len, "lane index {} is out of bounds", ix);
+            }
+            let mask = indices.iter().map(|&ix| C_i32(ccx, ix as i32)).collect::<Vec<_>>();
+            ShuffleVector(bcx, llargs[0], llargs[1], C_vector(mask.as_slice()))
+        }
+        "simd_insert" => {
+            require!(out_ty == in_elem, "expected `{}` to insert, found `{}`",
+                     ty_to_string(tcx, in_elem), ty_to_string(tcx, out_ty));
+            InsertElement(bcx, llargs[0], llargs[2], llargs[1])
+        }
+        "simd_extract" => {
+            require!(out_ty == in_elem, "expected `{}` to extract, found `{}`",
+                     ty_to_string(tcx, in_elem), ty_to_string(tcx, out_ty));
+            ExtractElement(bcx, llargs[0], llargs[1])
+        }
+        "simd_select" => {
+            require!(ty::type_is_integral(in_elem),
+                     "expected a mask of integers, found `{}`", ty_to_string(tcx, in_ty));
+            require!(is_vector_of(out_ty, in_len),
+                     "expected a SIMD type of length {}, found `{}`",
+                     in_len, ty_to_string(tcx, out_ty));
+            let mask = ICmp(bcx, llvm::IntNE, llargs[0], C_null(val_ty(llargs[0])));
+            Select(bcx, mask, llargs[1], llargs[2])
+        }
+        "simd_cast" => {
+            require!(is_vector_of(out_ty, in_len),
+                     "expected a SIMD type of length {}, found `{}`",
+                     in_len, ty_to_string(tcx, out_ty));
+            let out_elem = ty::simd_type(tcx, out_ty);
+            let (in_bits, out_bits) = (machine::llbitsize_of_real(ccx, type_of(ccx, in_elem)),
+                                       machine::llbitsize_of_real(ccx, type_of(ccx, out_elem)));
+            let v = llargs[0];
+            match (ty::type_is_fp(in_elem), ty::type_is_fp(out_elem)) {
+                (false, false) if out_bits < in_bits => Trunc(bcx, v, llret_ty),
+                (false, false) if out_bits == in_bits => BitCast(bcx, v, llret_ty),
+                (false, false) if ty::type_is_signed(in_elem) => SExt(bcx, v, llret_ty),
+                (false, false) => ZExt(bcx, v, llret_ty),
+                (false, true) if ty::type_is_signed(in_elem) => SIToFP(bcx, v, llret_ty),
+                (false, true) => UIToFP(bcx, v, llret_ty),
+                (true, false) if ty::type_is_signed(out_elem) => FPToSI(bcx, v, llret_ty),
+                (true, false) => FPToUI(bcx, v, llret_ty),
+                (true, true) if out_bits < in_bits => FPTrunc(bcx, v, llret_ty),
+                (true, true) if out_bits == in_bits => v,
+                (true, true) => FPExt(bcx, v, llret_ty)
+            }
+        }
+        "simd_sqrt" | "simd_fmin" | "simd_fmax" => {
+            require!(ty::type_is_fp(in_elem),
+                     "expected a SIMD type of floats, found `{}`", ty_to_string(tcx, in_ty));
+            let elem_bits = machine::llbitsize_of_real(ccx, type_of(ccx, in_elem));
+            let llvm_name = match (name, elem_bits, in_len) {
+                ("simd_sqrt", _, _) => format!("llvm.sqrt.v{}f{}", in_len, elem_bits),
+                ("simd_fmin", 32, 4) if has_target_feature(ccx, "sse") =>
+                    "llvm.x86.sse.min.ps".to_string(),
+                ("simd_fmax", 32, 4) if has_target_feature(ccx, "sse") =>
+                    "llvm.x86.sse.max.ps".to_string(),
+                ("simd_fmin", 64, 2) if has_target_feature(ccx, "sse2") =>
+                    "llvm.x86.sse2.min.pd".to_string(),
+                ("simd_fmax", 64, 2) if has_target_feature(ccx, "sse2") =>
+                    "llvm.x86.sse2.max.pd".to_string(),
+                ("simd_fmin", 32, 8) if has_target_feature(ccx, "avx") =>
+                    "llvm.x86.avx.min.ps.256".to_string(),
+                ("simd_fmax", 32, 8) if has_target_feature(ccx, "avx") =>
+                    "llvm.x86.avx.max.ps.256".to_string(),
+                ("simd_fmin", _, _) | ("simd_fmax", _, _) => {
+                    // Like `minps` and `maxps`, pick the second operand
+                    // unless the comparison holds, which it doesn't for NaN.
+                    let op = if name == "simd_fmin" { llvm::RealOLT } else { llvm::RealOGT };
+                    let cmp = FCmp(bcx, op, llargs[0], llargs[1]);
+                    return Select(bcx, cmp, llargs[0], llargs[1]);
+                }
+                _ => unreachable!()
+            };
+            let llfnty = Type::func(Vec::from_elem(llargs.len(), llret_ty).as_slice(),
+                                    &llret_ty);
+            let llfn = get_extern_fn(ccx, &mut *ccx.externs().borrow_mut(), llvm_name.as_slice(),
+                                     llvm::CCallConv, llfnty, ty::mk_nil());
+            Call(bcx, llfn, llargs, None)
+        }
+        _ => ccx.sess().span_bug(span, format!("unknown SIMD intrinsic `{}`", name).as_slice())
+    }
+}
@@ -24,6 +24,7 @@ use middle::trans::callee;
 use middle::trans::cleanup;
 use middle::trans::common::*;
 use middle::trans::datum::*;
+use middle::trans::debuginfo;
 use middle::trans::expr::{SaveIn, Ignore};
 use middle::trans::expr;
 use middle::trans::glue;
@@ -655,6 +656,7 @@ pub fn get_vtable(bcx: Block,
     // Generate a destructor for the vtable.
     let drop_glue = glue::get_drop_glue(ccx, box_ty);
     let vtable = make_vtable(ccx, drop_glue, ll_size, ll_align, methods);
+    debuginfo::create_vtable_metadata(ccx, trait_ref.self_ty(), &*trait_ref, vtable);
 
     ccx.vtables().borrow_mut().insert(cache_key, vtable);
     vtable
@@ -3612,6 +3612,9 @@ pub fn expr_kind(tcx: &ctxt, expr: &ast::Expr) -> ExprKind {
         ast::ExprIfLet(..) => {
             tcx.sess.span_bug(expr.span, "non-desugared ExprIfLet");
         }
+        ast::ExprWhileLet(..) => {
+            tcx.sess.span_bug(expr.span, "non-desugared ExprWhileLet");
+        }
 
         ast::ExprLit(ref lit) if lit_is_str(&**lit) => {
             RvalueDpsExpr
@@ -4482,6 +4485,12 @@ pub fn has_attr(tcx: &ctxt, did: DefId, attr: &str) -> bool {
     found
 }
 
+/// Determine whether an impl is annotated with `#[default_impl]`, allowing
+/// more specific impls of the same trait to override it
+pub fn impl_is_default(tcx: &ctxt, impl_did: DefId) -> bool {
+    has_attr(tcx, impl_did, "default_impl")
+}
+
 /// Determine whether an item is annotated with `#[repr(packed)]`
 pub fn lookup_packed(tcx: &ctxt, did: DefId) -> bool {
     lookup_repr_hints(tcx, did).contains(&attr::ReprPacked)
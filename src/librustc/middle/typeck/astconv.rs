@@ -992,6 +992,12 @@ pub fn ast_ty_to_ty<'tcx, AC: AstConv<'tcx>, RS: RegionScope>(
                 match const_eval::eval_const_expr_partial(tcx, &**e) {
                     Ok(ref r) => {
                         match *r {
+                            const_eval::const_int(i) if i < 0 => {
+                                tcx.sess.span_fatal(
+                                    ast_ty.span,
+                                    format!("expected positive integer for vector \
+                                             length, found `{}`", i).as_slice());
+                            }
                             const_eval::const_int(i) =>
                                 ty::mk_vec(tcx, ast_ty_to_ty(this, rscope, &**ty),
                                            Some(i as uint)),
@@ -1000,7 +1006,11 @@ pub fn ast_ty_to_ty<'tcx, AC: AstConv<'tcx>, RS: RegionScope>(
                                            Some(i as uint)),
                             _ => {
                                 tcx.sess.span_fatal(
-                                    ast_ty.span, "expected constant expr for vector length");
+                                    ast_ty.span,
+                                    format!("expected integer for vector length, \
+                                             found {}",
+                                            const_eval::const_val_description(r))
+                                        .as_slice());
                             }
                         }
                     }
@@ -1190,7 +1190,8 @@ impl<'a, 'tcx> LookupContext<'a, 'tcx> {
     fn consider_candidates(&self, rcvr_ty: ty::t,
                            candidates: &[Candidate])
                            -> Option<MethodCallee> {
-        let relevant_candidates = self.filter_candidates(rcvr_ty, candidates);
+        let mut relevant_candidates = self.filter_candidates(rcvr_ty, candidates);
+        self.prune_specialized_candidates(rcvr_ty, &mut relevant_candidates);
 
         if relevant_candidates.len() == 0 {
             return None;
@@ -1260,6 +1261,95 @@ impl<'a, 'tcx> LookupContext<'a, 'tcx> {
         relevant_candidates
     }
 
+    fn prune_specialized_candidates(&self, rcvr_ty: ty::t, candidates: &mut Vec<Candidate>) {
+        /*!
+         * Once the receiver type is fully known, drops candidates
+         * from `#[default_impl]` impls that are specialized by the
+         * impl of another candidate. Without this, calling a method
+         * on (e.g.) a `Vec<u8>` would report multiple applicable
+         * methods rather than picking the more specific impl.
+         */
+
+        if candidates.len() <= 1 {
+            return;
+        }
+
+        let rcvr_ty = self.infcx().resolve_type_vars_if_possible(rcvr_ty);
+        if ty::type_needs_infer(rcvr_ty) {
+            return;
+        }
+
+        let candidate_impls: Vec<DefId> =
+            candidates.iter().filter_map(|c| self.impl_of_candidate(c)).collect();
+
+        candidates.retain(|c| {
+            match self.impl_of_candidate(c) {
+                Some(impl_did) => {
+                    !candidate_impls.iter().any(|&other_did| {
+                        other_did != impl_did &&
+                            traits::impl_specializes(self.infcx(), other_did, impl_did)
+                    })
+                }
+                None => true,
+            }
+        });
+    }
+
+    fn impl_of_candidate(&self, candidate: &Candidate) -> Option<DefId> {
+        match candidate.origin {
+            MethodStatic(method_did) => ty::impl_of_method(self.tcx(), method_did),
+            _ => None,
+        }
+    }
+
+    fn specializable_origin(&self, candidate: &Candidate, all_substs: subst::Substs)
+                            -> (MethodOrigin, subst::Substs) {
+        /*!
+         * A method of a `#[default_impl]` impl may be overridden by a
+         * more specific impl once the type parameters in the receiver
+         * type are known. Calls whose receiver involves type
+         * parameters are therefore recorded as calls through the
+         * trait, just like calls on a type parameter, so that the
+         * impl is selected again after monomorphization.
+         */
+
+        let tcx = self.tcx();
+        let trait_ref = match self.impl_of_candidate(candidate) {
+            Some(impl_did) if ty::impl_is_default(tcx, impl_did) => {
+                match ty::impl_trait_ref(tcx, impl_did) {
+                    Some(trait_ref) => trait_ref.subst(tcx, &candidate.rcvr_substs),
+                    None => return (candidate.origin.clone(), all_substs),
+                }
+            }
+            _ => return (candidate.origin.clone(), all_substs),
+        };
+
+        let self_ty = self.infcx().resolve_type_vars_if_possible(trait_ref.self_ty());
+        if !ty::type_has_params(self_ty) {
+            return (candidate.origin.clone(), all_substs);
+        }
+
+        let method_num = ty::trait_items(tcx, trait_ref.def_id).iter().position(|item| {
+            item.ident().name == candidate.method_ty.ident.name
+        });
+        let method_num = match method_num {
+            Some(method_num) => method_num,
+            None => return (candidate.origin.clone(), all_substs),
+        };
+
+        // The callee substitutions are those of the trait rather than
+        // the impl, followed by those of the method itself.
+        let m_types = all_substs.types.get_slice(subst::FnSpace).to_vec();
+        let m_regions = all_substs.regions().get_slice(subst::FnSpace).to_vec();
+        let substs = trait_ref.substs.clone().with_method(m_types, m_regions);
+
+        let origin = MethodTypeParam(MethodParam {
+            trait_ref: trait_ref,
+            method_num: method_num,
+        });
+        (origin, substs)
+    }
+
     fn confirm_candidate(&self, rcvr_ty: ty::t, candidate: &Candidate)
                          -> MethodCallee
     {
@@ -1379,10 +1469,12 @@ impl<'a, 'tcx> LookupContext<'a, 'tcx> {
             &all_substs,
             &candidate.method_ty.generics);
 
+        let (origin, substs) = self.specializable_origin(candidate, all_substs);
+
         MethodCallee {
-            origin: candidate.origin.clone(),
+            origin: origin,
             ty: fty,
-            substs: all_substs
+            substs: substs
         }
     }
 
@@ -4100,6 +4100,9 @@ fn check_expr_with_unifier(fcx: &FnCtxt,
       ast::ExprIfLet(..) => {
         tcx.sess.span_bug(expr.span, "non-desugared ExprIfLet");
       }
+      ast::ExprWhileLet(..) => {
+        tcx.sess.span_bug(expr.span, "non-desugared ExprWhileLet");
+      }
       ast::ExprWhile(ref cond, ref body, _) => {
         check_expr_has_type(fcx, &**cond, ty::mk_bool());
         check_block_no_value(fcx, &**body);
@@ -5811,6 +5814,24 @@ pub fn check_intrinsic_type(ccx: &CrateCtxt, it: &ast::ForeignItem) {
 
             "return_address" => (0, vec![], ty::mk_imm_ptr(tcx, ty::mk_u8())),
 
+            // Whether the `#[simd]` types these are used with fit together is
+            // checked when they are translated.
+            "simd_eq" | "simd_ne" | "simd_lt" | "simd_le" | "simd_gt" | "simd_ge" =>
+                (2, vec!(param(ccx, 0), param(ccx, 0)), param(ccx, 1)),
+            "simd_shuffle2" | "simd_shuffle4" | "simd_shuffle8" | "simd_shuffle16" => {
+                let lanes = from_str::<uint>(name.get().slice_from("simd_shuffle".len()));
+                (2,
+                 vec!(param(ccx, 0), param(ccx, 0), ty::mk_vec(tcx, ty::mk_u32(), lanes)),
+                 param(ccx, 1))
+            }
+            "simd_insert" => (2, vec!(param(ccx, 0), ty::mk_u32(), param(ccx, 1)), param(ccx, 0)),
+            "simd_extract" => (2, vec!(param(ccx, 0), ty::mk_u32()), param(ccx, 1)),
+            "simd_cast" => (2, vec!(param(ccx, 0)), param(ccx, 1)),
+            "simd_select" =>
+                (2, vec!(param(ccx, 0), param(ccx, 1), param(ccx, 1)), param(ccx, 1)),
+            "simd_sqrt" => (1, vec!(param(ccx, 0)), param(ccx, 0)),
+            "simd_fmin" | "simd_fmax" => (1, vec!(param(ccx, 0), param(ccx, 0)), param(ccx, 0)),
+
             ref other => {
                 span_err!(tcx.sess, it.span, E0093,
                     "unrecognized intrinsic function: `{}`", *other);
@@ -87,6 +87,14 @@ impl<'cx, 'tcx> OverlapChecker<'cx, 'tcx> {
             return;
         }
 
+        // Overlap is permitted when one impl is a strict specialization
+        // of a `#[default_impl]`; selection then prefers the specialization.
+        if traits::impl_specializes(&infcx, impl1_def_id, impl2_def_id) ||
+           traits::impl_specializes(&infcx, impl2_def_id, impl1_def_id) {
+            debug!("impls overlap, but one specializes the other");
+            return;
+        }
+
         span_err!(self.tcx.sess, self.span_of_impl(impl1_def_id), E0119,
                   "conflicting implementations for trait `{}`",
                   ty::item_path_str(self.tcx, trait_def_id));
@@ -1943,14 +1943,27 @@ fn ty_generics<'tcx,AC>(this: &AC,
         DontCreateTypeParametersForAssociatedTypes => {}
         CreateTypeParametersForAssociatedTypes => {
             let mut index = 0;
-            for param in types.iter() {
-                for bound in param.bounds.iter() {
+            for (i, param) in types.iter().enumerate() {
+                // Bounds from `where` clauses introduce associated types
+                // too. A trait that is named more than once only gets one
+                // set of parameters.
+                let param_ty = ty::ParamTy::new(space, i, local_def(param.id));
+                let bounds = merge_param_bounds(this.tcx(),
+                                                param_ty,
+                                                param.bounds.as_slice(),
+                                                where_clause);
+                let mut seen_traits = Vec::new();
+                for bound in bounds.into_iter() {
                     match *bound {
                         ast::TraitTyParamBound(ref trait_bound) => {
                             match lookup_def_tcx(this.tcx(),
                                                  trait_bound.path.span,
                                                  trait_bound.ref_id) {
                                 def::DefTrait(trait_did) => {
+                                    if seen_traits.contains(&trait_did) {
+                                        continue
+                                    }
+                                    seen_traits.push(trait_did);
                                     ensure_associated_types(this, trait_did);
                                     let associated_types =
                                         ty::associated_types_for_trait(
@@ -0,0 +1,195 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Writes an inventory of the unsafe code in a crate, for `-Z unsafe-audit`.
+//!
+//! Every user-written `unsafe` block and `unsafe fn` is listed along with
+//! the operations inside it that need the unsafe context, as classified by
+//! `middle::effect`. The report is written as JSON to
+//! `<out-dir>/unsafe-audit/<crate name>.json`.
+//!
+//! An operation is attributed to the innermost enclosing region. Blocks
+//! generated by the compiler (for example by `println!`) are not listed,
+//! and the operations inside them count towards the enclosing region.
+//!
+//! Unsafe items other than `unsafe fn` are not in the inventory. This
+//! compiler has no `unsafe impl` or `unsafe trait` yet, so these would
+//! need their own region kind when they are added.
+
+use driver::driver::CrateAnalysis;
+use driver::session::Session;
+use middle::effect;
+use middle::ty;
+
+use std::io;
+use std::io::{fs, File};
+use std::mem;
+use serialize::{json, Encodable};
+use syntax::ast;
+use syntax::ast_util::{local_def, PostExpansionMethod};
+use syntax::codemap::Span;
+use syntax::visit;
+use syntax::visit::Visitor;
+
+#[deriving(Encodable)]
+struct Location {
+    file: String,
+    line: uint,
+    col: uint,
+}
+
+#[deriving(Encodable)]
+struct Operation {
+    kind: String,
+    location: Location,
+}
+
+#[deriving(Encodable)]
+struct UnsafeRegion {
+    /// Either `unsafe_fn` or `unsafe_block`.
+    kind: String,
+    /// Path of the function containing the region.
+    function: Option<String>,
+    location: Location,
+    operations: Vec<Operation>,
+}
+
+#[deriving(Encodable)]
+struct UnsafeAudit {
+    crate_name: String,
+    regions: Vec<UnsafeRegion>,
+}
+
+struct AuditVisitor<'a, 'tcx: 'a> {
+    tcx: &'a ty::ctxt<'tcx>,
+    regions: Vec<UnsafeRegion>,
+    /// Indices into `regions` of the regions enclosing the current node.
+    region_stack: Vec<uint>,
+    /// The innermost enclosing function item or method.
+    cur_fn: Option<ast::NodeId>,
+}
+
+impl<'a, 'tcx> AuditVisitor<'a, 'tcx> {
+    fn location(&self, span: Span) -> Location {
+        let loc = self.tcx.sess.codemap().lookup_char_pos(span.lo);
+        Location {
+            file: loc.file.name.clone(),
+            line: loc.line,
+            col: loc.col.to_uint() + 1,
+        }
+    }
+
+    fn push_region(&mut self, kind: &str, span: Span) {
+        let region = UnsafeRegion {
+            kind: kind.to_string(),
+            function: self.cur_fn.map(|id| ty::item_path_str(self.tcx, local_def(id))),
+            location: self.location(span),
+            operations: vec!(),
+        };
+        self.region_stack.push(self.regions.len());
+        self.regions.push(region);
+    }
+}
+
+impl<'a, 'tcx, 'v> Visitor<'v> for AuditVisitor<'a, 'tcx> {
+    fn visit_fn(&mut self, fn_kind: visit::FnKind<'v>, fn_decl: &'v ast::FnDecl,
+                block: &'v ast::Block, span: Span, id: ast::NodeId) {
+        let (is_item_fn, is_unsafe_fn) = match fn_kind {
+            visit::FkItemFn(_, _, fn_style, _) =>
+                (true, fn_style == ast::UnsafeFn),
+            visit::FkMethod(_, _, method) =>
+                (true, method.pe_fn_style() == ast::UnsafeFn),
+            _ => (false, false),
+        };
+
+        // Closures share the regions of their environment, like in
+        // `middle::effect`, but nested fn items don't.
+        if !is_item_fn {
+            visit::walk_fn(self, fn_kind, fn_decl, block, span);
+            return;
+        }
+
+        let old_fn = mem::replace(&mut self.cur_fn, Some(id));
+        let old_stack = mem::replace(&mut self.region_stack, vec!());
+        if is_unsafe_fn {
+            self.push_region("unsafe_fn", span);
+        }
+
+        visit::walk_fn(self, fn_kind, fn_decl, block, span);
+
+        self.region_stack = old_stack;
+        self.cur_fn = old_fn;
+    }
+
+    fn visit_block(&mut self, block: &ast::Block) {
+        match block.rules {
+            ast::UnsafeBlock(ast::UserProvided) => {
+                self.push_region("unsafe_block", block.span);
+                visit::walk_block(self, block);
+                self.region_stack.pop();
+            }
+            _ => visit::walk_block(self, block),
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        match (effect::unsafe_operation(self.tcx, expr), self.region_stack.last()) {
+            (Some(op), Some(&idx)) => {
+                let op = Operation {
+                    kind: op.name().to_string(),
+                    location: self.location(expr.span),
+                };
+                self.regions.get_mut(idx).operations.push(op);
+            }
+            _ => {}
+        }
+
+        visit::walk_expr(self, expr);
+    }
+}
+
+pub fn process_crate(sess: &Session,
+                     krate: &ast::Crate,
+                     analysis: &CrateAnalysis,
+                     odir: &Option<Path>) {
+    let mut visitor = AuditVisitor {
+        tcx: &analysis.ty_cx,
+        regions: vec!(),
+        region_stack: vec!(),
+        cur_fn: None,
+    };
+    visit::walk_crate(&mut visitor, krate);
+
+    let audit = UnsafeAudit {
+        crate_name: analysis.name.clone(),
+        regions: visitor.regions,
+    };
+
+    let mut path = match *odir {
+        Some(ref dir) => dir.join("unsafe-audit"),
+        None => Path::new("unsafe-audit"),
+    };
+    match fs::mkdir_recursive(&path, io::USER_RWX) {
+        Err(e) => sess.fatal(format!("could not create directory {}: {}",
+                                     path.display(), e).as_slice()),
+        Ok(()) => {}
+    }
+    path.push(format!("{}.json", audit.crate_name));
+
+    let result = File::create(&path).and_then(|mut file| {
+        let mut encoder = json::PrettyEncoder::new(&mut file);
+        audit.encode(&mut encoder)
+    });
+    match result {
+        Err(e) => sess.fatal(format!("could not write {}: {}",
+                                     path.display(), e).as_slice()),
+        Ok(()) => {}
+    }
+}
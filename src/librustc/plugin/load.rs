@@ -50,6 +50,10 @@ struct PluginLoader<'a> {
     sess: &'a Session,
     reader: PluginMetadataReader<'a>,
     plugins: Plugins,
+    /// The modules enclosing the item being visited.
+    mod_path: Vec<ast::Ident>,
+    /// How many blocks enclose the item being visited.
+    block_depth: uint,
 }
 
 impl<'a> PluginLoader<'a> {
@@ -61,6 +65,8 @@ impl<'a> PluginLoader<'a> {
                 macros: vec!(),
                 registrars: vec!(),
             },
+            mod_path: vec!(),
+            block_depth: 0,
         }
     }
 }
@@ -110,8 +116,19 @@ impl<'a, 'v> Visitor<'v> for PluginLoader<'a> {
                 let PluginMetadata { macros, lib, registrar_symbol } =
                     self.reader.read_plugin_metadata(vi);
 
+                // `$crate` names the crate through this item, which has no
+                // path if it is inside a block.
+                let crate_path = if self.block_depth == 0 {
+                    let mut path = self.mod_path.clone();
+                    path.push(name);
+                    Some(path)
+                } else {
+                    None
+                };
+
                 self.plugins.macros.push(ExportedMacros {
                     crate_name: name,
+                    crate_path: crate_path,
                     macros: macros,
                 });
 
@@ -124,6 +141,21 @@ impl<'a, 'v> Visitor<'v> for PluginLoader<'a> {
             _ => (),
         }
     }
+    fn visit_item(&mut self, item: &ast::Item) {
+        match item.node {
+            ast::ItemMod(..) => {
+                self.mod_path.push(item.ident);
+                visit::walk_item(self, item);
+                self.mod_path.pop();
+            }
+            _ => visit::walk_item(self, item),
+        }
+    }
+    fn visit_block(&mut self, block: &ast::Block) {
+        self.block_depth += 1;
+        visit::walk_block(self, block);
+        self.block_depth -= 1;
+    }
     fn visit_mac(&mut self, _: &ast::Mac) {
         // bummer... can't see plugins inside macros.
         // do nothing.
@@ -14,7 +14,8 @@ use lint::{LintPassObject, LintId, Lint};
 
 use syntax::ext::base::{SyntaxExtension, NamedSyntaxExtension, NormalTT};
 use syntax::ext::base::{IdentTT, LetSyntaxTT, Decorator, Modifier};
-use syntax::ext::base::{MacroExpanderFn};
+use syntax::ext::base::{MacroExpanderFn, ItemDecorator};
+use syntax::ext::deriving;
 use syntax::codemap::Span;
 use syntax::parse::token;
 use syntax::ast;
@@ -77,6 +78,19 @@ impl Registry {
         self.register_syntax_extension(token::intern(name), NormalTT(box expander, None));
     }
 
+    /// Register an expander for `#[deriving(trait_name)]`.
+    ///
+    /// The expander is called with the `trait_name` item of the
+    /// `deriving` list, just like the built-in derivable traits.
+    /// `syntax::ext::deriving::generic::TraitDef` builds the impl, so the
+    /// expander usually only has to describe how each method combines the
+    /// fields of the type.
+    pub fn register_deriving(&mut self, trait_name: &str,
+                             expander: Box<ItemDecorator + 'static>) {
+        let name = token::intern(deriving::plugin_deriving_name(trait_name).as_slice());
+        self.register_syntax_extension(name, Decorator(expander));
+    }
+
     /// Register a compiler lint pass.
     pub fn register_lint_pass(&mut self, lint_pass: LintPassObject) {
         self.lint_passes.push(lint_pass);
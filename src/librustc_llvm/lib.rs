@@ -1874,6 +1874,8 @@ extern {
                                         -> ValueRef;
 
     pub fn LLVMDICompositeTypeSetTypeArray(CompositeType: ValueRef, TypeArray: ValueRef);
+    pub fn LLVMRustDICompositeTypeSetTemplateParams(CompositeType: ValueRef,
+                                                    TemplateParams: ValueRef);
     pub fn LLVMWriteTypeToString(Type: TypeRef, s: RustStringRef);
     pub fn LLVMWriteValueToString(value_ref: ValueRef, s: RustStringRef);
 
@@ -1908,8 +1910,10 @@ extern {
                                        UseSoftFP: bool,
                                        NoFramePointerElim: bool,
                                        FunctionSections: bool,
-                                       DataSections: bool) -> TargetMachineRef;
+                                       DataSections: bool,
+                                       CompressDebugSections: bool) -> TargetMachineRef;
     pub fn LLVMRustDisposeTargetMachine(T: TargetMachineRef);
+    pub fn LLVMRustHasFeature(T: TargetMachineRef, Feature: *const c_char) -> bool;
     pub fn LLVMRustAddAnalysisPasses(T: TargetMachineRef,
                                      PM: PassManagerRef,
                                      M: ModuleRef);
@@ -1939,6 +1943,12 @@ extern {
                                       syms: *const *const c_char,
                                       len: size_t);
     pub fn LLVMRustMarkAllFunctionsNounwind(M: ModuleRef);
+    pub fn LLVMRustAddProfileInstrumentationPass(PM: PassManagerRef);
+    pub fn LLVMRustAddProfileUsePass(PM: PassManagerRef, Path: *const c_char) -> bool;
+    pub fn LLVMRustAddGcovPass(PM: PassManagerRef, M: ModuleRef, NotesPath: *const c_char);
+    pub fn LLVMRustAddAddressSanitizerPasses(PM: PassManagerRef);
+    pub fn LLVMRustAddThreadSanitizerPass(PM: PassManagerRef);
+    pub fn LLVMRustAddMemorySanitizerPass(PM: PassManagerRef);
 
     pub fn LLVMRustOpenArchive(path: *const c_char) -> ArchiveRef;
     pub fn LLVMRustArchiveReadSection(AR: ArchiveRef, name: *const c_char,
@@ -1969,6 +1979,9 @@ extern {
     pub fn LLVMGetDiagInfoKind(DI: DiagnosticInfoRef) -> DiagnosticKind;
 
     pub fn LLVMWriteDebugLocToString(C: ContextRef, DL: DebugLocRef, s: RustStringRef);
+    pub fn LLVMRustUnpackDebugLoc(C: ContextRef, DL: DebugLocRef,
+                                  line_out: *mut c_uint, col_out: *mut c_uint,
+                                  file_out: RustStringRef) -> bool;
 
     pub fn LLVMSetInlineAsmDiagnosticHandler(C: ContextRef,
                                              H: InlineAsmDiagHandler,
@@ -2139,6 +2152,22 @@ pub unsafe fn debug_loc_to_string(c: ContextRef, tr: DebugLocRef) -> String {
         .expect("got a non-UTF8 DebugLoc from LLVM")
 }
 
+/// The file, line and column of a debug location, or `None` for an unknown
+/// one. The file is relative to the compilation directory if it is inside
+/// it, and the column counts characters from zero.
+pub unsafe fn unpack_debug_loc(c: ContextRef, dl: DebugLocRef) -> Option<(String, uint, uint)> {
+    let mut line = 0;
+    let mut col = 0;
+    let mut known = false;
+    let file = build_string(|s| known = LLVMRustUnpackDebugLoc(c, dl, &mut line, &mut col, s))
+        .expect("got a non-UTF8 file name from LLVM");
+    if known {
+        Some((file, line as uint, col as uint))
+    } else {
+        None
+    }
+}
+
 // FIXME #15460 - create a public function that actually calls our
 // static LLVM symbols. Otherwise the linker will just throw llvm
 // away.  We're just calling lots of stuff until we transitively get
@@ -61,6 +61,7 @@ pub mod local_data;
 pub mod mutex;
 pub mod rtio;
 pub mod stack;
+pub mod stack_overflow;
 pub mod task;
 pub mod thread;
 pub mod unwind;
@@ -113,6 +114,7 @@ pub fn init(argc: int, argv: *const *const u8) {
         args::init(argc, argv);
         local_ptr::init();
         at_exit_imp::init();
+        stack_overflow::init();
     }
 
     // FIXME(#14344) this shouldn't be necessary
@@ -20,6 +20,10 @@
 //! functions here are used at the borders of the task lifetime in order to
 //! manage these limits.
 //!
+//! Code compiled with `-C stack-check=probe` (the default on most targets) does
+//! not check these limits at all, and relies on guard pages instead (see
+//! `stack_overflow`).
+//!
 //! This function is an unstable module because this scheme for stack overflow
 //! detection is not guaranteed to continue in the future. Usage of this module
 //! is discouraged unless absolutely necessary.
@@ -0,0 +1,325 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Guard page based stack overflow detection
+//!
+//! Code compiled with stack probes (`-C stack-check=probe`, the default on the
+//! targets this module supports) does
+//! not compare the stack pointer against a limit in its prologue. Instead it
+//! relies on the guard page which the OS places below the stack of every
+//! thread: running off the end of the stack touches the guard page, and the
+//! probes make sure that no frame is large enough to jump over it.
+//!
+//! Touching the guard page raises `SIGSEGV` (or `SIGBUS`). The handler
+//! installed by `init` runs on a small alternate stack, recognizes faults in
+//! the guard page of the current thread and reports them as a stack overflow
+//! of the current task. Threads opt into this by creating a `Handler`, which
+//! libnative does for all of its tasks and libgreen for all of its schedulers.
+//! A scheduler which switches to a stack of its own tells the handler where
+//! that stack's guard page is with `set_guard_page`. Any other fault, or a
+//! fault on a thread without a `Handler`, kills the process with the original
+//! signal.
+
+pub use self::imp::{init, set_guard_page, Handler};
+
+#[cfg(any(all(target_os = "linux",
+              any(target_arch = "x86",
+                  target_arch = "x86_64",
+                  target_arch = "arm")),
+          target_os = "macos"))]
+mod imp {
+    use core::prelude::*;
+    use core::intrinsics;
+    use core::mem;
+    use core::ptr;
+    use libc;
+
+    use local::Local;
+    use task::Task;
+    use thread_local_storage as tls;
+
+    use self::signal::{sigaction, siginfo, sighandler_t, stack_t};
+    use self::signal::{SA_ONSTACK, SA_SIGINFO, SIGBUS, SIG_DFL, SIGSTKSZ, SS_DISABLE};
+
+    // The lowest address of the stack currently in use, right above its guard
+    // page, or null if the thread has no `Handler`.
+    static mut STACK_LO_KEY: tls::Key = 0;
+    // The same for the stack the thread was started on.
+    static mut THREAD_STACK_LO_KEY: tls::Key = 0;
+    static mut PAGE_SIZE: uint = 0;
+
+    /// Installs the signal handler which reports guard page faults as stack
+    /// overflow. This must be called once, before any `Handler` is created.
+    pub unsafe fn init() {
+        PAGE_SIZE = libc::sysconf(libc::_SC_PAGESIZE) as uint;
+        tls::create(&mut STACK_LO_KEY);
+        tls::create(&mut THREAD_STACK_LO_KEY);
+
+        let mut action: sigaction = mem::zeroed();
+        action.sa_flags = SA_SIGINFO | SA_ONSTACK;
+        action.sa_sigaction = signal_handler as sighandler_t;
+        sigaction(libc::SIGSEGV, &action, ptr::null_mut());
+        sigaction(SIGBUS, &action, ptr::null_mut());
+    }
+
+    /// Enables stack overflow detection for the current thread until dropped.
+    ///
+    /// The signal handler cannot run on the stack which has just overflowed,
+    /// so this maps an alternate stack for it.
+    pub struct Handler {
+        stack: *mut libc::c_void,
+    }
+
+    impl Handler {
+        pub unsafe fn new() -> Handler {
+            let stack_lo = match current_stack_lo() {
+                Some(lo) => lo,
+                None => return Handler { stack: ptr::null_mut() },
+            };
+
+            let stack = libc::mmap(ptr::null_mut(),
+                                   SIGSTKSZ,
+                                   libc::PROT_READ | libc::PROT_WRITE,
+                                   libc::MAP_PRIVATE | libc::MAP_ANON,
+                                   -1,
+                                   0);
+            if stack == libc::MAP_FAILED {
+                rtabort!("failed to allocate an alternative stack");
+            }
+
+            let stack_t = stack_t {
+                ss_sp: stack,
+                ss_flags: 0,
+                ss_size: SIGSTKSZ,
+            };
+            sigaltstack(&stack_t, ptr::null_mut());
+            tls::set(STACK_LO_KEY, stack_lo as *mut u8);
+            tls::set(THREAD_STACK_LO_KEY, stack_lo as *mut u8);
+
+            Handler { stack: stack }
+        }
+    }
+
+    impl Drop for Handler {
+        fn drop(&mut self) {
+            if self.stack.is_null() {
+                return;
+            }
+            unsafe {
+                let stack_t = stack_t {
+                    ss_sp: ptr::null_mut(),
+                    ss_flags: SS_DISABLE,
+                    ss_size: SIGSTKSZ,
+                };
+                sigaltstack(&stack_t, ptr::null_mut());
+                libc::munmap(self.stack, SIGSTKSZ);
+                tls::set(STACK_LO_KEY, ptr::null_mut());
+                tls::set(THREAD_STACK_LO_KEY, ptr::null_mut());
+            }
+        }
+    }
+
+    /// Records that the current thread is about to run on a stack whose lowest
+    /// page, at `guard`, is its guard page, or back on its own stack if
+    /// `guard` is `None`. This does nothing on a thread without a `Handler`.
+    pub unsafe fn set_guard_page(guard: Option<uint>) {
+        let thread_stack_lo = tls::get(THREAD_STACK_LO_KEY);
+        if thread_stack_lo.is_null() {
+            return;
+        }
+        let stack_lo = match guard {
+            Some(guard) => (guard + PAGE_SIZE) as *mut u8,
+            None => thread_stack_lo,
+        };
+        tls::set(STACK_LO_KEY, stack_lo);
+    }
+
+    extern fn signal_handler(signum: libc::c_int,
+                             info: *mut siginfo,
+                             _data: *mut libc::c_void) {
+        unsafe {
+            // Stack probes never touch memory more than a page beyond the
+            // last access, so an overflow always faults in the page right
+            // below the stack.
+            let addr = (*info).si_addr as uint;
+            let stack_lo = tls::get(STACK_LO_KEY) as uint;
+            if stack_lo == 0 || addr >= stack_lo || addr < stack_lo - PAGE_SIZE {
+                // Not ours. Returning re-executes the faulting instruction,
+                // which now kills the process as if we had never been here.
+                let mut action: sigaction = mem::zeroed();
+                action.sa_sigaction = SIG_DFL;
+                sigaction(signum, &action, ptr::null_mut());
+                return;
+            }
+
+            // This is the same report as the one made by `stack_exhausted`,
+            // and the same caveats about unwinding apply here.
+            let task: Option<*mut Task> = Local::try_unsafe_borrow();
+            let name = match task {
+                Some(task) => (*task).name.as_ref().map(|n| n.as_slice()),
+                None => None,
+            };
+            let name = name.unwrap_or("<unknown>");
+            rterrln!("task '{}' has overflowed its stack", name);
+
+            intrinsics::abort();
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    unsafe fn current_stack_lo() -> Option<uint> {
+        let mut attr: libc::pthread_attr_t = mem::zeroed();
+        if pthread_getattr_np(pthread_self(), &mut attr) != 0 {
+            return None;
+        }
+        let mut stackaddr = ptr::null_mut();
+        let mut stacksize = 0;
+        let ret = pthread_attr_getstack(&attr, &mut stackaddr, &mut stacksize);
+        assert_eq!(pthread_attr_destroy(&mut attr), 0);
+        if ret == 0 { Some(stackaddr as uint) } else { None }
+    }
+
+    #[cfg(target_os = "macos")]
+    unsafe fn current_stack_lo() -> Option<uint> {
+        let thread = pthread_self();
+        let stack_hi = pthread_get_stackaddr_np(thread) as uint;
+        Some(stack_hi - pthread_get_stacksize_np(thread) as uint)
+    }
+
+    #[cfg(target_os = "linux")]
+    mod signal {
+        use libc;
+
+        pub static SA_ONSTACK: libc::c_ulong = 0x08000000;
+        pub static SA_SIGINFO: libc::c_ulong = 0x00000004;
+        pub static SIGBUS: libc::c_int = 7;
+        pub static SIG_DFL: sighandler_t = 0;
+        pub static SIGSTKSZ: libc::size_t = 8192;
+        pub static SS_DISABLE: libc::c_int = 2;
+
+        pub type sighandler_t = libc::size_t;
+
+        // Only the fields which are set for SIGSEGV and SIGBUS are declared.
+        #[repr(C)]
+        pub struct siginfo {
+            si_signo: libc::c_int,
+            si_errno: libc::c_int,
+            si_code: libc::c_int,
+            pub si_addr: *mut libc::c_void,
+        }
+
+        #[repr(C)]
+        pub struct sigaction {
+            pub sa_sigaction: sighandler_t,
+            pub sa_mask: sigset_t,
+            pub sa_flags: libc::c_ulong,
+            sa_restorer: *mut libc::c_void,
+        }
+
+        #[repr(C)]
+        #[cfg(target_word_size = "32")]
+        pub struct sigset_t {
+            __val: [libc::c_ulong, ..32],
+        }
+
+        #[repr(C)]
+        #[cfg(target_word_size = "64")]
+        pub struct sigset_t {
+            __val: [libc::c_ulong, ..16],
+        }
+
+        #[repr(C)]
+        pub struct stack_t {
+            pub ss_sp: *mut libc::c_void,
+            pub ss_flags: libc::c_int,
+            pub ss_size: libc::size_t,
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    mod signal {
+        use libc;
+
+        pub static SA_ONSTACK: libc::c_int = 0x0001;
+        pub static SA_SIGINFO: libc::c_int = 0x0040;
+        pub static SIGBUS: libc::c_int = 10;
+        pub static SIG_DFL: sighandler_t = 0;
+        pub static SIGSTKSZ: libc::size_t = 131072;
+        pub static SS_DISABLE: libc::c_int = 4;
+
+        pub type sighandler_t = libc::size_t;
+        pub type sigset_t = u32;
+
+        // Only the fields which are set for SIGSEGV and SIGBUS are declared.
+        #[repr(C)]
+        pub struct siginfo {
+            si_signo: libc::c_int,
+            si_errno: libc::c_int,
+            si_code: libc::c_int,
+            si_pid: libc::pid_t,
+            si_uid: libc::uid_t,
+            si_status: libc::c_int,
+            pub si_addr: *mut libc::c_void,
+        }
+
+        #[repr(C)]
+        pub struct sigaction {
+            pub sa_sigaction: sighandler_t,
+            pub sa_mask: sigset_t,
+            pub sa_flags: libc::c_int,
+        }
+
+        #[repr(C)]
+        pub struct stack_t {
+            pub ss_sp: *mut libc::c_void,
+            pub ss_size: libc::size_t,
+            pub ss_flags: libc::c_int,
+        }
+    }
+
+    extern {
+        fn sigaction(signum: libc::c_int,
+                     act: *const sigaction,
+                     oldact: *mut sigaction) -> libc::c_int;
+        fn sigaltstack(ss: *const stack_t, oss: *mut stack_t) -> libc::c_int;
+
+        fn pthread_self() -> libc::pthread_t;
+        #[cfg(target_os = "linux")]
+        fn pthread_getattr_np(thread: libc::pthread_t,
+                              attr: *mut libc::pthread_attr_t) -> libc::c_int;
+        #[cfg(target_os = "linux")]
+        fn pthread_attr_getstack(attr: *const libc::pthread_attr_t,
+                                 stackaddr: *mut *mut libc::c_void,
+                                 stacksize: *mut libc::size_t) -> libc::c_int;
+        #[cfg(target_os = "linux")]
+        fn pthread_attr_destroy(attr: *mut libc::pthread_attr_t) -> libc::c_int;
+        #[cfg(target_os = "macos")]
+        fn pthread_get_stackaddr_np(thread: libc::pthread_t) -> *mut libc::c_void;
+        #[cfg(target_os = "macos")]
+        fn pthread_get_stacksize_np(thread: libc::pthread_t) -> libc::size_t;
+    }
+}
+
+#[cfg(not(any(all(target_os = "linux",
+                  any(target_arch = "x86",
+                      target_arch = "x86_64",
+                      target_arch = "arm")),
+              target_os = "macos")))]
+mod imp {
+    pub unsafe fn init() {}
+
+    pub unsafe fn set_guard_page(_guard: Option<uint>) {}
+
+    pub struct Handler;
+
+    impl Handler {
+        pub unsafe fn new() -> Handler { Handler }
+    }
+}
@@ -376,6 +376,12 @@ These can all be interpreted as flags for a particular formatter.
     * `X` - precedes the argument with a "0x"
     * `t` - precedes the argument with a "0b"
     * `o` - precedes the argument with a "0o"
+    * `Show` - for `#[deriving(Show)]` types and the standard collections,
+               prints one field or element per line, indented, and formats
+               nested values in the alternate form as well. Slices and
+               vectors used to print their elements without the surrounding
+               brackets with `{:#}`; code relying on that should now join
+               the elements itself.
 * '0' - This is used to indicate for integer formats that the padding should
         both be done with a `0` character as well as be sign-aware. A format
         like `{:08d}` would yield `00000001` for the integer `1`, while the same
@@ -428,6 +434,7 @@ pub use core::fmt::{LowerHex, UpperHex, String, Pointer};
 pub use core::fmt::{Float, LowerExp, UpperExp};
 pub use core::fmt::{FormatError, WriteError};
 pub use core::fmt::{Argument, Arguments, write, radix, Radix, RadixFmt};
+pub use core::fmt::{ShowStruct, ShowTuple, ShowCollection};
 
 #[doc(hidden)]
 pub use core::fmt::{argument, argumentstr, argumentuint};
@@ -17,6 +17,7 @@ use codemap;
 use codemap::Span;
 use ext::base;
 use ext::base::*;
+use parse::parser::Parser;
 use parse::token::InternedString;
 use parse::token;
 use ptr::P;
@@ -56,6 +57,8 @@ pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
     let mut volatile = false;
     let mut alignstack = false;
     let mut dialect = ast::AsmAtt;
+    let mut asm_sp = sp;
+    let mut names = Vec::new();
 
     let mut state = Asm;
 
@@ -70,6 +73,7 @@ pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
                 };
                 asm = s;
                 asm_str_style = Some(style);
+                asm_sp = p.last_span;
             }
             Outputs => {
                 while p.token != token::EOF &&
@@ -80,6 +84,8 @@ pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
                         p.eat(&token::COMMA);
                     }
 
+                    parse_operand_name(cx, &mut p, &mut names, outputs.len());
+
                     let (constraint, _str_style) = p.parse_str();
 
                     let span = p.last_span;
@@ -121,6 +127,8 @@ pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
                         p.eat(&token::COMMA);
                     }
 
+                    parse_operand_name(cx, &mut p, &mut names, outputs.len() + inputs.len());
+
                     let (constraint, _str_style) = p.parse_str();
 
                     if constraint.get().starts_with("=") {
@@ -199,6 +207,11 @@ pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
         }
     }
 
+    // Read+write outputs get an extra input operand each; see `trans::asm`.
+    let num_operands = outputs.len() + inputs.len() +
+                       outputs.iter().filter(|&&(_, _, is_rw)| is_rw).count();
+    let asm = expand_operand_refs(cx, asm_sp, asm.get(), names.as_slice(), num_operands);
+
     let expn_id = cx.codemap().record_expansion(codemap::ExpnInfo {
         call_site: sp,
         callee: codemap::NameAndSpan {
@@ -211,7 +224,7 @@ pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
     MacExpr::new(P(ast::Expr {
         id: ast::DUMMY_NODE_ID,
         node: ast::ExprInlineAsm(ast::InlineAsm {
-            asm: token::intern_and_get_ident(asm.get()),
+            asm: token::intern_and_get_ident(asm.as_slice()),
             asm_str_style: asm_str_style.unwrap(),
             outputs: outputs,
             inputs: inputs,
@@ -224,3 +237,123 @@ pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
         span: sp
     }))
 }
+
+/// Parses the optional name of an operand, as in `[name] "r"(expr)`,
+/// recording it against the operand's position.
+fn parse_operand_name(cx: &ExtCtxt, p: &mut Parser,
+                      names: &mut Vec<(ast::Name, uint)>, index: uint) {
+    if p.token != token::LBRACKET {
+        return;
+    }
+    p.bump();
+    let span = p.span;
+    let ident = p.parse_ident();
+    p.expect(&token::RBRACKET);
+
+    if names.iter().any(|&(name, _)| name == ident.name) {
+        cx.span_err(span, format!("duplicate operand name `{}`",
+                                  token::get_ident(ident)).as_slice());
+    } else {
+        names.push((ident.name, index));
+    }
+}
+
+/// Rewrites references to named operands in the assembly template,
+/// `$[name]` and `${name:modifier}`, into the positional form LLVM
+/// expects, and checks that positional references name an operand.
+fn expand_operand_refs(cx: &ExtCtxt, sp: Span, template: &str,
+                       names: &[(ast::Name, uint)], num_operands: uint) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut chars = template.chars().peekable();
+
+    loop {
+        match chars.next() {
+            Some('$') => {}
+            Some(c) => {
+                out.push_char(c);
+                continue;
+            }
+            None => break
+        }
+
+        match chars.peek().map(|c| *c) {
+            Some('$') => {
+                chars.next();
+                out.push_str("$$");
+            }
+            Some(open) if open == '[' || open == '{' => {
+                chars.next();
+                let close = if open == '[' { ']' } else { '}' };
+                let mut reference = String::new();
+                let mut terminated = false;
+                for c in chars.by_ref() {
+                    if c == close {
+                        terminated = true;
+                        break;
+                    }
+                    reference.push_char(c);
+                }
+                if !terminated {
+                    cx.span_err(sp, format!("unterminated operand reference `${}{}`",
+                                            open, reference).as_slice());
+                    break;
+                }
+
+                // `${operand:modifier}` passes the modifier through.
+                let (operand, modifier) = match reference.as_slice().find(':') {
+                    Some(i) if open == '{' => {
+                        (reference.as_slice().slice_to(i), reference.as_slice().slice_from(i))
+                    }
+                    _ => (reference.as_slice(), "")
+                };
+                let index = operand_index(cx, sp, operand, names, num_operands);
+                if open == '[' {
+                    out.push_str(format!("${}", index).as_slice());
+                } else {
+                    out.push_str(format!("${{{}{}}}", index, modifier).as_slice());
+                }
+            }
+            Some(d) if d.is_digit_radix(10) => {
+                let mut digits = String::new();
+                loop {
+                    match chars.peek().map(|c| *c) {
+                        Some(d) if d.is_digit_radix(10) => digits.push_char(d),
+                        _ => break
+                    }
+                    chars.next();
+                }
+                let index = operand_index(cx, sp, digits.as_slice(), names, num_operands);
+                out.push_str(format!("${}", index).as_slice());
+            }
+            _ => out.push_char('$')
+        }
+    }
+
+    out
+}
+
+fn operand_index(cx: &ExtCtxt, sp: Span, operand: &str,
+                 names: &[(ast::Name, uint)], num_operands: uint) -> uint {
+    match from_str::<uint>(operand) {
+        Some(index) => {
+            if index >= num_operands {
+                cx.span_err(sp, format!("invalid reference to operand `${}`: \
+                                         the assembly has {} operand{}",
+                                        index, num_operands,
+                                        if num_operands == 1 { "" } else { "s" }).as_slice());
+            }
+            index
+        }
+        None => {
+            let name = token::intern(operand);
+            match names.iter().find(|&&(n, _)| n == name) {
+                Some(&(_, index)) => index,
+                None => {
+                    cx.span_err(sp, format!("there is no operand named `{}`",
+                                            operand).as_slice());
+                    0
+                }
+            }
+        }
+    }
+}
@@ -461,6 +461,10 @@ pub struct ExtCtxt<'a> {
     pub mod_path: Vec<ast::Ident> ,
     pub trace_mac: bool,
     pub exported_macros: Vec<P<ast::Item>>,
+    /// The path from the crate root to the `extern crate` item of the crate
+    /// that the macro definitions being expanded were imported from. It is
+    /// empty if that item is inside a block, where no path can name it.
+    pub imported_from: Option<Vec<ast::Ident>>,
 
     pub syntax_env: SyntaxEnv,
     pub recursion_count: uint,
@@ -478,6 +482,7 @@ impl<'a> ExtCtxt<'a> {
             ecfg: ecfg,
             trace_mac: false,
             exported_macros: Vec::new(),
+            imported_from: None,
             syntax_env: env,
             recursion_count: 0,
         }
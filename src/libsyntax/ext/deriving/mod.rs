@@ -19,8 +19,9 @@ library.
 */
 
 use ast::{Item, MetaItem, MetaList, MetaNameValue, MetaWord};
-use ext::base::ExtCtxt;
+use ext::base::{ExtCtxt, Decorator};
 use codemap::Span;
+use parse::token;
 use ptr::P;
 
 pub mod bounds;
@@ -97,10 +98,31 @@ pub fn expand_meta_deriving(cx: &mut ExtCtxt,
                             "Copy" => expand!(bounds::expand_deriving_bound),
 
                             ref tname => {
-                                cx.span_err(titem.span,
-                                            format!("unknown `deriving` \
-                                                     trait: `{}`",
-                                                    *tname).as_slice());
+                                // Traits that plugins know how to derive are
+                                // registered as decorators named `deriving_Trait`.
+                                let name = token::intern(plugin_deriving_name(*tname)
+                                                             .as_slice());
+                                match cx.syntax_env.find(&name) {
+                                    Some(ext) => match *ext {
+                                        Decorator(ref dec) => {
+                                            dec.expand(cx, titem.span, &**titem, item,
+                                                       |i| push(i))
+                                        }
+                                        _ => {
+                                            cx.span_err(titem.span,
+                                                        format!("`{}` is not a `deriving` \
+                                                                 extension",
+                                                                token::get_name(name))
+                                                            .as_slice());
+                                        }
+                                    },
+                                    None => {
+                                        cx.span_err(titem.span,
+                                                    format!("unknown `deriving` \
+                                                             trait: `{}`",
+                                                            *tname).as_slice());
+                                    }
+                                }
                             }
                         };
                     }
@@ -109,3 +131,9 @@ pub fn expand_meta_deriving(cx: &mut ExtCtxt,
         }
     }
 }
+
+/// The name under which a plugin registers its expander for
+/// `#[deriving(Trait)]`; see `rustc::plugin::Registry::register_deriving`.
+pub fn plugin_deriving_name(trait_name: &str) -> String {
+    format!("deriving_{}", trait_name)
+}
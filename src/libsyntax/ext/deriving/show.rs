@@ -11,7 +11,6 @@
 use ast;
 use ast::{MetaItem, Item, Expr,};
 use codemap::Span;
-use ext::format;
 use ext::base::ExtCtxt;
 use ext::build::AstBuilder;
 use ext::deriving::generic::*;
@@ -19,8 +18,6 @@ use ext::deriving::generic::ty::*;
 use parse::token;
 use ptr::P;
 
-use std::collections::HashMap;
-
 pub fn expand_deriving_show(cx: &mut ExtCtxt,
                             span: Span,
                             mitem: &MetaItem,
@@ -53,12 +50,14 @@ pub fn expand_deriving_show(cx: &mut ExtCtxt,
     trait_def.expand(cx, mitem, item, push)
 }
 
-/// We construct a format string and then defer to std::fmt, since that
-/// knows what's up with formatting and so on.
+/// We call the `show_struct` or `show_tuple` builder of the formatter and
+/// feed it the fields, since that knows how to lay them out, on one line or
+/// on several when the alternate flag is given.
 fn show_substructure(cx: &mut ExtCtxt, span: Span,
                      substr: &Substructure) -> P<Expr> {
-    // build `<name>`, `<name>({}, {}, ...)` or `<name> { <field>: {},
-    // <field>: {}, ... }` based on the "shape".
+    // build `fmt.show_tuple("<name>").field(..)...finish()` or
+    // `fmt.show_struct("<name>").field("<field>", ..)...finish()`
+    // based on the "shape".
     //
     // Easy start: they all start with the name.
     let name = match *substr.fields {
@@ -68,69 +67,35 @@ fn show_substructure(cx: &mut ExtCtxt, span: Span,
             cx.span_bug(span, "nonsensical .fields in `#[deriving(Show)]`")
         }
     };
+    let name = cx.expr_str(span, token::get_ident(name));
+    let formatter = substr.nonself_args[0].clone();
 
-    let mut format_string = String::from_str(token::get_ident(name).get());
-    // the internal fields we're actually formatting
-    let mut exprs = Vec::new();
-
-    // Getting harder... making the format string:
-    match *substr.fields {
-        // unit struct/nullary variant: no work necessary!
-        Struct(ref fields) if fields.len() == 0 => {}
-        EnumMatching(_, _, ref fields) if fields.len() == 0 => {}
-
+    let builder = match *substr.fields {
         Struct(ref fields) | EnumMatching(_, _, ref fields) => {
-            if fields.get(0).name.is_none() {
-                // tuple struct/"normal" variant
-
-                format_string.push_str("(");
-
-                for (i, field) in fields.iter().enumerate() {
-                    if i != 0 { format_string.push_str(", "); }
-
-                    format_string.push_str("{}");
-
-                    exprs.push(field.self_.clone());
-                }
-
-                format_string.push_str(")");
+            if fields.is_empty() || fields.get(0).name.is_none() {
+                // unit struct/nullary variant or tuple struct/"normal" variant
+                let show_tuple = cx.ident_of("show_tuple");
+                let builder = cx.expr_method_call(span, formatter, show_tuple, vec!(name));
+
+                let field = cx.ident_of("field");
+                fields.iter().fold(builder, |builder, f| {
+                    cx.expr_method_call(f.span, builder, field, vec!(f.self_.clone()))
+                })
             } else {
                 // normal struct/struct variant
+                let show_struct = cx.ident_of("show_struct");
+                let builder = cx.expr_method_call(span, formatter, show_struct, vec!(name));
 
-                format_string.push_str(" {{");
-
-                for (i, field) in fields.iter().enumerate() {
-                    if i != 0 { format_string.push_str(","); }
-
-                    let name = token::get_ident(field.name.unwrap());
-                    format_string.push_str(" ");
-                    format_string.push_str(name.get());
-                    format_string.push_str(": {}");
-
-                    exprs.push(field.self_.clone());
-                }
-
-                format_string.push_str(" }}");
+                let field = cx.ident_of("field");
+                fields.iter().fold(builder, |builder, f| {
+                    let name = cx.expr_str(f.span, token::get_ident(f.name.unwrap()));
+                    cx.expr_method_call(f.span, builder, field, vec!(name, f.self_.clone()))
+                })
             }
         }
         _ => unreachable!()
-    }
-
-    // AST construction!
-    // we're basically calling
-    //
-    // format_arg_method!(fmt, write_fmt, "<format_string>", exprs...)
-    //
-    // but doing it directly via ext::format.
-    let formatter = substr.nonself_args[0].clone();
-
-    let meth = cx.ident_of("write_fmt");
-    let s = token::intern_and_get_ident(format_string.as_slice());
-    let format_string = cx.expr_str(span, s);
+    };
 
-    // phew, not our responsibility any more!
-    format::expand_preparsed_format_args(cx, span,
-                                         format::MethodCall(formatter, meth),
-                                         format_string, exprs, Vec::new(),
-                                         HashMap::new())
+    let finish = cx.ident_of("finish");
+    cx.expr_method_call(span, builder, finish, Vec::new())
 }
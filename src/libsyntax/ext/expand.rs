@@ -67,6 +67,41 @@ pub fn expand_expr(e: P<ast::Expr>, fld: &mut MacroExpander) -> P<ast::Expr> {
             fld.cx.expr(span, ast::ExprWhile(cond, body, opt_ident))
         }
 
+        // Desugar ExprWhileLet
+        // From: `[opt_ident]: while let <pat> = <expr> <body>`
+        ast::ExprWhileLet(pat, expr, body, opt_ident) => {
+            // to:
+            //
+            //   [opt_ident]: loop {
+            //     match <expr> {
+            //       <pat> => <body>,
+            //       _ => break
+            //     }
+            //   }
+
+            // `<pat> => <body>`
+            let pat_arm = {
+                let body_expr = fld.cx.expr_block(body);
+                fld.cx.arm(pat.span, vec![pat], body_expr)
+            };
+
+            // `_ => break`
+            let break_arm = {
+                let pat_under = fld.cx.pat_wild(span);
+                let break_expr = fld.cx.expr(span, ast::ExprBreak(None));
+                fld.cx.arm(span, vec![pat_under], break_expr)
+            };
+
+            let arms = vec![pat_arm, break_arm];
+            let match_expr = fld.cx.expr(span,
+                                         ast::ExprMatch(expr, arms, ast::MatchWhileLetDesugar));
+
+            // `[opt_ident]: loop { ... }`
+            let loop_block = fld.cx.block_expr(match_expr);
+            let loop_expr = fld.cx.expr(span, ast::ExprLoop(loop_block, opt_ident));
+            fld.fold_expr(loop_expr)
+        }
+
         // Desugar ExprIfLet
         // From: `if let <pat> = <expr> <body> [<elseopt>]`
         ast::ExprIfLet(pat, expr, body, mut elseopt) => {
@@ -1085,6 +1120,9 @@ impl ExpansionConfig {
 
 pub struct ExportedMacros {
     pub crate_name: Ident,
+    /// The path from the crate root to the `extern crate` item, ending in
+    /// `crate_name`, or `None` if the item is inside a block.
+    pub crate_path: Option<Vec<Ident>>,
     pub macros: Vec<String>,
 }
 
@@ -1099,10 +1137,13 @@ pub fn expand_crate(parse_sess: &parse::ParseSess,
         cx: &mut cx,
     };
 
-    for ExportedMacros { crate_name, macros } in imported_macros.into_iter() {
+    for exported in imported_macros.into_iter() {
+        let ExportedMacros { crate_name, crate_path, macros } = exported;
         let name = format!("<{} macros>", token::get_ident(crate_name))
             .into_string();
 
+        // `$crate` in these macros refers to the crate they came from.
+        expander.cx.imported_from = Some(crate_path.unwrap_or(Vec::new()));
         for source in macros.into_iter() {
             let item = parse::parse_item_from_source_str(name.clone(),
                                                          source,
@@ -1111,6 +1152,7 @@ pub fn expand_crate(parse_sess: &parse::ParseSess,
                     .expect("expected a serialized item");
             expand_item_mac(item, &mut expander);
         }
+        expander.cx.imported_from = None;
     }
 
     for (name, extension) in user_exts.into_iter() {
@@ -85,7 +85,7 @@ use parse::lexer::*; //resolve bug?
 use parse::ParseSess;
 use parse::attr::ParserAttr;
 use parse::parser::{LifetimeAndTypesWithoutColons, Parser};
-use parse::token::{Token, EOF, Nonterminal};
+use parse::token::{Token, EOF, Nonterminal, keywords};
 use parse::token;
 use ptr::P;
 
@@ -100,6 +100,7 @@ nonempty body. */
 pub struct MatcherPos {
     elts: Vec<ast::Matcher> , // maybe should be <'>? Need to understand regions.
     sep: Option<Token>,
+    repeats: bool,
     idx: uint,
     up: Option<Box<MatcherPos>>,
     matches: Vec<Vec<Rc<NamedMatch>>>,
@@ -136,6 +137,7 @@ pub fn initial_matcher_pos(ms: Vec<Matcher> , sep: Option<Token>, lo: BytePos)
     box MatcherPos {
         elts: ms,
         sep: sep,
+        repeats: false,
         idx: 0u,
         up: None,
         matches: matches,
@@ -301,6 +303,7 @@ pub fn parse(sess: &ParseSess,
 
                     // the *_t vars are workarounds for the lack of unary move
                     match ei.sep {
+                      _ if !ei.repeats => {} // a `$(...)?` matches at most once
                       Some(ref t) if idx == len => { // we need a separator
                         // i'm conflicted about whether this should be hygienic....
                         // though in this case, if the separators are never legal
@@ -323,9 +326,9 @@ pub fn parse(sess: &ParseSess,
             } else {
                 match ei.elts.get(idx).node.clone() {
                   /* need to descend into sequence */
-                  MatchSeq(ref matchers, ref sep, zero_ok,
+                  MatchSeq(ref matchers, ref sep, op,
                            match_idx_lo, match_idx_hi) => {
-                    if zero_ok {
+                    if op.zero_ok() {
                         let mut new_ei = ei.clone();
                         new_ei.idx += 1u;
                         //we specifically matched zero repeats.
@@ -343,6 +346,7 @@ pub fn parse(sess: &ParseSess,
                     cur_eis.push(box MatcherPos {
                         elts: (*matchers).clone(),
                         sep: (*sep).clone(),
+                        repeats: op.repeats(),
                         idx: 0u,
                         up: Some(ei_t),
                         matches: matches,
@@ -454,6 +458,43 @@ pub fn parse_nt(p: &mut Parser, name: &str) -> Nonterminal {
                              token_str.as_slice())).as_slice())
         }
       },
+      // like ident, this is a single token
+      "lifetime" => match p.token {
+        token::LIFETIME(name) => { p.bump(); token::NtLifetime(name) }
+        _ => {
+            let token_str = token::to_string(&p.token);
+            p.fatal((format!("expected lifetime, found {}",
+                             token_str.as_slice())).as_slice())
+        }
+      },
+      // literals and visibilities are kept as tokens, so that they can be
+      // used in any position: patterns, expressions, attributes, items...
+      "literal" => {
+        let mut tts = Vec::new();
+        if p.token == token::BINOP(token::MINUS) {
+            let sp = p.span;
+            tts.push(ast::TTTok(sp, p.bump_and_get()));
+        }
+        if token::is_lit(&p.token) || p.is_keyword(keywords::True) ||
+                p.is_keyword(keywords::False) {
+            let sp = p.span;
+            tts.push(ast::TTTok(sp, p.bump_and_get()));
+        } else {
+            let token_str = token::to_string(&p.token);
+            p.fatal((format!("expected literal, found {}",
+                             token_str.as_slice())).as_slice())
+        }
+        token::NtLiteral(tts)
+      }
+      // matches nothing if the item is private
+      "vis" => {
+        let mut tts = Vec::new();
+        if p.is_keyword(keywords::Pub) {
+            let sp = p.span;
+            tts.push(ast::TTTok(sp, p.bump_and_get()));
+        }
+        token::NtVis(tts)
+      }
       "path" => {
         token::NtPath(box p.parse_path(LifetimeAndTypesWithoutColons).path)
       }
@@ -9,6 +9,7 @@
 // except according to those terms.
 
 use ast::{Ident, Matcher_, Matcher, MatchTok, MatchNonterminal, MatchSeq, TTDelim};
+use ast::{TokenTree, TTTok, TTSeq, TTNonterminal};
 use ast;
 use codemap::{Span, Spanned, DUMMY_SP};
 use ext::base::{ExtCtxt, MacResult, MacroDef};
@@ -16,11 +17,12 @@ use ext::base::{NormalTT, TTMacroExpander};
 use ext::tt::macro_parser::{Success, Error, Failure};
 use ext::tt::macro_parser::{NamedMatch, MatchedSeq, MatchedNonterminal};
 use ext::tt::macro_parser::{parse, parse_or_else};
+use ext::tt::transcribe::is_crate_var;
 use parse::lexer::new_tt_reader;
 use parse::parser::Parser;
 use parse::attr::ParserAttr;
 use parse::token::{special_idents, gensym_ident};
-use parse::token::{FAT_ARROW, SEMI, NtMatchers, NtTT, EOF};
+use parse::token::{FAT_ARROW, SEMI, NtMatchers, NtTT, EOF, keywords};
 use parse::token;
 use print;
 use ptr::P;
@@ -28,6 +30,8 @@ use ptr::P;
 use util::small_vector::SmallVector;
 
 use std::cell::RefCell;
+use std::cmp;
+use std::collections::HashMap;
 use std::rc::Rc;
 
 struct ParserAnyMacro<'a> {
@@ -110,6 +114,7 @@ impl<'a> MacResult for ParserAnyMacro<'a> {
 
 struct MacroRulesMacroExpander {
     name: Ident,
+    imported_from: Option<Vec<Ident>>,
     lhses: Vec<Rc<NamedMatch>>,
     rhses: Vec<Rc<NamedMatch>>,
 }
@@ -123,6 +128,7 @@ impl TTMacroExpander for MacroRulesMacroExpander {
         generic_extension(cx,
                           sp,
                           self.name,
+                          self.imported_from.clone(),
                           arg,
                           self.lhses.as_slice(),
                           self.rhses.as_slice())
@@ -138,10 +144,22 @@ impl MacResult for MacroRulesDefiner {
     }
 }
 
+/// Whether a macro rhs uses `$crate` anywhere.
+fn uses_crate_var(tts: &[TokenTree]) -> bool {
+    tts.iter().any(|tt| {
+        match *tt {
+            TTNonterminal(_, name) => is_crate_var(name),
+            TTDelim(ref tts) | TTSeq(_, ref tts, _, _) => uses_crate_var(tts.as_slice()),
+            TTTok(..) => false,
+        }
+    })
+}
+
 /// Given `lhses` and `rhses`, this is the new macro we create
 fn generic_extension<'cx>(cx: &'cx ExtCtxt,
                           sp: Span,
                           name: Ident,
+                          imported_from: Option<Vec<Ident>>,
                           arg: &[ast::TokenTree],
                           lhses: &[Rc<NamedMatch>],
                           rhses: &[Rc<NamedMatch>])
@@ -165,6 +183,7 @@ fn generic_extension<'cx>(cx: &'cx ExtCtxt,
           MatchedNonterminal(NtMatchers(ref mtcs)) => {
             // `None` is because we're not interpolating
             let arg_rdr = new_tt_reader(&cx.parse_sess().span_diagnostic,
+                                        None,
                                         None,
                                         arg.iter()
                                            .map(|x| (*x).clone())
@@ -188,9 +207,17 @@ fn generic_extension<'cx>(cx: &'cx ExtCtxt,
                     },
                     _ => cx.span_bug(sp, "bad thing in rhs")
                 };
+                match imported_from {
+                    Some(ref path) if path.is_empty() && uses_crate_var(rhs.as_slice()) => {
+                        cx.span_err(sp, "`$crate` cannot name a crate whose `extern crate` \
+                                         item is inside a block");
+                    }
+                    _ => {}
+                }
                 // rhs has holes ( `$id` and `$(...)` that need filled)
                 let trncbr = new_tt_reader(&cx.parse_sess().span_diagnostic,
                                            Some(named_matches),
+                                           imported_from.clone(),
                                            rhs);
                 let p = Parser::new(cx.parse_sess(), cx.cfg(), box trncbr);
                 // Let the context choose how to interpret the result.
@@ -239,14 +266,16 @@ pub fn add_new_extension<'cx>(cx: &'cx mut ExtCtxt,
         ms(MatchSeq(vec!(
             ms(MatchNonterminal(lhs_nm, special_idents::matchers, 0u)),
             ms(MatchTok(FAT_ARROW)),
-            ms(MatchNonterminal(rhs_nm, special_idents::tt, 1u))), Some(SEMI), false, 0u, 2u)),
+            ms(MatchNonterminal(rhs_nm, special_idents::tt, 1u))),
+            Some(SEMI), ast::OneOrMore, 0u, 2u)),
         //to phase into semicolon-termination instead of
         //semicolon-separation
-        ms(MatchSeq(vec!(ms(MatchTok(SEMI))), None, true, 2u, 2u)));
+        ms(MatchSeq(vec!(ms(MatchTok(SEMI))), None, ast::ZeroOrMore, 2u, 2u)));
 
 
     // Parse the macro_rules! invocation (`none` is for no interpolations):
     let arg_reader = new_tt_reader(&cx.parse_sess().span_diagnostic,
+                                   None,
                                    None,
                                    arg.clone());
     let argument_map = parse_or_else(cx.parse_sess(),
@@ -265,8 +294,13 @@ pub fn add_new_extension<'cx>(cx: &'cx mut ExtCtxt,
         _ => cx.span_bug(sp, "wrong-structured rhs")
     };
 
+    for (lhs, rhs) in lhses.iter().zip(rhses.iter()) {
+        check_repetition_depths(cx, &**lhs, &**rhs);
+    }
+
     let exp = box MacroRulesMacroExpander {
         name: name,
+        imported_from: cx.imported_from.clone(),
         lhses: lhses,
         rhses: rhses,
     };
@@ -278,3 +312,78 @@ pub fn add_new_extension<'cx>(cx: &'cx mut ExtCtxt,
         })
     } as Box<MacResult+'cx>
 }
+
+/// Checks that every macro variable used in `rhs` is used inside at least as
+/// many `$(...)` sequences as it is matched in by `lhs`, and that every
+/// `$(...)` of `rhs` has a variable to repeat over, so that macro writers hear
+/// about these mistakes when the macro is defined rather than when it is
+/// first used. These are only warnings for now, since such arms used to be
+/// accepted as long as they were never expanded.
+fn check_repetition_depths(cx: &ExtCtxt, lhs: &NamedMatch, rhs: &NamedMatch) {
+    fn lhs_depths(ms: &[Matcher], depth: uint, depths: &mut HashMap<Ident, uint>) {
+        for m in ms.iter() {
+            match m.node {
+                MatchTok(_) => {}
+                MatchSeq(ref ms, _, _, _, _) => lhs_depths(ms.as_slice(), depth + 1, depths),
+                MatchNonterminal(name, _, _) => { depths.insert(name, depth); }
+            }
+        }
+    }
+
+    // Returns the deepest depth any variable in `tts` is matched at, or
+    // `None` if `tts` uses a variable which `lhs` doesn't bind; that is
+    // reported when the macro is expanded.
+    fn check_tts(cx: &ExtCtxt, tts: &[TokenTree], depth: uint,
+                 depths: &HashMap<Ident, uint>) -> Option<uint> {
+        let mut deepest = Some(0u);
+        for tt in tts.iter() {
+            let tt_deepest = match *tt {
+                TTTok(..) => Some(0u),
+                TTDelim(ref tts) => check_tts(cx, tts.as_slice(), depth, depths),
+                TTNonterminal(_, name) if name.name == keywords::Crate.to_name() => Some(0u),
+                TTNonterminal(sp, name) => match depths.find(&name) {
+                    Some(&d) => {
+                        if d > depth {
+                            cx.span_warn(sp, format!("variable `{}` is still repeating at \
+                                                      this depth",
+                                                     token::get_ident(name)).as_slice());
+                        }
+                        Some(d)
+                    }
+                    None => None
+                },
+                TTSeq(sp, ref tts, _, _) => {
+                    let inner = check_tts(cx, tts.as_slice(), depth + 1, depths);
+                    match inner {
+                        Some(d) if d <= depth => {
+                            cx.span_warn(sp, "attempted to repeat an expression containing \
+                                              no syntax variables matched as repeating at \
+                                              this depth");
+                        }
+                        _ => {}
+                    }
+                    inner
+                }
+            };
+            deepest = match (deepest, tt_deepest) {
+                (Some(a), Some(b)) => Some(cmp::max(a, b)),
+                _ => None
+            };
+        }
+        deepest
+    }
+
+    let mut depths = HashMap::new();
+    match *lhs {
+        MatchedNonterminal(NtMatchers(ref mtcs)) => lhs_depths(mtcs.as_slice(), 0, &mut depths),
+        _ => cx.bug("non-matcher found in parsed lhses")
+    }
+    match *rhs {
+        MatchedNonterminal(NtTT(ref tt)) => match **tt {
+            TTDelim(ref tts) => { check_tts(cx, tts.as_slice(), 0, &depths); }
+            // reported when the macro is expanded
+            _ => {}
+        },
+        _ => cx.bug("bad thing in rhs")
+    }
+}
@@ -13,7 +13,8 @@ use ast::{TokenTree, TTDelim, TTTok, TTSeq, TTNonterminal, Ident};
 use codemap::{Span, DUMMY_SP};
 use diagnostic::SpanHandler;
 use ext::tt::macro_parser::{NamedMatch, MatchedSeq, MatchedNonterminal};
-use parse::token::{EOF, INTERPOLATED, IDENT, Token, NtIdent};
+use parse::token::{EOF, INTERPOLATED, IDENT, LIFETIME, Token};
+use parse::token::{NtIdent, NtLifetime, NtLiteral, NtVis};
 use parse::token;
 use parse::lexer::TokenAndSpan;
 
@@ -38,6 +39,10 @@ pub struct TtReader<'a> {
     interpolations: HashMap<Ident, Rc<NamedMatch>>,
     repeat_idx: Vec<uint>,
     repeat_len: Vec<uint>,
+    /* for `$crate` in macros imported from another crate */
+    imported_from: Option<Vec<Ident>>,
+    /* the rest of an expanded `$crate`, last token first */
+    crate_path_next: Vec<(Token, Span)>,
     /* cached: */
     pub cur_tok: Token,
     pub cur_span: Span,
@@ -45,9 +50,12 @@ pub struct TtReader<'a> {
 
 /// This can do Macro-By-Example transcription. On the other hand, if
 /// `src` contains no `TTSeq`s and `TTNonterminal`s, `interp` can (and
-/// should) be none.
+/// should) be none. `imported_from` is the path from the crate root to the
+/// `extern crate` item of the crate defining the macro, if it is not the
+/// current crate; `$crate` expands to that path.
 pub fn new_tt_reader<'a>(sp_diag: &'a SpanHandler,
                          interp: Option<HashMap<Ident, Rc<NamedMatch>>>,
+                         imported_from: Option<Vec<Ident>>,
                          src: Vec<ast::TokenTree> )
                          -> TtReader<'a> {
     let mut r = TtReader {
@@ -64,6 +72,8 @@ pub fn new_tt_reader<'a>(sp_diag: &'a SpanHandler,
         },
         repeat_idx: Vec::new(),
         repeat_len: Vec::new(),
+        imported_from: imported_from,
+        crate_path_next: Vec::new(),
         /* dummy values, never read: */
         cur_tok: EOF,
         cur_span: DUMMY_SP,
@@ -116,7 +126,7 @@ fn lis_merge(lhs: LockstepIterSize, rhs: LockstepIterSize) -> LockstepIterSize {
                 let l_n = token::get_ident(l_id);
                 let r_n = token::get_ident(r_id);
                 LisContradiction(format!("inconsistent lockstep iteration: \
-                                          '{}' has {} items, but '{}' has {}",
+                                          `{}` has {} items, but `{}` has {}",
                                           l_n, l_len, r_n, r_len).to_string())
             }
         }
@@ -131,6 +141,7 @@ fn lockstep_iter_size(t: &TokenTree, r: &TtReader) -> LockstepIterSize {
             })
         }
         TTTok(..) => LisUnconstrained,
+        TTNonterminal(_, name) if is_crate_var(name) => LisUnconstrained,
         TTNonterminal(_, name) => match *lookup_cur_matched(r, name) {
             MatchedNonterminal(_) => LisUnconstrained,
             MatchedSeq(ref ads, _) => LisConstraint(ads.len(), name)
@@ -138,6 +149,11 @@ fn lockstep_iter_size(t: &TokenTree, r: &TtReader) -> LockstepIterSize {
     }
 }
 
+/// Whether a `TTNonterminal` is `$crate` rather than a macro variable.
+pub fn is_crate_var(name: Ident) -> bool {
+    name.name == token::keywords::Crate.to_name()
+}
+
 /// Return the next token from the TtReader.
 /// EFFECT: advances the reader's token field
 pub fn tt_next_token(r: &mut TtReader) -> TokenAndSpan {
@@ -146,6 +162,15 @@ pub fn tt_next_token(r: &mut TtReader) -> TokenAndSpan {
         tok: r.cur_tok.clone(),
         sp: r.cur_span.clone(),
     };
+    match r.crate_path_next.pop() {
+        Some((tok, sp)) => {
+            // the rest of `$crate` in an imported macro: `::a::b::name`
+            r.cur_span = sp;
+            r.cur_tok = tok;
+            return ret_val;
+        }
+        None => {}
+    }
     loop {
         let should_pop = match r.stack.last() {
             None => {
@@ -212,9 +237,9 @@ pub fn tt_next_token(r: &mut TtReader) -> TokenAndSpan {
                 r.stack.last_mut().unwrap().idx += 1;
                 return ret_val;
             }
-            TTSeq(sp, tts, sep, zerok) => {
+            TTSeq(sp, tts, sep, op) => {
                 // FIXME(pcwalton): Bad copy.
-                match lockstep_iter_size(&TTSeq(sp, tts.clone(), sep.clone(), zerok), r) {
+                match lockstep_iter_size(&TTSeq(sp, tts.clone(), sep.clone(), op), r) {
                     LisUnconstrained => {
                         r.sp_diag.span_fatal(
                             sp.clone(), /* blame macro writer */
@@ -226,9 +251,17 @@ pub fn tt_next_token(r: &mut TtReader) -> TokenAndSpan {
                             // FIXME #2887 blame macro invoker instead
                             r.sp_diag.span_fatal(sp.clone(), msg.as_slice());
                         }
-                    LisConstraint(len, _) => {
+                    LisConstraint(len, name) => {
+                        if len > 1 && !op.repeats() {
+                            // FIXME #2887 blame invoker
+                            r.sp_diag.span_fatal(
+                                sp.clone(),
+                                format!("`{}` repeats {} times, but `$(...)?` allows \
+                                         at most one",
+                                        token::get_ident(name), len).as_slice());
+                        }
                         if len == 0 {
-                            if !zerok {
+                            if !op.zero_ok() {
                                 // FIXME #2887 blame invoker
                                 r.sp_diag.span_fatal(sp.clone(),
                                                      "this must repeat at least once");
@@ -248,6 +281,23 @@ pub fn tt_next_token(r: &mut TtReader) -> TokenAndSpan {
                     }
                 }
             }
+            TTNonterminal(sp, ident) if is_crate_var(ident) => {
+                r.stack.last_mut().unwrap().idx += 1;
+                let path = r.imported_from.clone().unwrap_or(Vec::new());
+                for &id in path.iter().rev() {
+                    r.crate_path_next.push((IDENT(id, false), sp));
+                    r.crate_path_next.push((token::MOD_SEP, sp));
+                }
+                match r.crate_path_next.pop() {
+                    Some((tok, sp)) => {
+                        r.cur_span = sp;
+                        r.cur_tok = tok;
+                        return ret_val;
+                    }
+                    // In the defining crate, `$crate::foo` is just `::foo`.
+                    None => return tt_next_token(r)
+                }
+            }
             // FIXME #2887: think about span stuff here
             TTNonterminal(sp, ident) => {
                 r.stack.last_mut().unwrap().idx += 1;
@@ -260,6 +310,25 @@ pub fn tt_next_token(r: &mut TtReader) -> TokenAndSpan {
                         r.cur_tok = IDENT(sn,b);
                         return ret_val;
                     }
+                    MatchedNonterminal(NtLifetime(name)) => {
+                        r.cur_span = sp;
+                        r.cur_tok = LIFETIME(name);
+                        return ret_val;
+                    }
+                    /* splice the tokens in; `vis` may have none */
+                    MatchedNonterminal(NtLiteral(ref tts)) |
+                    MatchedNonterminal(NtVis(ref tts)) => {
+                        // Popping the spliced frame moves past the
+                        // nonterminal, so don't do it twice.
+                        r.stack.last_mut().unwrap().idx -= 1;
+                        r.stack.push(TtFrame {
+                            forest: Rc::new(tts.clone()),
+                            idx: 0,
+                            dotdotdoted: false,
+                            sep: None
+                        });
+                        return tt_next_token(r);
+                    }
                     MatchedNonterminal(ref other_whole_nt) => {
                         // FIXME(pcwalton): Bad copy.
                         r.cur_span = sp;
@@ -268,8 +337,8 @@ pub fn tt_next_token(r: &mut TtReader) -> TokenAndSpan {
                     }
                     MatchedSeq(..) => {
                         r.sp_diag.span_fatal(
-                            r.cur_span, /* blame the macro writer */
-                            format!("variable '{}' is still repeating at this depth",
+                            sp, /* blame the macro writer */
+                            format!("variable `{}` is still repeating at this depth",
                                     token::get_ident(ident)).as_slice());
                     }
                 }
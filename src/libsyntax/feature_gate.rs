@@ -73,6 +73,8 @@ static KNOWN_FEATURES: &'static [(&'static str, Status)] = &[
     ("slicing_syntax", Active),
 
     ("if_let", Active),
+    ("while_let", Active),
+    ("specialization", Active),
 
     // if you change this list without updating src/doc/reference.md, cmr will be sad
 
@@ -246,6 +248,13 @@ impl<'a, 'v> Visitor<'v> for Context<'a> {
                                        removed in the future");
                 }
 
+                if attr::contains_name(i.attrs.as_slice(),
+                                       "default_impl") {
+                    self.gate_feature("specialization",
+                                      i.span,
+                                      "`#[default_impl]` is experimental");
+                }
+
                 for item in items.iter() {
                     match *item {
                         ast::MethodImplItem(_) => {}
@@ -351,6 +360,10 @@ impl<'a, 'v> Visitor<'v> for Context<'a> {
                 self.gate_feature("if_let", e.span,
                                   "`if let` syntax is experimental");
             }
+            ast::ExprWhileLet(..) => {
+                self.gate_feature("while_let", e.span,
+                                  "`while let` syntax is experimental");
+            }
             ast::ExprSlice(..) => {
                 self.gate_feature("slicing_syntax",
                                   e.span,
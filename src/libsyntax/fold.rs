@@ -572,11 +572,11 @@ pub fn noop_fold_tt<T: Folder>(tt: &TokenTree, fld: &mut T) -> TokenTree {
         TTTok(span, ref tok) =>
             TTTok(span, fld.fold_token(tok.clone())),
         TTDelim(ref tts) => TTDelim(Rc::new(fld.fold_tts(tts.as_slice()))),
-        TTSeq(span, ref pattern, ref sep, is_optional) =>
+        TTSeq(span, ref pattern, ref sep, op) =>
             TTSeq(span,
                   Rc::new(fld.fold_tts(pattern.as_slice())),
                   sep.clone().map(|tok| fld.fold_token(tok)),
-                  is_optional),
+                  op),
         TTNonterminal(sp,ref ident) =>
             TTNonterminal(sp,fld.fold_ident(*ident))
     }
@@ -642,6 +642,9 @@ pub fn noop_fold_interpolated<T: Folder>(nt: token::Nonterminal, fld: &mut T)
         token::NtMeta(meta_item) => token::NtMeta(fld.fold_meta_item(meta_item)),
         token::NtPath(box path) => token::NtPath(box fld.fold_path(path)),
         token::NtTT(tt) => token::NtTT(P(fld.fold_tt(&*tt))),
+        token::NtLifetime(name) => token::NtLifetime(fld.fold_ident(name)),
+        token::NtLiteral(tts) => token::NtLiteral(fld.fold_tts(tts.as_slice())),
+        token::NtVis(tts) => token::NtVis(fld.fold_tts(tts.as_slice())),
         // it looks to me like we can leave out the matchers: token::NtMatchers(matchers)
         _ => nt
     }
@@ -1215,6 +1218,12 @@ pub fn noop_fold_expr<T: Folder>(Expr {id, node, span}: Expr, folder: &mut T) ->
                           folder.fold_block(body),
                           opt_ident.map(|i| folder.fold_ident(i)))
             }
+            ExprWhileLet(pat, expr, body, opt_ident) => {
+                ExprWhileLet(folder.fold_pat(pat),
+                             folder.fold_expr(expr),
+                             folder.fold_block(body),
+                             opt_ident.map(|i| folder.fold_ident(i)))
+            }
             ExprForLoop(pat, iter, body, opt_ident) => {
                 ExprForLoop(folder.fold_pat(pat),
                             folder.fold_expr(iter),
@@ -26,7 +26,7 @@ use ast::{ExprField, ExprTupField, ExprFnBlock, ExprIf, ExprIfLet, ExprIndex, Ex
 use ast::{ExprLit, ExprLoop, ExprMac};
 use ast::{ExprMethodCall, ExprParen, ExprPath, ExprProc};
 use ast::{ExprRepeat, ExprRet, ExprStruct, ExprTup, ExprUnary, ExprUnboxedFn};
-use ast::{ExprVec, ExprWhile, ExprForLoop, Field, FnDecl};
+use ast::{ExprVec, ExprWhile, ExprWhileLet, ExprForLoop, Field, FnDecl};
 use ast::{Once, Many};
 use ast::{FnUnboxedClosureKind, FnMutUnboxedClosureKind};
 use ast::{FnOnceUnboxedClosureKind};
@@ -2492,28 +2492,37 @@ impl<'a> Parser<'a> {
     }
 
     /// Parse an optional separator followed by a kleene-style
-    /// repetition token (+ or *).
-    pub fn parse_sep_and_zerok(&mut self) -> (Option<token::Token>, bool) {
-        fn parse_zerok(parser: &mut Parser) -> Option<bool> {
-            match parser.token {
-                token::BINOP(token::STAR) | token::BINOP(token::PLUS) => {
-                    let zerok = parser.token == token::BINOP(token::STAR);
-                    parser.bump();
-                    Some(zerok)
-                },
-                _ => None
-            }
+    /// repetition token (+, * or ?).
+    pub fn parse_sep_and_kleene_op(&mut self) -> (Option<token::Token>, ast::KleeneOp) {
+        fn parse_kleene_op(parser: &mut Parser) -> Option<ast::KleeneOp> {
+            // `$(...)?*` and `$(...)?+` use `?` as the separator
+            let repeated = parser.look_ahead(1, |t| {
+                *t == token::BINOP(token::STAR) || *t == token::BINOP(token::PLUS)
+            });
+            let op = match parser.token {
+                token::BINOP(token::STAR) => ast::ZeroOrMore,
+                token::BINOP(token::PLUS) => ast::OneOrMore,
+                token::QUESTION if !repeated => ast::ZeroOrOne,
+                _ => return None
+            };
+            parser.bump();
+            Some(op)
         };
 
-        match parse_zerok(self) {
-            Some(zerok) => return (None, zerok),
+        match parse_kleene_op(self) {
+            Some(op) => return (None, op),
             None => {}
         }
 
         let separator = self.bump_and_get();
-        match parse_zerok(self) {
-            Some(zerok) => (Some(separator), zerok),
-            None => self.fatal("expected `*` or `+`")
+        match parse_kleene_op(self) {
+            Some(ast::ZeroOrOne) => {
+                self.span_err(self.last_span,
+                              "a `?` repetition cannot have a separator");
+                (Some(separator), ast::ZeroOrOne)
+            }
+            Some(op) => (Some(separator), op),
+            None => self.fatal("expected `*`, `+` or `?`")
         }
     }
 
@@ -2558,11 +2567,15 @@ impl<'a> Parser<'a> {
                         seq_sep_none(),
                         |p| p.parse_token_tree()
                     );
-                    let (s, z) = p.parse_sep_and_zerok();
+                    let (s, op) = p.parse_sep_and_kleene_op();
                     let seq = match seq {
                         Spanned { node, .. } => node,
                     };
-                    TTSeq(mk_sp(sp.lo, p.span.hi), Rc::new(seq), s, z)
+                    TTSeq(mk_sp(sp.lo, p.span.hi), Rc::new(seq), s, op)
+                } else if p.is_keyword(keywords::Crate) {
+                    // `$crate`, a path to the crate defining the macro
+                    p.bump();
+                    TTNonterminal(sp, ast::Ident::new(keywords::Crate.to_name()))
                 } else {
                     TTNonterminal(sp, p.parse_ident())
                 }
@@ -2667,8 +2680,8 @@ impl<'a> Parser<'a> {
                 if ms.len() == 0u {
                     self.fatal("repetition body must be nonempty");
                 }
-                let (sep, zerok) = self.parse_sep_and_zerok();
-                MatchSeq(ms, sep, zerok, name_idx_lo, *name_idx)
+                let (sep, op) = self.parse_sep_and_kleene_op();
+                MatchSeq(ms, sep, op, name_idx_lo, *name_idx)
             } else {
                 let bound_to = self.parse_ident();
                 self.expect(&token::COLON);
@@ -2935,7 +2948,11 @@ impl<'a> Parser<'a> {
         self.mk_expr(lo, hi, ExprForLoop(pat, expr, loop_block, opt_ident))
     }
 
+    /// Parse a 'while' or 'while let' expression ('while' token already eaten)
     pub fn parse_while_expr(&mut self, opt_ident: Option<ast::Ident>) -> P<Expr> {
+        if self.is_keyword(keywords::Let) {
+            return self.parse_while_let_expr(opt_ident);
+        }
         let lo = self.last_span.lo;
         let cond = self.parse_expr_res(RESTRICTION_NO_STRUCT_LITERAL);
         let body = self.parse_block();
@@ -2943,6 +2960,18 @@ impl<'a> Parser<'a> {
         return self.mk_expr(lo, hi, ExprWhile(cond, body, opt_ident));
     }
 
+    /// Parse a 'while let' expression ('while' token already eaten)
+    pub fn parse_while_let_expr(&mut self, opt_ident: Option<ast::Ident>) -> P<Expr> {
+        let lo = self.last_span.lo;
+        self.expect_keyword(keywords::Let);
+        let pat = self.parse_pat();
+        self.expect(&token::EQ);
+        let expr = self.parse_expr_res(RESTRICTION_NO_STRUCT_LITERAL);
+        let body = self.parse_block();
+        let hi = body.span.hi;
+        return self.mk_expr(lo, hi, ExprWhileLet(pat, expr, body, opt_ident));
+    }
+
     pub fn parse_loop_expr(&mut self, opt_ident: Option<ast::Ident>) -> P<Expr> {
         let lo = self.last_span.lo;
         let body = self.parse_block();
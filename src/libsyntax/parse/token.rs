@@ -127,7 +127,12 @@ pub enum Nonterminal {
     NtMeta( P<ast::MetaItem>),
     NtPath(Box<ast::Path>),
     NtTT(   P<ast::TokenTree>), // needs P'ed to break a circularity
-    NtMatchers(Vec<ast::Matcher>)
+    NtMatchers(Vec<ast::Matcher>),
+    NtLifetime(Ident),
+    /// A literal, possibly negated, as the tokens it was made of
+    NtLiteral(Vec<ast::TokenTree>),
+    /// `pub` or nothing
+    NtVis(Vec<ast::TokenTree>)
 }
 
 impl fmt::Show for Nonterminal {
@@ -144,6 +149,9 @@ impl fmt::Show for Nonterminal {
             NtPath(..) => f.pad("NtPath(..)"),
             NtTT(..) => f.pad("NtTT(..)"),
             NtMatchers(..) => f.pad("NtMatchers(..)"),
+            NtLifetime(..) => f.pad("NtLifetime(..)"),
+            NtLiteral(..) => f.pad("NtLiteral(..)"),
+            NtVis(..) => f.pad("NtVis(..)"),
         }
     }
 }
@@ -264,7 +272,10 @@ pub fn to_string(t: &Token) -> String {
                     NtIdent(..) => s.push_str("identifier"),
                     NtPath(..) => fail!("should have been handled"),
                     NtTT(..) => s.push_str("tt"),
-                    NtMatchers(..) => s.push_str("matcher sequence")
+                    NtMatchers(..) => s.push_str("matcher sequence"),
+                    NtLifetime(..) => s.push_str("lifetime"),
+                    NtLiteral(..) => s.push_str("literal"),
+                    NtVis(..) => s.push_str("visibility")
                 };
                 s
             }
@@ -14,11 +14,17 @@
 
 #include "llvm/Support/CBindingWrapping.h"
 #include "llvm/Support/FileSystem.h"
+#include "llvm/MC/MCSubtargetInfo.h"
 #include "llvm/Target/TargetLibraryInfo.h"
 #include "llvm/Transforms/IPO/PassManagerBuilder.h"
 
 #include "llvm-c/Transforms/PassManagerBuilder.h"
 
+#include "llvm/IR/MDBuilder.h"
+#if LLVM_VERSION_MINOR >= 5
+#include "llvm/ProfileData/InstrProfReader.h"
+#endif
+
 using namespace llvm;
 
 extern cl::opt<bool> EnableARMEHABI;
@@ -72,7 +78,8 @@ LLVMRustCreateTargetMachine(const char *triple,
                             bool UseSoftFloat,
                             bool NoFramePointerElim,
                             bool FunctionSections,
-                            bool DataSections) {
+                            bool DataSections,
+                            bool CompressDebugSections) {
     std::string Error;
     Triple Trip(Triple::normalize(triple));
     const llvm::Target *TheTarget = TargetRegistry::lookupTarget(Trip.getTriple(),
@@ -82,6 +89,12 @@ LLVMRustCreateTargetMachine(const char *triple,
         return NULL;
     }
 
+    // `-C target-cpu=native` means the CPU rustc runs on, which LLVM only
+    // works out when asked to.
+    std::string CPU = cpu;
+    if (CPU == "native")
+        CPU = sys::getHostCPUName();
+
     TargetOptions Options;
     Options.NoFramePointerElim = NoFramePointerElim;
 #if LLVM_VERSION_MINOR < 5
@@ -94,7 +107,7 @@ LLVMRustCreateTargetMachine(const char *triple,
     }
 
     TargetMachine *TM = TheTarget->createTargetMachine(Trip.getTriple(),
-                                                       cpu,
+                                                       CPU,
                                                        feature,
                                                        Options,
                                                        RM,
@@ -102,6 +115,10 @@ LLVMRustCreateTargetMachine(const char *triple,
                                                        OptLevel);
     TM->setDataSections(DataSections);
     TM->setFunctionSections(FunctionSections);
+#if LLVM_VERSION_MINOR >= 5
+    // Only has an effect if LLVM was built with zlib
+    TM->setCompressDebugSections(CompressDebugSections);
+#endif
     return wrap(TM);
 }
 
@@ -110,6 +127,24 @@ LLVMRustDisposeTargetMachine(LLVMTargetMachineRef TM) {
     delete unwrap(TM);
 }
 
+// Whether code generated by `TM` may use the subtarget feature `feature`,
+// either because its CPU has it or because it was enabled explicitly.
+extern "C" bool
+LLVMRustHasFeature(LLVMTargetMachineRef TM, const char *feature) {
+    TargetMachine *Target = unwrap(TM);
+    MCSubtargetInfo *STI =
+        Target->getTarget().createMCSubtargetInfo(Target->getTargetTriple(),
+                                                  Target->getTargetCPU(),
+                                                  Target->getTargetFeatureString());
+    uint64_t Bits = STI->getFeatureBits();
+    // Toggling a feature that is on turns it off, along with the features that
+    // imply it; toggling one that is off turns on at least the feature itself.
+    // Unknown features leave the bits alone.
+    uint64_t Toggled = STI->ToggleFeature(feature);
+    delete STI;
+    return Toggled != Bits && (Toggled & ~Bits) == 0;
+}
+
 // Unfortunately, LLVM doesn't expose a C API to add the corresponding analysis
 // passes for a target to a pass manager. We export that functionality through
 // this function.
@@ -288,3 +323,273 @@ LLVMRustMarkAllFunctionsNounwind(LLVMModuleRef M) {
         }
     }
 }
+
+// Profile-guided optimization.
+//
+// With `-C profile-generate`, every function counts how often each of its
+// basic blocks runs, using the data layout of compiler-rt's profiling runtime,
+// which writes the counts to `default.profraw` (or `$LLVM_PROFILE_FILE`) when
+// the program exits. `llvm-profdata merge` turns one or more of those files
+// into the indexed profile read by `-C profile-use`, which annotates branches
+// with the counts of the blocks they lead to.
+//
+// Both passes run on the module as translated, before any other pass, so that
+// the same source gives the same blocks in the same order. A function whose
+// blocks have changed since the profile was taken is left alone.
+
+// A summary of the shape of `F`, to detect stale profiles.
+static uint64_t
+getCFGHash(Function &F) {
+    uint64_t Hash = F.size();
+    for (Function::iterator BB = F.begin(), E = F.end(); BB != E; ++BB)
+        Hash = Hash * 31 + BB->getTerminator()->getNumSuccessors();
+    return Hash;
+}
+
+static std::string
+getProfileSection(Module &M, const char *Name) {
+    std::string Section = Triple(M.getTargetTriple()).isOSDarwin() ? "__DATA," : "";
+    return Section + Name;
+}
+
+// Adds `Values` to `llvm.used`, keeping anything that's already there.
+static void
+appendToUsed(Module &M, ArrayRef<GlobalValue*> Values) {
+    Type *Int8PtrTy = Type::getInt8PtrTy(M.getContext());
+    std::vector<Constant*> Used;
+    if (GlobalVariable *GV = M.getGlobalVariable("llvm.used")) {
+        if (ConstantArray *Init = dyn_cast<ConstantArray>(GV->getInitializer()))
+            for (unsigned i = 0, e = Init->getNumOperands(); i != e; ++i)
+                Used.push_back(Init->getOperand(i));
+        GV->eraseFromParent();
+    }
+    for (unsigned i = 0, e = Values.size(); i != e; ++i)
+        Used.push_back(ConstantExpr::getBitCast(Values[i], Int8PtrTy));
+
+    ArrayType *ATy = ArrayType::get(Int8PtrTy, Used.size());
+    GlobalVariable *GV = new GlobalVariable(M, ATy, false,
+                                            GlobalValue::AppendingLinkage,
+                                            ConstantArray::get(ATy, Used),
+                                            "llvm.used");
+    GV->setSection("llvm.metadata");
+}
+
+namespace {
+
+struct RustProfileInstrumentation : public ModulePass {
+    static char ID;
+    RustProfileInstrumentation() : ModulePass(ID) {}
+
+    virtual bool runOnModule(Module &M);
+};
+
+struct RustProfileUse : public ModulePass {
+    static char ID;
+#if LLVM_VERSION_MINOR >= 5
+    std::unique_ptr<IndexedInstrProfReader> Reader;
+
+    RustProfileUse(std::unique_ptr<IndexedInstrProfReader> Reader)
+        : ModulePass(ID), Reader(std::move(Reader)) {}
+#endif
+
+    virtual bool runOnModule(Module &M);
+};
+
+}
+
+char RustProfileInstrumentation::ID = 0;
+char RustProfileUse::ID = 0;
+
+bool
+RustProfileInstrumentation::runOnModule(Module &M) {
+    LLVMContext &Ctx = M.getContext();
+    Type *Int32Ty = Type::getInt32Ty(Ctx);
+    Type *Int64Ty = Type::getInt64Ty(Ctx);
+    Type *Int8PtrTy = Type::getInt8PtrTy(Ctx);
+
+    // `__llvm_profile_data` in the runtime's InstrProfiling.h
+    Type *DataFields[] = {
+        Int32Ty,                   // NameSize
+        Int32Ty,                   // NumCounters
+        Int64Ty,                   // FuncHash
+        Int8PtrTy,                 // Name
+        Int64Ty->getPointerTo(),   // Counters
+    };
+    StructType *DataTy = StructType::get(Ctx, DataFields);
+
+    std::vector<GlobalValue*> Used;
+    for (Module::iterator F = M.begin(), E = M.end(); F != E; ++F) {
+        if (F->isDeclaration())
+            continue;
+        std::string Name = F->getName();
+
+        ArrayType *CountersTy = ArrayType::get(Int64Ty, F->size());
+        GlobalVariable *Counters =
+            new GlobalVariable(M, CountersTy, false, GlobalValue::PrivateLinkage,
+                               Constant::getNullValue(CountersTy),
+                               "__llvm_profile_counters_" + Name);
+        Counters->setSection(getProfileSection(M, "__llvm_prf_cnts"));
+        Counters->setAlignment(8);
+
+        Constant *NameStr = ConstantDataArray::getString(Ctx, Name, false);
+        GlobalVariable *NameVar =
+            new GlobalVariable(M, NameStr->getType(), true, GlobalValue::PrivateLinkage,
+                               NameStr, "__llvm_profile_name_" + Name);
+        NameVar->setSection(getProfileSection(M, "__llvm_prf_names"));
+        NameVar->setAlignment(1);
+
+        Constant *DataValues[] = {
+            ConstantInt::get(Int32Ty, Name.size()),
+            ConstantInt::get(Int32Ty, F->size()),
+            ConstantInt::get(Int64Ty, getCFGHash(*F)),
+            ConstantExpr::getBitCast(NameVar, Int8PtrTy),
+            ConstantExpr::getBitCast(Counters, Int64Ty->getPointerTo()),
+        };
+        GlobalVariable *Data =
+            new GlobalVariable(M, DataTy, true, GlobalValue::PrivateLinkage,
+                               ConstantStruct::get(DataTy, DataValues),
+                               "__llvm_profile_data_" + Name);
+        Data->setSection(getProfileSection(M, "__llvm_prf_data"));
+        Data->setAlignment(8);
+        Used.push_back(Data);
+
+        uint64_t Index = 0;
+        for (Function::iterator BB = F->begin(), BE = F->end(); BB != BE; ++BB) {
+            IRBuilder<> Builder(BB->getFirstInsertionPt());
+            Value *Addr = Builder.CreateConstInBoundsGEP2_64(Counters, 0, Index++);
+            Value *Count = Builder.CreateLoad(Addr);
+            Builder.CreateStore(Builder.CreateAdd(Count, ConstantInt::get(Int64Ty, 1)),
+                                Addr);
+        }
+    }
+
+    if (Used.empty())
+        return false;
+
+    // Referencing `__llvm_profile_runtime` pulls in the part of the runtime
+    // which writes the profile out when the program exits.
+    Constant *Runtime = M.getOrInsertGlobal("__llvm_profile_runtime", Int32Ty);
+    Function *User = Function::Create(FunctionType::get(Int32Ty, false),
+                                      GlobalValue::LinkOnceODRLinkage,
+                                      "__llvm_profile_runtime_user", &M);
+    User->setVisibility(GlobalValue::HiddenVisibility);
+    User->addFnAttr(Attribute::NoInline);
+    IRBuilder<> Builder(BasicBlock::Create(Ctx, "", User));
+    Builder.CreateRet(Builder.CreateLoad(Runtime));
+    Used.push_back(User);
+
+    appendToUsed(M, Used);
+    return true;
+}
+
+bool
+RustProfileUse::runOnModule(Module &M) {
+#if LLVM_VERSION_MINOR >= 5
+    MDBuilder MDB(M.getContext());
+    uint64_t MaxEntryCount = Reader->getMaximumFunctionCount();
+    bool Changed = false;
+
+    for (Module::iterator F = M.begin(), E = M.end(); F != E; ++F) {
+        if (F->isDeclaration())
+            continue;
+        std::vector<uint64_t> Counts;
+        if (Reader->getFunctionCounts(F->getName(), getCFGHash(*F), Counts) ||
+            Counts.size() != F->size())
+            continue;
+        Changed = true;
+
+        // The same thresholds as clang's
+        if (Counts[0] == 0 || Counts[0] * 100 <= MaxEntryCount)
+            F->addFnAttr(Attribute::Cold);
+        else if (Counts[0] * 10 >= MaxEntryCount * 3)
+            F->addFnAttr(Attribute::InlineHint);
+
+        DenseMap<BasicBlock*, uint64_t> BlockCounts;
+        uint64_t Index = 0;
+        for (Function::iterator BB = F->begin(), BE = F->end(); BB != BE; ++BB)
+            BlockCounts[BB] = Counts[Index++];
+
+        // The count of a successor is the count of the edge leading to it
+        // unless it has other predecessors; that's the case for the arms of
+        // conditionals and matches, which is where the weights matter most.
+        for (Function::iterator BB = F->begin(), BE = F->end(); BB != BE; ++BB) {
+            TerminatorInst *TI = BB->getTerminator();
+            if (TI->getNumSuccessors() < 2 ||
+                !(isa<BranchInst>(TI) || isa<SwitchInst>(TI)))
+                continue;
+
+            uint64_t MaxCount = 0;
+            for (unsigned i = 0, e = TI->getNumSuccessors(); i != e; ++i)
+                MaxCount = std::max(MaxCount, BlockCounts[TI->getSuccessor(i)]);
+            uint64_t Scale = MaxCount / UINT32_MAX + 1;
+
+            SmallVector<uint32_t, 4> Weights;
+            for (unsigned i = 0, e = TI->getNumSuccessors(); i != e; ++i)
+                Weights.push_back(BlockCounts[TI->getSuccessor(i)] / Scale + 1);
+            TI->setMetadata(LLVMContext::MD_prof, MDB.createBranchWeights(Weights));
+        }
+    }
+    return Changed;
+#else
+    return false;
+#endif
+}
+
+extern "C" void
+LLVMRustAddProfileInstrumentationPass(LLVMPassManagerRef PM) {
+    unwrap(PM)->add(new RustProfileInstrumentation());
+}
+
+extern "C" bool
+LLVMRustAddProfileUsePass(LLVMPassManagerRef PM, const char *Path) {
+#if LLVM_VERSION_MINOR >= 5
+    std::unique_ptr<IndexedInstrProfReader> Reader;
+    if (std::error_code EC = IndexedInstrProfReader::create(Path, Reader)) {
+        LLVMRustSetLastError(EC.message().c_str());
+        return false;
+    }
+    unwrap(PM)->add(new RustProfileUse(std::move(Reader)));
+    return true;
+#else
+    LLVMRustSetLastError("profile-guided optimization requires LLVM 3.5 or later");
+    return false;
+#endif
+}
+
+// Adds gcov instrumentation to the module. The pass only instruments
+// functions with debug info, and names its notes (.gcno) and data (.gcda)
+// files after the `llvm.gcov` entry of each compile unit, which we point at
+// the object file's directory so the counts end up next to the objects.
+extern "C" void
+LLVMRustAddGcovPass(LLVMPassManagerRef PM, LLVMModuleRef M, const char *NotesPath) {
+    Module *Mod = unwrap(M);
+    NamedMDNode *CUs = Mod->getNamedMetadata("llvm.dbg.cu");
+    if (CUs) {
+        LLVMContext &Ctx = Mod->getContext();
+        NamedMDNode *GCov = Mod->getOrInsertNamedMetadata("llvm.gcov");
+        for (unsigned i = 0, e = CUs->getNumOperands(); i != e; ++i) {
+            Value *Ops[] = { MDString::get(Ctx, NotesPath), CUs->getOperand(i) };
+            GCov->addOperand(MDNode::get(Ctx, Ops));
+        }
+    }
+    unwrap(PM)->add(createGCOVProfilerPass());
+}
+
+// The sanitizer passes only instrument functions with the matching
+// `sanitize_*` attribute, which trans adds to everything it defines.
+// AddressSanitizer also needs a module pass to put redzones around globals.
+extern "C" void
+LLVMRustAddAddressSanitizerPasses(LLVMPassManagerRef PM) {
+    unwrap(PM)->add(createAddressSanitizerFunctionPass());
+    unwrap(PM)->add(createAddressSanitizerModulePass());
+}
+
+extern "C" void
+LLVMRustAddThreadSanitizerPass(LLVMPassManagerRef PM) {
+    unwrap(PM)->add(createThreadSanitizerPass());
+}
+
+extern "C" void
+LLVMRustAddMemorySanitizerPass(LLVMPassManagerRef PM) {
+    unwrap(PM)->add(createMemorySanitizerPass());
+}
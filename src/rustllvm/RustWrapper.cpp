@@ -650,6 +650,18 @@ extern "C" void LLVMDICompositeTypeSetTypeArray(
 #endif
 }
 
+extern "C" void LLVMRustDICompositeTypeSetTemplateParams(
+    LLVMValueRef CompositeType,
+    LLVMValueRef TemplateParams)
+{
+    DICompositeType Ty = unwrapDI<DICompositeType>(CompositeType);
+#if LLVM_VERSION_MINOR >= 6
+    Ty.setArrays(Ty.getElements(), unwrapDI<DIArray>(TemplateParams));
+#else
+    Ty.setTypeArray(Ty.getTypeArray(), unwrapDI<DIArray>(TemplateParams));
+#endif
+}
+
 extern "C" void LLVMWriteTypeToString(LLVMTypeRef Type, RustStringRef str) {
     raw_rust_string_ostream os(str);
     unwrap<llvm::Type>(Type)->print(os);
@@ -907,6 +919,24 @@ extern "C" void LLVMWriteDebugLocToString(
     unwrap(dl)->print(*unwrap(C), os);
 }
 
+extern "C" bool LLVMRustUnpackDebugLoc(
+    LLVMContextRef C,
+    LLVMDebugLocRef dl,
+    unsigned *line_out,
+    unsigned *col_out,
+    RustStringRef file_out)
+{
+    DebugLoc *loc = unwrap(dl);
+    if (loc->isUnknown())
+        return false;
+
+    *line_out = loc->getLine();
+    *col_out = loc->getCol();
+    raw_rust_string_ostream os(file_out);
+    os << DIScope(loc->getScope(*unwrap(C))).getFilename();
+    return true;
+}
+
 DEFINE_SIMPLE_CONVERSION_FUNCTIONS(SMDiagnostic, LLVMSMDiagnosticRef)
 
 extern "C" void LLVMSetInlineAsmDiagnosticHandler(
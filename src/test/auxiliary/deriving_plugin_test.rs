@@ -0,0 +1,69 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// force-host
+
+#![feature(plugin_registrar)]
+
+extern crate syntax;
+extern crate rustc;
+
+use syntax::ast::{Item, MetaItem, Expr};
+use syntax::ast;
+use syntax::codemap::Span;
+use syntax::ext::base::ExtCtxt;
+use syntax::ext::build::AstBuilder;
+use syntax::ext::deriving::generic::{TraitDef, MethodDef, Substructure};
+use syntax::ext::deriving::generic::{combine_substructure, cs_same_method_fold};
+use syntax::ext::deriving::generic::ty::{Path, LifetimeBounds, Literal, borrowed_explicit_self};
+use syntax::ptr::P;
+use rustc::plugin::Registry;
+
+#[plugin_registrar]
+pub fn plugin_registrar(reg: &mut Registry) {
+    reg.register_deriving("TotalSum", box expand_deriving_total_sum);
+}
+
+// `deriving(TotalSum)` implements the local `TotalSum` trait by adding
+// up `total_sum()` of every field.
+fn expand_deriving_total_sum(cx: &mut ExtCtxt,
+                             span: Span,
+                             mitem: &MetaItem,
+                             item: &Item,
+                             push: |P<Item>|) {
+    let trait_def = TraitDef {
+        span: span,
+        attributes: Vec::new(),
+        path: Path::new_local("TotalSum"),
+        additional_bounds: Vec::new(),
+        generics: LifetimeBounds::empty(),
+        methods: vec!(
+            MethodDef {
+                name: "total_sum",
+                generics: LifetimeBounds::empty(),
+                explicit_self: borrowed_explicit_self(),
+                args: Vec::new(),
+                ret_ty: Literal(Path::new_local("int")),
+                attributes: Vec::new(),
+                combine_substructure: combine_substructure(|a, b, c| {
+                    total_sum_substructure(a, b, c)
+                })
+            })
+    };
+    trait_def.expand(cx, mitem, item, push)
+}
+
+fn total_sum_substructure(cx: &mut ExtCtxt, span: Span, substr: &Substructure) -> P<Expr> {
+    cs_same_method_fold(true,
+                        |cx, span, old, new| cx.expr_binary(span, ast::BiAdd, old, new),
+                        cx.expr_int(span, 0),
+                        |cx, span, _, _| cx.span_bug(span, "non-matching enum variants"),
+                        cx, span, substr)
+}
@@ -0,0 +1,53 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(asm)]
+
+struct Pair {
+    a: uint,
+    b: uint,
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub fn main() {
+    let p = Pair { a: 1, b: 2 };
+    let x: uint;
+    let y: u64;
+    let s: &str = "hello";
+    let mut w = 0u;
+    let mut wp: *mut uint = &mut w;
+    let wpp = &mut wp;
+    unsafe {
+        // Indirect operands are pointers to memory, and are fine.
+        asm!("" : "=*m"(*wpp));
+        asm!("" : "=&*m"(*wpp));
+        asm!("" :: "*m"(*wpp));
+        asm!("" :: "w"(1u)); //~ ERROR invalid constraint `w`
+        asm!("" :: "{foo}"(1u)); //~ ERROR unknown register `foo`
+        asm!("" ::: "bar"); //~ ERROR unknown register `bar` in clobber list
+        asm!("" :: "{al}"(1u32));
+        //~^ ERROR operand of type `u32` is too wide for constraint `{al}`
+        asm!("" :: "{ax}"(1u32));
+        //~^ ERROR operand of type `u32` is too wide for constraint `{ax}`
+        asm!("" :: "{eax}"(1u8));
+        asm!("" :: "r"(p)); //~ ERROR operand of type `Pair` cannot be passed in a register
+        asm!("" :: "r"(s)); //~ ERROR operand of type `&str` cannot be passed in a register
+        asm!("" : "=i"(x)); //~ ERROR output operand cannot use the immediate constraint `=i`
+        asm!("" :: "i"(1.5f64)); //~ ERROR immediate operand must be an integer, found `f64`
+        asm!("" : "=r"(y) : "0"(1u8));
+        //~^ ERROR input operand of type `u8` is tied to output operand 0
+        asm!("" :: "1"(1u));
+        //~^ ERROR input operand is tied to output operand 1, but there are only 0
+    }
+    println!("{} {} {}", x, y, p.a + p.b);
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+pub fn main() {}
@@ -0,0 +1,34 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(asm)]
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub fn main() {
+    let x: uint;
+    let y: uint;
+    let z: uint;
+    unsafe {
+        asm!("mov $[input], $[out]" //~ ERROR there is no operand named `input`
+             : [out] "=r"(x)
+             : [src] "r"(5u));
+        asm!("mov $2, $0"
+        //~^ ERROR invalid reference to operand `$2`: the assembly has 2 operands
+             : "=r"(y)
+             : "r"(5u));
+        asm!("mov $[a], $[b]"
+             : [b] "=r"(z)
+             : [a] "r"(5u), [a] "r"(6u)); //~ ERROR duplicate operand name `a`
+    }
+    println!("{} {} {}", x, y, z);
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+pub fn main() {}
@@ -0,0 +1,20 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+struct Config {
+    small: bool,
+}
+
+static CONFIG: Config = Config { small: true };
+
+static SIZE: uint = if CONFIG.small { 64 } else { 256 };
+//~^ ERROR condition of constant `if` is not a constant: unsupported constant expr
+
+fn main() {}
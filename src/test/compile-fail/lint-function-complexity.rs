@@ -0,0 +1,63 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![deny(cyclomatic_complexity, nesting_depth, function_length)]
+#![allow(dead_code)]
+
+#[complexity_threshold = "2"]
+fn branchy(x: int) -> int { //~ ERROR cyclomatic complexity of 3, the threshold is 2
+    if x > 0 {
+        1
+    } else if x < 0 {
+        -1
+    } else {
+        0
+    }
+}
+
+#[complexity_threshold = "3"]
+fn branchy_allowed(x: int) -> int {
+    if x > 0 { 1 } else if x < 0 { -1 } else { 0 }
+}
+
+#[nesting_threshold = "2"]
+fn nested(v: &[int]) -> int { //~ ERROR nests control flow 3 levels deep, the threshold is 2
+    let mut n = 0;
+    for &x in v.iter() {
+        if x > 0 {
+            match x {
+                1 => n += 1,
+                _ => {}
+            }
+        }
+    }
+    n
+}
+
+#[nesting_threshold = "1"]
+fn closures_not_counted(v: &[int]) -> uint {
+    if v.len() > 0 {
+        v.iter().filter(|&&x| if x > 0 { true } else { false }).count()
+    } else {
+        0
+    }
+}
+
+#[fn_length_threshold = "3"]
+fn long() { //~ ERROR function spans 5 lines, the threshold is 3
+    let _a = 1i;
+    let _b = 2i;
+    let _c = 3i;
+}
+
+#[complexity_threshold = "many"] //~ ERROR malformed `complexity_threshold` attribute
+fn malformed() {}
+
+fn main() {}
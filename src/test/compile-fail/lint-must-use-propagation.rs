@@ -0,0 +1,40 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:lint_must_use_xcrate.rs
+
+#![deny(unused_must_use, let_underscore_must_use)]
+#![allow(dead_code)]
+
+extern crate lint_must_use_xcrate;
+
+use lint_must_use_xcrate::{Token, token};
+
+#[must_use = "some message"]
+struct MustUse;
+
+fn foo<T>() -> T { fail!() }
+
+fn main() {
+    let _ = foo::<MustUse>(); //~ ERROR: unused result which must be used: some message
+    let _x = foo::<MustUse>();
+    let _ = foo::<int>();
+
+    foo::<Option<MustUse>>(); //~ ERROR: unused result containing a value which must be used
+    foo::<Vec<MustUse>>(); //~ ERROR: unused result containing a value which must be used
+    foo::<(int, Box<MustUse>)>(); //~ ERROR: unused result containing a value which must be used
+    foo::<Option<int>>();
+
+    [1i].iter().map(|_| foo::<MustUse>()); //~ ERROR: but the closure's result is discarded
+
+    token(); //~ ERROR: unused result which must be used: tokens must be redeemed
+    let _ = token(); //~ ERROR: unused result which must be used: tokens must be redeemed
+    foo::<Option<Token>>(); //~ ERROR: unused result containing a value which must be used
+}
@@ -0,0 +1,48 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "lib"]
+#![deny(reachable_failure)]
+#![allow(dead_code)]
+
+pub fn first(v: &[int]) -> int {
+    v[0] //~ ERROR indexing may fail the task, reachable from the public API through `first`
+}
+
+pub fn parse(s: Option<int>) -> int {
+    helper(s)
+}
+
+fn helper(s: Option<int>) -> int {
+    s.unwrap() //~ ERROR call to `Option::unwrap` may fail the task, reachable from the public API through `parse` -> `helper`
+}
+
+pub fn check(x: int) {
+    if x < 0 {
+        fail!("negative") //~ ERROR `fail!` may fail the task
+    }
+}
+
+pub fn allowed(s: Option<int>) -> int {
+    allowed_helper(s)
+}
+
+#[allow(reachable_failure)]
+fn allowed_helper(s: Option<int>) -> int {
+    s.unwrap()
+}
+
+fn private_only(s: Option<int>) -> int {
+    s.unwrap()
+}
+
+pub fn handled(s: Option<int>) -> int {
+    s.unwrap_or(0)
+}
@@ -0,0 +1,23 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(macro_rules)]
+
+macro_rules! separated(
+    ($($e:expr),?) => (()) //~ ERROR a `?` repetition cannot have a separator
+)
+
+macro_rules! optional(
+    ($($e:expr)?) => ($($e)?)
+)
+
+fn main() {
+    optional!(1i 2i); //~ ERROR no rules expected the token `2i`
+}
@@ -0,0 +1,29 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(macro_rules)]
+
+macro_rules! flatten(
+    ($($x:expr),*) => ($x) //~ WARNING variable `x` is still repeating at this depth
+    //~^ ERROR variable `x` is still repeating at this depth
+)
+
+macro_rules! flatten_inner(
+    ($($x:ident: [$($y:expr),*]);*) => ($($y),*) //~ WARNING variable `y` is still repeating
+)
+
+macro_rules! nothing_repeats(
+    ($x:expr) => ($($x)*)
+    //~^ WARNING attempted to repeat an expression containing no syntax variables
+)
+
+fn main() {
+    flatten!(1i, 2i);
+}
@@ -0,0 +1,54 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(advanced_slice_patterns)]
+
+fn variable_length_slice(list: &[Option<()>]) {
+    // Slices of length 4 and up are the first where the `None` and the
+    // `Some(_)` can't be at the same index.
+    match list {
+    //~^ ERROR non-exhaustive patterns: `[None, Some(_), None, _]` not covered
+        [] => {}
+        [_] => {}
+        [_, _] => {}
+        [_, None, ..] => {}
+        [.., Some(_), _] => {}
+    }
+}
+
+fn subslice_in_the_middle(list: &[bool]) {
+    match list {
+        [] => {}
+        [_] => {}
+        [false, ..] => {}
+        [.., false] => {}
+        [true, .., true] => {}
+    }
+}
+
+fn fixed_length_array(array: [bool, ..4]) {
+    match array {
+    //~^ ERROR non-exhaustive patterns: `[false, _, _, true]` not covered
+        [true, ..] => {}
+        [.., false] => {}
+    }
+    match array {
+        [true, ..] => {}
+        [false, .., true] => {}
+        [_, _, _, false] => {}
+    }
+    match array {
+        [false, .., true] => {}
+        [false, _, _, true] => {} //~ ERROR unreachable pattern
+        _ => {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,40 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![allow(experimental)]
+#![feature(intrinsics)]
+
+use std::simd::{f32x4, i16x8, i32x4};
+
+extern "rust-intrinsic" {
+    fn simd_eq<T, U>(x: T, y: T) -> U;
+    fn simd_shuffle4<T, U>(x: T, y: T, idx: [u32, ..4]) -> U;
+    fn simd_extract<T, E>(x: T, idx: u32) -> E;
+    fn simd_sqrt<T>(x: T) -> T;
+}
+
+unsafe fn f(idx: [u32, ..4]) {
+    let v = f32x4(1.0, 2.0, 3.0, 4.0);
+
+    let _: i16x8 = simd_eq(v, v);
+    //~^ ERROR invalid use of `simd_eq` intrinsic: expected a SIMD type of 4 integers
+    let _: i32x4 = simd_eq(1u32, 2u32);
+    //~^ ERROR invalid use of `simd_eq` intrinsic: expected a SIMD type, found `u32`
+    let _: f32x4 = simd_shuffle4(v, v, idx);
+    //~^ ERROR invalid use of `simd_shuffle4` intrinsic: the lane indices must be an array literal
+    let _: f32x4 = simd_shuffle4(v, v, [0, 1, 2, 8]);
+    //~^ ERROR invalid use of `simd_shuffle4` intrinsic: lane index 8 is out of bounds
+    let _: f64 = simd_extract(v, 0);
+    //~^ ERROR invalid use of `simd_extract` intrinsic: expected `f32` to extract, found `f64`
+    let _ = simd_sqrt(i32x4(1, 4, 9, 16));
+    //~^ ERROR invalid use of `simd_sqrt` intrinsic: expected a SIMD type of floats
+}
+
+fn main() {}
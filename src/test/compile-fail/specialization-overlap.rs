@@ -0,0 +1,45 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that `#[default_impl]` only permits overlap with impls that are
+// strictly more specific than it.
+
+#![feature(specialization)]
+
+trait Foo {
+    fn foo(&self) {}
+}
+
+// Neither impl is marked as a default.
+impl<T> Foo for Vec<T> {} //~ ERROR conflicting implementations
+
+impl Foo for Vec<u8> {} //~ NOTE conflicting implementation here
+
+trait Bar {
+    fn bar(&self) {}
+}
+
+// The default impl is the more specific of the two.
+impl<T> Bar for T {} //~ ERROR conflicting implementations
+
+#[default_impl]
+impl Bar for int {} //~ NOTE conflicting implementation here
+
+trait Baz {
+    fn baz(&self) {}
+}
+
+// The impls overlap, but neither is more specific than the other.
+#[default_impl]
+impl<T> Baz for (T, int) {} //~ ERROR conflicting implementations
+
+impl<T> Baz for (int, T) {} //~ NOTE conflicting implementation here
+
+fn main() {}
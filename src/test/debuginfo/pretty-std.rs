@@ -0,0 +1,146 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-windows failing on win32 bot
+// ignore-tidy-linelength
+// ignore-android: FIXME(#10381)
+// compile-flags:-g
+// gdb-use-pretty-printer
+
+// The printers below use some GDB Python API features (e.g. accessing anonymous
+// fields) which are only available in newer GDB versions:
+// min-gdb-version 7.7
+
+// === GDB TESTS ===================================================================================
+
+// gdb-command: set print pretty off
+// gdb-command: rbreak zzz
+// gdb-command: run
+// gdb-command: finish
+
+// gdb-command: print vec
+// gdb-check:$1 = Vec<u32>(len: 4, cap: [...]) = {4, 5, 6, 7}
+
+// gdb-command: print string
+// gdb-check:$2 = "IAMA string!"
+
+// gdb-command: print slice
+// gdb-check:$3 = &[u32](len: 3) = {5, 6, 7}
+
+// gdb-command: print some_string
+// gdb-check:$4 = Some = {"IAMA optional string!"}
+
+// gdb-command: print none_string
+// gdb-check:$5 = None
+
+// gdb-command: print some_box
+// gdb-check:$6 = Some = {[...]}
+
+// gdb-command: print hash_map
+// gdb-check:$7 = HashMap<[...]>(len: 1) = {[42] = "the answer"}
+
+// gdb-command: print rc
+// gdb-check:$8 = Rc<i32>(strong: 2, weak: 2) = {5}
+
+// gdb-command: print arc
+// gdb-check:$9 = Arc<i32>(strong: 1, weak: 1) = {6}
+
+// gdb-command: print tree_map
+// gdb-check:$10 = TreeMap<i32, i32>(len: 3) = {[1] = 10, [2] = 20, [3] = 30}
+
+// gdb-command: print ring_buf
+// gdb-check:$11 = RingBuf<i32>(len: 3) = {0, 1, 2}
+
+
+// === LLDB TESTS ==================================================================================
+
+// lldb-command:run
+
+// lldb-command:print vec
+// lldb-check:[...]$0 = vec![4, 5, 6, 7]
+
+// lldb-command:print string
+// lldb-check:[...]$1 = "IAMA string!"
+
+// lldb-command:print slice
+// lldb-check:[...]$2 = &[5, 6, 7]
+
+// lldb-command:print some_string
+// lldb-check:[...]$3 = Some("IAMA optional string!")
+
+// lldb-command:print none_string
+// lldb-check:[...]$4 = None
+
+// lldb-command:print hash_map
+// lldb-check:[...]$5 = {42: "the answer"}
+
+// lldb-command:print rc
+// lldb-check:[...]$6 = Rc(strong: 2, weak: 2, 5)
+
+// lldb-command:print arc
+// lldb-check:[...]$7 = Arc(strong: 1, weak: 1, 6)
+
+// lldb-command:print tree_map
+// lldb-check:[...]$8 = {1: 10, 2: 20, 3: 30}
+
+// lldb-command:print ring_buf
+// lldb-check:[...]$9 = [0, 1, 2]
+
+#![allow(unused_variable)]
+
+use std::collections::{HashMap, TreeMap, RingBuf, Deque};
+use std::rc::Rc;
+use std::sync::Arc;
+
+fn main() {
+
+    // Vec
+    let vec = vec![4u32, 5, 6, 7];
+
+    // String
+    let string = "IAMA string!".to_string();
+
+    // Slice
+    let slice = vec.slice(1, 4);
+
+    // Option
+    let some_string = Some("IAMA optional string!".to_string());
+    let none_string: Option<String> = None;
+    let some_box = Some(box 8i);
+
+    // HashMap
+    let mut hash_map = HashMap::new();
+    hash_map.insert(42u64, "the answer".to_string());
+
+    // Rc
+    let rc = Rc::new(5i32);
+    let rc_clone = rc.clone();
+    let rc_weak = rc.downgrade();
+
+    // Arc
+    let arc = Arc::new(6i32);
+
+    // TreeMap
+    let mut tree_map = TreeMap::new();
+    tree_map.insert(3i32, 30i32);
+    tree_map.insert(1, 10);
+    tree_map.insert(2, 20);
+
+    // RingBuf
+    let mut ring_buf = RingBuf::new();
+    ring_buf.push(1i32);
+    ring_buf.push(2);
+    // Makes the elements wrap around the end of the ring buffer's storage
+    ring_buf.push_front(0);
+
+    zzz(); // #break
+}
+
+fn zzz() { () }
@@ -0,0 +1,56 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-tidy-linelength
+// ignore-lldb
+// ignore-android: FIXME(#10381)
+
+// compile-flags:-g
+// gdb-command:rbreak zzz
+// gdb-command:run
+// gdb-command:finish
+
+// The vtable's symbol tells which type is behind a trait object.
+// gdb-command:print reference.vtable
+// gdb-check:$1 = (uint *) [...] <vtable for <trait-objects::Struct as Trait>>
+// gdb-command:print unique.vtable
+// gdb-check:$2 = (uint *) [...] <vtable for <trait-objects::TupleStruct as Trait>>
+
+// The vtable header: the drop glue, then the size and alignment of the type.
+// gdb-command:print reference.vtable[1]
+// gdb-check:$3 = 8
+// gdb-command:print unique.vtable[2]
+// gdb-check:$4 = 2
+
+#![allow(unused_variable)]
+
+trait Trait {
+    fn method(&self) -> int { 0 }
+}
+
+struct Struct {
+    a: i32,
+    b: f32
+}
+
+struct TupleStruct(u16, u16, u16);
+
+impl Trait for Struct {}
+impl Trait for TupleStruct {}
+
+fn main() {
+    let stack_struct = Struct { a: 0, b: 1.5 };
+    let reference: &Trait = &stack_struct as &Trait;
+    let unique: Box<Trait> = box TupleStruct(1, 2, 3) as Box<Trait>;
+
+    zzz(); // #break
+}
+
+fn zzz() {()}
@@ -0,0 +1,86 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-lldb
+// ignore-android: FIXME(#10381)
+
+// compile-flags:-g
+// gdb-command:rbreak zzz
+// gdb-command:run
+
+// CAPTURED BY VALUE
+// gdb-command:finish
+// gdb-command:print constant
+// gdb-check:$1 = 1
+// gdb-command:print a_struct
+// gdb-check:$2 = {a = -2, b = 3.5, c = 4}
+// gdb-command:print *owned
+// gdb-check:$3 = 5
+// gdb-command:continue
+
+// CAPTURED BY REFERENCE
+// gdb-command:finish
+// gdb-command:print constant
+// gdb-check:$4 = 1
+// gdb-command:print another_struct
+// gdb-check:$5 = {a = 6, b = 7.5, c = 8}
+// gdb-command:continue
+
+// THE ENVIRONMENT
+// gdb-command:finish
+// gdb-command:print by_value
+// gdb-check:$6 = {constant = 1, a_struct = {a = -2, b = 3.5, c = 4}, owned = [...]}
+
+#![feature(overloaded_calls, unboxed_closures)]
+#![allow(unused_variable)]
+
+struct Struct {
+    a: int,
+    b: f64,
+    c: uint
+}
+
+fn main() {
+    let constant = 1i;
+
+    let a_struct = Struct {
+        a: -2,
+        b: 3.5,
+        c: 4
+    };
+
+    let owned = box 5i;
+
+    let another_struct = Struct {
+        a: 6,
+        b: 7.5,
+        c: 8
+    };
+
+    let by_value = move |&:| {
+        zzz(); // #break
+        do_something(&constant, &a_struct.a, &*owned);
+    };
+    by_value();
+
+    let by_ref = |&:| {
+        zzz(); // #break
+        do_something(&constant, &another_struct.a, &constant);
+    };
+    by_ref();
+
+    zzz(); // #break
+}
+
+fn do_something(_: &int, _:&int, _:&int) {
+
+}
+
+fn zzz() {()}
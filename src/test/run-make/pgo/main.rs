@@ -0,0 +1,23 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+fn classify(n: uint) -> &'static str {
+    if n % 10 == 0 { "round" } else { "other" }
+}
+
+fn main() {
+    let mut round = 0u;
+    for i in range(0u, 1000) {
+        if classify(i) == "round" {
+            round += 1;
+        }
+    }
+    assert_eq!(round, 100);
+}
@@ -0,0 +1,23 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::os;
+
+#[inline(never)]
+fn read(xs: &[u8, ..4], i: int) -> u8 {
+    unsafe { *xs.as_ptr().offset(i) }
+}
+
+fn main() {
+    let xs = [1u8, 2, 3, 4];
+    // One past the end, but not known at compile time.
+    let i = os::args().len() as int + 3;
+    println!("{}", read(&xs, i));
+}
@@ -0,0 +1,24 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "lib"]
+#![feature(simd, intrinsics)]
+
+#[simd]
+#[allow(non_camel_case_types)]
+pub struct f32x8(pub f32, pub f32, pub f32, pub f32, pub f32, pub f32, pub f32, pub f32);
+
+extern "rust-intrinsic" {
+    fn simd_fmin<T>(x: T, y: T) -> T;
+}
+
+pub fn min(x: f32x8, y: f32x8) -> f32x8 {
+    unsafe { simd_fmin(x, y) }
+}
@@ -0,0 +1,33 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "lib"]
+
+extern crate libc;
+
+use std::mem;
+
+static mut COUNTER: uint = 0;
+
+pub unsafe fn bump() {
+    COUNTER += 1;
+}
+
+pub fn read(p: *const u8) -> u8 {
+    unsafe { *p }
+}
+
+pub fn pid() -> libc::c_int {
+    unsafe { libc::getpid() }
+}
+
+pub fn bits(f: f32) -> u32 {
+    unsafe { mem::transmute(f) }
+}
@@ -0,0 +1,52 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:deriving_plugin_test.rs
+// ignore-stage1
+
+#![feature(phase)]
+
+#[phase(plugin)]
+extern crate deriving_plugin_test;
+
+trait TotalSum {
+    fn total_sum(&self) -> int;
+}
+
+impl TotalSum for int {
+    fn total_sum(&self) -> int { *self }
+}
+
+#[deriving(PartialEq, Show, TotalSum)]
+struct Point {
+    x: int,
+    y: int,
+}
+
+#[deriving(PartialEq, Show, TotalSum)]
+struct Line(Point, Point);
+
+#[deriving(TotalSum)]
+enum Shape {
+    Dot(Point),
+    Segment(Line, int),
+    Empty,
+}
+
+pub fn main() {
+    let a = Point { x: 1, y: 2 };
+    let b = Point { x: 3, y: 4 };
+    assert_eq!(a.total_sum(), 3);
+    assert_eq!(Line(a, b).total_sum(), 10);
+    assert_eq!(Line(a, b), Line(a, b));
+    assert_eq!(Dot(b).total_sum(), 7);
+    assert_eq!(Segment(Line(a, b), 5).total_sum(), 15);
+    assert_eq!(Empty.total_sum(), 0);
+}
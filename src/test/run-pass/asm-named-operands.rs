@@ -0,0 +1,43 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(asm)]
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub fn main() {
+    let x: uint;
+    let mut y: uint = 5;
+    unsafe {
+        // Named operands can be referred to by name or by position.
+        asm!("mov $[src], $[dst]; add $2, $0"
+             : [dst] "=&r"(x)
+             : [src] "r"(y), "r"(1u));
+    }
+    assert_eq!(x, 6);
+
+    unsafe {
+        // A read+write output keeps its position; its tied input comes last.
+        asm!("add ${inc}, ${acc}; shl $$1, $0"
+             : [acc] "+r"(y)
+             : [inc] "ir"(2u)
+             : "cc");
+    }
+    assert_eq!(y, 14);
+
+    let z: u8;
+    unsafe {
+        // Byte registers hold byte operands.
+        asm!("mov $$7, $0" : "={al}"(z));
+    }
+    assert_eq!(z, 7);
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+pub fn main() {}
@@ -0,0 +1,48 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that array lengths can be computed from constants defined in
+// other crates, using casts, comparisons and conditionals.
+
+// aux-build:cci_const_eval.rs
+
+extern crate cci_const_eval;
+
+use cci_const_eval::{BUF_SIZE, HEADER_SIZE, MAX_PACKETS};
+
+static PACKET_SIZE: uint = BUF_SIZE * 2 + HEADER_SIZE as uint;
+static SMALL_BUF: bool = BUF_SIZE < 1024;
+static CHUNK_SIZE: uint = if SMALL_BUF { 64 } else { 256 };
+
+struct Packet {
+    header: [u8, ..HEADER_SIZE as uint],
+    body: [u8, ..BUF_SIZE * 2],
+}
+
+pub fn main() {
+    let p = Packet {
+        header: [0, ..HEADER_SIZE as uint],
+        body: [0, ..BUF_SIZE * 2],
+    };
+    assert_eq!(p.header.len(), 16);
+    assert_eq!(p.body.len(), 1024);
+
+    let packet: [u8, ..PACKET_SIZE] = [0, ..PACKET_SIZE];
+    assert_eq!(packet.len(), 1040);
+
+    let chunk: [u8, ..CHUNK_SIZE] = [0, ..64];
+    assert_eq!(chunk.len(), 64);
+
+    let packets: [uint, ..MAX_PACKETS] = [0, ..8];
+    assert_eq!(packets.len(), 8);
+
+    let flags: [bool, ..if HEADER_SIZE >= 16 { 2 } else { 1 }] = [true, false];
+    assert_eq!(flags.len(), 2);
+}
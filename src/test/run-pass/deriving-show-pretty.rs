@@ -0,0 +1,78 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(struct_variant)]
+
+use std::collections::TreeMap;
+
+#[deriving(Show)]
+struct Unit;
+
+#[deriving(Show)]
+struct Point(int, int);
+
+#[deriving(Show)]
+struct Config {
+    name: &'static str,
+    origin: Point,
+    shapes: Vec<Shape>,
+}
+
+#[deriving(Show)]
+enum Shape {
+    Empty,
+    Circle { radius: uint },
+}
+
+pub fn main() {
+    let config = Config {
+        name: "test",
+        origin: Point(1, 2),
+        shapes: vec!(Empty, Circle { radius: 3 }),
+    };
+
+    // Without the alternate flag everything stays on one line.
+    assert_eq!(format!("{}", config).as_slice(),
+               "Config { name: test, origin: Point(1, 2), shapes: [Empty, Circle { radius: 3 }] }");
+
+    assert_eq!(format!("{:#}", config).as_slice(),
+"Config {
+    name: test,
+    origin: Point(
+        1,
+        2
+    ),
+    shapes: [
+        Empty,
+        Circle {
+            radius: 3
+        }
+    ]
+}");
+
+    assert_eq!(format!("{:#}", Unit).as_slice(), "Unit");
+    assert_eq!(format!("{:#}", Vec::<int>::new()).as_slice(), "[]");
+
+    let mut map = TreeMap::new();
+    map.insert(1i, Point(3, 4));
+    map.insert(2i, Point(5, 6));
+    assert_eq!(format!("{}", map).as_slice(), "{1: Point(3, 4), 2: Point(5, 6)}");
+    assert_eq!(format!("{:#}", map).as_slice(),
+"{
+    1: Point(
+        3,
+        4
+    ),
+    2: Point(
+        5,
+        6
+    )
+}");
+}
@@ -0,0 +1,73 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![allow(dead_code)]
+
+use std::mem::size_of;
+
+enum Tri { A, B, C }
+
+enum Cases { Data(char, u8), Empty1, Empty2, Empty3 }
+
+struct Padded { a: u8, b: u32, c: u8, d: u32 }
+
+#[repr(C)]
+struct PaddedC { a: u8, b: u32, c: u8, d: u32 }
+
+struct Unsized<Sized? T> { a: u8, b: u32, c: T }
+
+static CONST_NONE: Option<bool> = None;
+static CONST_SOME: Option<bool> = Some(false);
+
+fn is_none<T>(o: &Option<T>) -> bool {
+    match *o { None => true, Some(_) => false }
+}
+
+fn main() {
+    // Invalid values of bools, chars and discriminants hold the other variants
+    assert_eq!(size_of::<Option<bool>>(), 1);
+    assert_eq!(size_of::<Option<Option<bool>>>(), 1);
+    assert_eq!(size_of::<Option<char>>(), 4);
+    assert_eq!(size_of::<Option<Tri>>(), 1);
+    assert_eq!(size_of::<Option<Option<int>>>(), size_of::<Option<int>>());
+    assert_eq!(size_of::<Option<(u32, bool)>>(), size_of::<(u32, bool)>());
+    assert_eq!(size_of::<Cases>(), size_of::<(char, u8)>());
+
+    let values = [Some(Some(true)), Some(Some(false)), Some(None), None];
+    let expected = [(false, false), (false, false), (false, true), (true, true)];
+    for (v, &(outer, inner)) in values.iter().zip(expected.iter()) {
+        assert_eq!(is_none(v), outer);
+        assert_eq!(is_none(&(*v).unwrap_or(None)), inner);
+    }
+    assert_eq!(Some(Some(true)).unwrap().unwrap(), true);
+
+    match Empty2 {
+        Data(..) | Empty1 | Empty3 => fail!(),
+        Empty2 => {}
+    }
+    match Data('\U0010FFFF', 7) {
+        Data(c, n) => { assert_eq!(c, '\U0010FFFF'); assert_eq!(n, 7); }
+        _ => fail!()
+    }
+
+    assert!(is_none(&CONST_NONE));
+    assert_eq!(CONST_SOME, Some(false));
+
+    // Fields are reordered to avoid padding, unless asked not to
+    assert_eq!(size_of::<Padded>(), 12);
+    assert_eq!(size_of::<PaddedC>(), 16);
+    let p = Padded { a: 1, b: 2, c: 3, d: 4 };
+    assert_eq!((p.a, p.b, p.c, p.d), (1, 2, 3, 4));
+
+    // The last field stays last, so that it can be unsized
+    let s: &Unsized<[u8]> = &Unsized { a: 1, b: 2, c: [3u8, 4, 5] };
+    assert_eq!((s.a, s.b), (1, 2));
+    assert_eq!((s.c.len(), s.c[0], s.c[2]), (3, 3, 5));
+}
@@ -0,0 +1,29 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:macro_dollar_crate.rs
+
+#![feature(phase)]
+
+#[phase(plugin, link)]
+extern crate renamed = "macro_dollar_crate";
+
+mod nested {
+    pub fn check() {
+        assert_eq!(increment!(2), 3);
+    }
+}
+
+pub fn main() {
+    assert_eq!(increment!(1), 2);
+    assert_eq!(answer!(), 42);
+    assert_eq!(renamed::inner::twice(1), 3);
+    nested::check();
+}
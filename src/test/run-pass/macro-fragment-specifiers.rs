@@ -0,0 +1,86 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(macro_rules)]
+
+macro_rules! constant(
+    ($v:vis $name:ident = $value:literal) => (
+        $v static $name: int = $value;
+    )
+)
+
+constant!(pub ANSWER = 42)
+constant!(NEGATIVE = -1)
+
+// The tokens right after a spliced `vis` or `literal` must survive
+macro_rules! getter(
+    ($v:vis $name:ident -> $value:literal) => (
+        $v fn $name() -> [int, ..2] { [$value, $value + 1] }
+    )
+)
+
+getter!(pub public_pair -> 10)
+getter!(private_pair -> -10)
+
+macro_rules! matches_literal(
+    ($e:expr, $l:literal) => (
+        match $e {
+            $l => true,
+            _ => false
+        }
+    )
+)
+
+macro_rules! first_fn(
+    ($lt:lifetime) => (
+        fn first<$lt>(v: &$lt [int]) -> &$lt int { &v[0] }
+    )
+)
+
+first_fn!('a)
+
+macro_rules! plus_one(
+    ($($value:expr)?) => (1i $(+ $value)?)
+)
+
+macro_rules! count(
+    ($($e:expr),* $(,)?) => (0u $(+ { $e; 1u })*)
+)
+
+macro_rules! pairs(
+    ($($outer:ident: [$($inner:expr),*]);*) => (
+        vec![$($((stringify!($outer), $inner)),*),*]
+    )
+)
+
+pub fn main() {
+    assert_eq!(ANSWER, 42);
+    assert_eq!(NEGATIVE, -1);
+    let (a, b) = (public_pair(), private_pair());
+    assert_eq!((a[0], a[1], b[0], b[1]), (10, 11, -10, -9));
+
+    assert!(matches_literal!(3i, 3));
+    assert!(matches_literal!(-3i, -3));
+    assert!(matches_literal!("hi", "hi"));
+    assert!(matches_literal!(true, true));
+    assert!(!matches_literal!(4i, 3));
+
+    assert_eq!(*first(&[5, 6]), 5);
+
+    assert_eq!(plus_one!(), 1);
+    assert_eq!(plus_one!(2 * 3), 7);
+
+    assert_eq!(count!(), 0);
+    assert_eq!(count!(1i, 2i), 2);
+    assert_eq!(count!(1i, 2i, 3i,), 3);
+
+    assert_eq!(pairs!(a: [1i, 2]; b: [3]),
+               vec![("a", 1), ("a", 2), ("b", 3)]);
+}
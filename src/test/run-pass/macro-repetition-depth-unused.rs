@@ -0,0 +1,23 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Arms with mismatched repetition depths only get a warning when they are
+// never expanded.
+
+#![feature(macro_rules)]
+
+macro_rules! first_or_flatten(
+    ($x:expr) => ($x);
+    ($($x:expr),*) => ($x)
+)
+
+pub fn main() {
+    assert_eq!(first_or_flatten!(1i), 1);
+}
@@ -25,6 +25,38 @@ fn silent_recurse() {
     silent_recurse();
 }
 
+// A frame larger than the guard page, which is only caught if it is probed.
+fn huge_recurse() {
+    let buf = [0i, ..100000];
+    black_box(buf);
+    huge_recurse();
+}
+
+// Slots smaller than a page that together make a frame larger than the guard
+// page.
+fn many_recurse() {
+    let (a, b, c, d) = ([0u8, ..2000], [0u8, ..2000], [0u8, ..2000], [0u8, ..2000]);
+    black_box((a, b, c, d));
+    many_recurse();
+}
+
+// Frames larger than the guard page whose slots are only needed in a loop or
+// in a branch picked at run time, which trans still allocates up front.
+fn nested_recurse(n: uint) {
+    let mut i = 0;
+    while i < n {
+        if n % 2 == 0 {
+            let buf = [0i, ..100000];
+            black_box(buf);
+        } else {
+            let (a, b) = ([0u8, ..3000], [0u8, ..3000]);
+            black_box((a, b));
+        }
+        i += 1;
+    }
+    nested_recurse(n);
+}
+
 fn loud_recurse() {
     println!("hello!");
     loud_recurse();
@@ -36,6 +68,12 @@ fn main() {
     let args = args.as_slice();
     if args.len() > 1 && args[1].as_slice() == "silent" {
         silent_recurse();
+    } else if args.len() > 1 && args[1].as_slice() == "huge" {
+        huge_recurse();
+    } else if args.len() > 1 && args[1].as_slice() == "many" {
+        many_recurse();
+    } else if args.len() > 1 && args[1].as_slice() == "nested" {
+        nested_recurse(args.len());
     } else if args.len() > 1 && args[1].as_slice() == "loud" {
         loud_recurse();
     } else {
@@ -47,6 +85,34 @@ fn main() {
             assert!(error.as_slice().contains("has overflowed its stack"));
         }
 
+        let huge = Command::new(args[0].as_slice()).arg("huge").output().unwrap();
+        assert!(!huge.status.success());
+        let error = String::from_utf8_lossy(huge.error.as_slice());
+        // FIXME #17562: Windows is using stack probes and isn't wired up to print an error
+        if !cfg!(windows) {
+            assert!(error.as_slice().contains("has overflowed its stack"));
+        }
+
+        let many = Command::new(args[0].as_slice()).arg("many").output().unwrap();
+        assert!(!many.status.success());
+        let error = String::from_utf8_lossy(many.error.as_slice());
+        // FIXME #17562: Windows is using stack probes and isn't wired up to print an error
+        if !cfg!(windows) {
+            assert!(error.as_slice().contains("has overflowed its stack"));
+        }
+
+        // One extra argument takes the branch with the one large array, two take
+        // the one with several arrays smaller than a page.
+        for extra in [vec!["nested"], vec!["nested", "odd"]].iter() {
+            let nested = Command::new(args[0].as_slice()).args(extra.as_slice()).output().unwrap();
+            assert!(!nested.status.success());
+            let error = String::from_utf8_lossy(nested.error.as_slice());
+            // FIXME #17562: Windows is using stack probes and isn't wired up to print an error
+            if !cfg!(windows) {
+                assert!(error.as_slice().contains("has overflowed its stack"));
+            }
+        }
+
         let loud = Command::new(args[0].as_slice()).arg("loud").output().unwrap();
         assert!(!loud.status.success());
         let error = String::from_utf8_lossy(silent.error.as_slice());
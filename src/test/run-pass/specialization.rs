@@ -0,0 +1,80 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that a more specific impl takes precedence over a
+// `#[default_impl]`, both when called directly and from generic code.
+
+#![feature(specialization)]
+
+trait Describe {
+    fn describe(&self) -> &'static str;
+}
+
+#[default_impl]
+impl<T> Describe for Vec<T> {
+    fn describe(&self) -> &'static str { "vec" }
+}
+
+impl Describe for Vec<u8> {
+    fn describe(&self) -> &'static str { "bytes" }
+}
+
+#[default_impl]
+impl<T> Describe for T {
+    fn describe(&self) -> &'static str { "other" }
+}
+
+impl Describe for int {
+    fn describe(&self) -> &'static str { "int" }
+}
+
+// The specializing impl's own bounds show that it is covered by the
+// default impl.
+trait Copied {
+    fn copied(&self) -> &'static str;
+}
+
+#[default_impl]
+impl<T: Clone> Copied for T {
+    fn copied(&self) -> &'static str { "clone" }
+}
+
+impl<T: Clone> Copied for Vec<T> {
+    fn copied(&self) -> &'static str { "vec of clones" }
+}
+
+fn describe<T: Describe>(x: &T) -> &'static str {
+    x.describe()
+}
+
+fn describe_vec<T>(v: &Vec<T>) -> &'static str {
+    v.describe()
+}
+
+pub fn main() {
+    let bytes: Vec<u8> = vec![1, 2, 3];
+    let chars: Vec<char> = vec!['a', 'b'];
+
+    assert_eq!(bytes.describe(), "bytes");
+    assert_eq!(chars.describe(), "vec");
+    assert_eq!(3i.describe(), "int");
+    assert_eq!(true.describe(), "other");
+
+    assert_eq!(describe(&bytes), "bytes");
+    assert_eq!(describe(&chars), "vec");
+    assert_eq!(describe(&3i), "int");
+    assert_eq!(describe(&'x'), "other");
+
+    assert_eq!(describe_vec(&bytes), "bytes");
+    assert_eq!(describe_vec(&chars), "vec");
+
+    assert_eq!(1i.copied(), "clone");
+    assert_eq!(bytes.copied(), "vec of clones");
+}